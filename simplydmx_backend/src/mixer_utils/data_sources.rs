@@ -1,10 +1,15 @@
 use super::state::{FullMixerBlendingData, FullMixerOutput};
+use crate::plugins::patcher::VisualizationInfo;
+use rustc_hash::FxHashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
 pub struct LayerDataSources {
 	pub base_layer: RwLock<Arc<FullMixerOutput>>,
 	pub blending_data: RwLock<Arc<FullMixerBlendingData>>,
+	pub visualization: RwLock<Arc<FxHashMap<Uuid, VisualizationInfo>>>,
+	pub frame: RwLock<Arc<FrameInfo>>,
 }
 
 impl LayerDataSources {
@@ -12,16 +17,38 @@ impl LayerDataSources {
 		return LayerDataSourcesLocked {
 			base_layer: Arc::clone(&*self.base_layer.read().await),
 			blending_data: Arc::clone(&*self.blending_data.read().await),
+			visualization: Arc::clone(&*self.visualization.read().await),
+			frame: Arc::clone(&*self.frame.read().await),
 		};
 	}
 }
 
+/// Timing and mixer state for the frame currently being blended
+#[derive(Default)]
+pub struct FrameInfo {
+	/// Seconds since the blender started
+	pub time: f64,
+
+	/// Seconds since the previous frame
+	pub delta: f64,
+
+	/// The phase of the current beat, from `0` to `1`
+	pub beat: f64,
+
+	/// The opacity of every layer in the show
+	pub layer_opacities: FxHashMap<Uuid, u16>,
+}
+
 pub struct LayerDataSourcesLocked {
 	base_layer: Arc<FullMixerOutput>,
 	blending_data: Arc<FullMixerBlendingData>,
+	visualization: Arc<FxHashMap<Uuid, VisualizationInfo>>,
+	frame: Arc<FrameInfo>,
 }
 
 impl LayerDataSourcesLocked {
 	pub fn base_layer<'a>(&'a self) -> &'a FullMixerOutput { &self.base_layer }
 	pub fn blending_data<'a>(&'a self) -> &'a FullMixerBlendingData { &self.blending_data }
+	pub fn visualization(&self) -> &FxHashMap<Uuid, VisualizationInfo> { &self.visualization }
+	pub fn frame(&self) -> &FrameInfo { &self.frame }
 }
@@ -0,0 +1,545 @@
+use rustc_hash::FxHashMap;
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// The maximum length of an expression's source, in characters
+const MAX_EXPRESSION_LENGTH: usize = 1024;
+
+/// The maximum nesting depth of an expression. This keeps the recursive parser and evaluator
+/// from overflowing the stack on malicious input.
+const MAX_EXPRESSION_DEPTH: usize = 32;
+
+/// A parsed expression, ready to be evaluated.
+///
+/// Expressions are pure arithmetic. They can't loop, allocate, or access anything other than
+/// the values in an `ExpressionInputs`, so they are safe to evaluate on the blender thread.
+///
+/// Supported syntax:
+/// - Numbers (`1`, `0.5`, `.25`) and the constant `pi`
+/// - Operators `+`, `-`, `*`, `/`, `%`, `^` (power) and parentheses
+/// - Variables `t` (seconds), `beat` (phase of the current beat, `0` to `1`), `index` (the
+///   fixture's index within the layer), `count` (the number of fixtures in the layer), and
+///   `x` / `y` (the fixture's visualization position, `0` to `1`)
+/// - Functions `sin`, `cos`, `tan`, `abs`, `floor`, `ceil`, `round`, `sqrt`, `fract`,
+///   `min`, `max`, `pow`, `clamp(value, min, max)`, and `lerp(from, to, amount)`
+/// - `opacity("<layer id>")`, which returns another layer's opacity (`0` to `1`)
+#[derive(Debug, Clone)]
+pub struct Expression {
+	root: Node,
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+	Number(f64),
+	Variable(Variable),
+	LayerOpacity(Uuid),
+	Negate(Box<Node>),
+	Binary(BinaryOperator, Box<Node>, Box<Node>),
+	Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Variable {
+	Time,
+	Beat,
+	Index,
+	Count,
+	X,
+	Y,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOperator {
+	Add,
+	Subtract,
+	Multiply,
+	Divide,
+	Remainder,
+	Power,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+	Sin,
+	Cos,
+	Tan,
+	Abs,
+	Floor,
+	Ceil,
+	Round,
+	Sqrt,
+	Fract,
+	Min,
+	Max,
+	Pow,
+	Clamp,
+	Lerp,
+}
+
+impl Function {
+	fn from_name(name: &str) -> Option<Function> {
+		return match name {
+			"sin" => Some(Function::Sin),
+			"cos" => Some(Function::Cos),
+			"tan" => Some(Function::Tan),
+			"abs" => Some(Function::Abs),
+			"floor" => Some(Function::Floor),
+			"ceil" => Some(Function::Ceil),
+			"round" => Some(Function::Round),
+			"sqrt" => Some(Function::Sqrt),
+			"fract" => Some(Function::Fract),
+			"min" => Some(Function::Min),
+			"max" => Some(Function::Max),
+			"pow" => Some(Function::Pow),
+			"clamp" => Some(Function::Clamp),
+			"lerp" => Some(Function::Lerp),
+			_ => None,
+		};
+	}
+
+	fn argument_count(&self) -> usize {
+		return match self {
+			Function::Min | Function::Max | Function::Pow => 2,
+			Function::Clamp | Function::Lerp => 3,
+			_ => 1,
+		};
+	}
+}
+
+/// The values available to an expression while it is evaluated
+pub struct ExpressionInputs<'a> {
+	/// Seconds since the blender started
+	pub time: f64,
+
+	/// The phase of the current beat, from `0` to `1`
+	pub beat: f64,
+
+	/// The fixture's index within the layer
+	pub index: f64,
+
+	/// The number of fixtures in the layer
+	pub count: f64,
+
+	/// The fixture's horizontal visualization position, from `0` to `1`
+	pub x: f64,
+
+	/// The fixture's vertical visualization position, from `0` to `1`
+	pub y: f64,
+
+	/// The opacity of every layer in the show
+	pub layer_opacities: &'a FxHashMap<Uuid, u16>,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while parsing an expression
+pub enum ExpressionError {
+	#[error("The expression is empty")]
+	Empty,
+	#[error("The expression is too long")]
+	TooLong,
+	#[error("The expression is nested too deeply")]
+	TooDeep,
+	#[error("Unexpected character {0:?} at position {1}")]
+	UnexpectedCharacter(char, usize),
+	#[error("Unexpected end of expression")]
+	UnexpectedEnd,
+	#[error("Unexpected {0:?} at position {1}")]
+	UnexpectedToken(String, usize),
+	#[error("Unknown variable {0:?}")]
+	UnknownVariable(String),
+	#[error("Unknown function {0:?}")]
+	UnknownFunction(String),
+	#[error("{0} expects {1} argument(s)")]
+	WrongArgumentCount(String, usize),
+	#[error("{0:?} is not a valid layer ID")]
+	InvalidLayerId(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+	Number(f64),
+	Identifier(String),
+	String(String),
+	Operator(char),
+	OpenParen,
+	CloseParen,
+	Comma,
+}
+
+impl Token {
+	fn describe(&self) -> String {
+		return match self {
+			Token::Number(number) => number.to_string(),
+			Token::Identifier(identifier) => identifier.clone(),
+			Token::String(string) => format!("\"{}\"", string),
+			Token::Operator(operator) => operator.to_string(),
+			Token::OpenParen => "(".into(),
+			Token::CloseParen => ")".into(),
+			Token::Comma => ",".into(),
+		};
+	}
+}
+
+/// Splits an expression's source into tokens, paired with their character positions
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+	let chars: Vec<char> = source.chars().collect();
+	let mut tokens = Vec::new();
+	let mut position = 0;
+
+	while position < chars.len() {
+		let start = position;
+		let character = chars[position];
+		if character.is_whitespace() {
+			position += 1;
+			continue;
+		}
+
+		let token = if character.is_ascii_digit() || character == '.' {
+			while position < chars.len() && (chars[position].is_ascii_digit() || chars[position] == '.') {
+				position += 1;
+			}
+			let text: String = chars[start..position].iter().collect();
+			Token::Number(text.parse().map_err(|_| ExpressionError::UnexpectedToken(text, start))?)
+		} else if character.is_ascii_alphabetic() || character == '_' {
+			while position < chars.len() && (chars[position].is_ascii_alphanumeric() || chars[position] == '_') {
+				position += 1;
+			}
+			Token::Identifier(chars[start..position].iter().collect())
+		} else if character == '"' {
+			position += 1;
+			while position < chars.len() && chars[position] != '"' {
+				position += 1;
+			}
+			if position >= chars.len() {
+				return Err(ExpressionError::UnexpectedEnd);
+			}
+			position += 1;
+			Token::String(chars[start + 1..position - 1].iter().collect())
+		} else {
+			position += 1;
+			match character {
+				'+' | '-' | '*' | '/' | '%' | '^' => Token::Operator(character),
+				'(' => Token::OpenParen,
+				')' => Token::CloseParen,
+				',' => Token::Comma,
+				_ => return Err(ExpressionError::UnexpectedCharacter(character, start)),
+			}
+		};
+		tokens.push((token, start));
+	}
+
+	return Ok(tokens);
+}
+
+/// A recursive descent parser over a list of tokens
+struct Parser {
+	tokens: Vec<(Token, usize)>,
+	position: usize,
+	depth: usize,
+}
+
+impl Parser {
+	fn peek(&self) -> Option<&Token> {
+		return self.tokens.get(self.position).map(|(token, _)| token);
+	}
+
+	fn next(&mut self) -> Result<(Token, usize), ExpressionError> {
+		let token = self.tokens.get(self.position).cloned().ok_or(ExpressionError::UnexpectedEnd)?;
+		self.position += 1;
+		return Ok(token);
+	}
+
+	fn expect(&mut self, expected: Token) -> Result<(), ExpressionError> {
+		let (token, position) = self.next()?;
+		if token == expected {
+			return Ok(());
+		} else {
+			return Err(ExpressionError::UnexpectedToken(token.describe(), position));
+		}
+	}
+
+	fn enter(&mut self) -> Result<(), ExpressionError> {
+		self.depth += 1;
+		if self.depth > MAX_EXPRESSION_DEPTH {
+			return Err(ExpressionError::TooDeep);
+		}
+		return Ok(());
+	}
+
+	/// `sum := product (("+" | "-") product)*`
+	fn parse_sum(&mut self) -> Result<Node, ExpressionError> {
+		self.enter()?;
+		let mut node = self.parse_product()?;
+		while let Some(Token::Operator(operator @ ('+' | '-'))) = self.peek() {
+			let operator = if *operator == '+' { BinaryOperator::Add } else { BinaryOperator::Subtract };
+			self.position += 1;
+			node = Node::Binary(operator, Box::new(node), Box::new(self.parse_product()?));
+		}
+		self.depth -= 1;
+		return Ok(node);
+	}
+
+	/// `product := unary (("*" | "/" | "%") unary)*`
+	fn parse_product(&mut self) -> Result<Node, ExpressionError> {
+		let mut node = self.parse_unary()?;
+		while let Some(Token::Operator(operator @ ('*' | '/' | '%'))) = self.peek() {
+			let operator = match operator {
+				'*' => BinaryOperator::Multiply,
+				'/' => BinaryOperator::Divide,
+				_ => BinaryOperator::Remainder,
+			};
+			self.position += 1;
+			node = Node::Binary(operator, Box::new(node), Box::new(self.parse_unary()?));
+		}
+		return Ok(node);
+	}
+
+	/// `unary := "-" unary | power`
+	fn parse_unary(&mut self) -> Result<Node, ExpressionError> {
+		if let Some(Token::Operator('-')) = self.peek() {
+			self.position += 1;
+			self.enter()?;
+			let node = Node::Negate(Box::new(self.parse_unary()?));
+			self.depth -= 1;
+			return Ok(node);
+		}
+		return self.parse_power();
+	}
+
+	/// `power := atom ("^" unary)?`
+	fn parse_power(&mut self) -> Result<Node, ExpressionError> {
+		let base = self.parse_atom()?;
+		if let Some(Token::Operator('^')) = self.peek() {
+			self.position += 1;
+			self.enter()?;
+			let exponent = self.parse_unary()?;
+			self.depth -= 1;
+			return Ok(Node::Binary(BinaryOperator::Power, Box::new(base), Box::new(exponent)));
+		}
+		return Ok(base);
+	}
+
+	/// `atom := number | variable | call | "(" sum ")"`
+	fn parse_atom(&mut self) -> Result<Node, ExpressionError> {
+		let (token, position) = self.next()?;
+		return match token {
+			Token::Number(number) => Ok(Node::Number(number)),
+			Token::OpenParen => {
+				let node = self.parse_sum()?;
+				self.expect(Token::CloseParen)?;
+				Ok(node)
+			}
+			Token::Identifier(name) => {
+				if let Some(Token::OpenParen) = self.peek() {
+					self.position += 1;
+					self.parse_call(name)
+				} else {
+					match name.as_str() {
+						"t" => Ok(Node::Variable(Variable::Time)),
+						"beat" => Ok(Node::Variable(Variable::Beat)),
+						"index" => Ok(Node::Variable(Variable::Index)),
+						"count" => Ok(Node::Variable(Variable::Count)),
+						"x" => Ok(Node::Variable(Variable::X)),
+						"y" => Ok(Node::Variable(Variable::Y)),
+						"pi" => Ok(Node::Number(std::f64::consts::PI)),
+						_ => Err(ExpressionError::UnknownVariable(name)),
+					}
+				}
+			}
+			token => Err(ExpressionError::UnexpectedToken(token.describe(), position)),
+		};
+	}
+
+	/// Parses the arguments of a function call. The opening parenthesis has already been consumed.
+	fn parse_call(&mut self, name: String) -> Result<Node, ExpressionError> {
+		if name == "opacity" {
+			let (token, position) = self.next()?;
+			let layer_id = match token {
+				Token::String(layer_id) => {
+					Uuid::parse_str(&layer_id).map_err(|_| ExpressionError::InvalidLayerId(layer_id))?
+				}
+				token => return Err(ExpressionError::UnexpectedToken(token.describe(), position)),
+			};
+			self.expect(Token::CloseParen)?;
+			return Ok(Node::LayerOpacity(layer_id));
+		}
+
+		let function = Function::from_name(&name).ok_or_else(|| ExpressionError::UnknownFunction(name.clone()))?;
+		let mut arguments = Vec::new();
+		if let Some(Token::CloseParen) = self.peek() {
+			self.position += 1;
+		} else {
+			loop {
+				arguments.push(self.parse_sum()?);
+				let (token, position) = self.next()?;
+				match token {
+					Token::Comma => continue,
+					Token::CloseParen => break,
+					token => return Err(ExpressionError::UnexpectedToken(token.describe(), position)),
+				}
+			}
+		}
+
+		if arguments.len() != function.argument_count() {
+			return Err(ExpressionError::WrongArgumentCount(name, function.argument_count()));
+		}
+		return Ok(Node::Call(function, arguments));
+	}
+}
+
+impl Expression {
+	/// Parses an expression from its source
+	pub fn parse(source: &str) -> Result<Expression, ExpressionError> {
+		if source.chars().count() > MAX_EXPRESSION_LENGTH {
+			return Err(ExpressionError::TooLong);
+		}
+		let tokens = tokenize(source)?;
+		if tokens.is_empty() {
+			return Err(ExpressionError::Empty);
+		}
+
+		let mut parser = Parser {
+			tokens,
+			position: 0,
+			depth: 0,
+		};
+		let root = parser.parse_sum()?;
+		if let Some((token, position)) = parser.tokens.get(parser.position) {
+			return Err(ExpressionError::UnexpectedToken(token.describe(), *position));
+		}
+
+		return Ok(Expression { root });
+	}
+
+	/// Evaluates the expression. Non-finite results (such as division by zero) evaluate to `0`.
+	pub fn evaluate(&self, inputs: &ExpressionInputs) -> f64 {
+		let result = evaluate_node(&self.root, inputs);
+		return if result.is_finite() { result } else { 0.0 };
+	}
+
+	/// Indicates if the expression changes over time, and must be re-evaluated every frame
+	pub fn is_animated(&self) -> bool {
+		return node_is_animated(&self.root);
+	}
+}
+
+fn evaluate_node(node: &Node, inputs: &ExpressionInputs) -> f64 {
+	return match node {
+		Node::Number(number) => *number,
+		Node::Variable(variable) => match variable {
+			Variable::Time => inputs.time,
+			Variable::Beat => inputs.beat,
+			Variable::Index => inputs.index,
+			Variable::Count => inputs.count,
+			Variable::X => inputs.x,
+			Variable::Y => inputs.y,
+		},
+		Node::LayerOpacity(layer_id) => {
+			inputs.layer_opacities.get(layer_id).map_or(0.0, |opacity| *opacity as f64 / u16::MAX as f64)
+		}
+		Node::Negate(node) => -evaluate_node(node, inputs),
+		Node::Binary(operator, left, right) => {
+			let left = evaluate_node(left, inputs);
+			let right = evaluate_node(right, inputs);
+			match operator {
+				BinaryOperator::Add => left + right,
+				BinaryOperator::Subtract => left - right,
+				BinaryOperator::Multiply => left * right,
+				BinaryOperator::Divide => left / right,
+				BinaryOperator::Remainder => left.rem_euclid(right),
+				BinaryOperator::Power => left.powf(right),
+			}
+		}
+		Node::Call(function, arguments) => {
+			let arguments: Vec<f64> = arguments.iter().map(|argument| evaluate_node(argument, inputs)).collect();
+			match function {
+				Function::Sin => arguments[0].sin(),
+				Function::Cos => arguments[0].cos(),
+				Function::Tan => arguments[0].tan(),
+				Function::Abs => arguments[0].abs(),
+				Function::Floor => arguments[0].floor(),
+				Function::Ceil => arguments[0].ceil(),
+				Function::Round => arguments[0].round(),
+				Function::Sqrt => arguments[0].sqrt(),
+				Function::Fract => arguments[0] - arguments[0].floor(),
+				Function::Min => arguments[0].min(arguments[1]),
+				Function::Max => arguments[0].max(arguments[1]),
+				Function::Pow => arguments[0].powf(arguments[1]),
+				Function::Clamp => arguments[0].max(arguments[1]).min(arguments[2]),
+				Function::Lerp => arguments[0] + (arguments[1] - arguments[0]) * arguments[2],
+			}
+		}
+	};
+}
+
+fn node_is_animated(node: &Node) -> bool {
+	return match node {
+		Node::Number(_) | Node::LayerOpacity(_) => false,
+		Node::Variable(variable) => matches!(variable, Variable::Time | Variable::Beat),
+		Node::Negate(node) => node_is_animated(node),
+		Node::Binary(_, left, right) => node_is_animated(left) || node_is_animated(right),
+		Node::Call(_, arguments) => arguments.iter().any(node_is_animated),
+	};
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn evaluate(source: &str) -> f64 {
+		let layer_opacities = FxHashMap::default();
+		let inputs = ExpressionInputs {
+			time: 0.0,
+			beat: 0.0,
+			index: 0.0,
+			count: 1.0,
+			x: 0.0,
+			y: 0.0,
+			layer_opacities: &layer_opacities,
+		};
+		return Expression::parse(source).ok().unwrap().evaluate(&inputs);
+	}
+
+	#[test]
+	fn precedence() {
+		assert_eq!(evaluate("1 + 2 * 3"), 7.0);
+		assert_eq!(evaluate("(1 + 2) * 3"), 9.0);
+		assert_eq!(evaluate("10 - 4 - 3"), 3.0);
+		assert_eq!(evaluate("2 ^ 3 ^ 2"), 512.0);
+		assert_eq!(evaluate("-2 ^ 2"), -4.0);
+		assert_eq!(evaluate("7 % 4 * 2"), 6.0);
+	}
+
+	#[test]
+	fn division_by_zero() {
+		assert_eq!(evaluate("1 / 0"), 0.0);
+		assert_eq!(evaluate("0 / 0"), 0.0);
+		assert_eq!(evaluate("1 % 0"), 0.0);
+	}
+
+	#[test]
+	fn unknown_identifiers() {
+		assert!(matches!(Expression::parse("speed * 2"), Err(ExpressionError::UnknownVariable(name)) if name == "speed"));
+		assert!(matches!(Expression::parse("wobble(t)"), Err(ExpressionError::UnknownFunction(name)) if name == "wobble"));
+		assert!(matches!(Expression::parse("min(1)"), Err(ExpressionError::WrongArgumentCount(..))));
+		assert!(matches!(Expression::parse("opacity(\"not a uuid\")"), Err(ExpressionError::InvalidLayerId(..))));
+	}
+
+	#[test]
+	fn limits() {
+		assert!(matches!(Expression::parse(""), Err(ExpressionError::Empty)));
+
+		let long = "1+".repeat(MAX_EXPRESSION_LENGTH / 2) + "1";
+		assert!(matches!(Expression::parse(&long), Err(ExpressionError::TooLong)));
+
+		let deep = "(".repeat(MAX_EXPRESSION_DEPTH + 1) + "1" + &")".repeat(MAX_EXPRESSION_DEPTH + 1);
+		assert!(matches!(Expression::parse(&deep), Err(ExpressionError::TooDeep)));
+		let negated = "-".repeat(MAX_EXPRESSION_DEPTH + 1) + "1";
+		assert!(matches!(Expression::parse(&negated), Err(ExpressionError::TooDeep)));
+
+		let shallow = "(".repeat(MAX_EXPRESSION_DEPTH - 1) + "1" + &")".repeat(MAX_EXPRESSION_DEPTH - 1);
+		assert_eq!(evaluate(&shallow), 1.0);
+	}
+}
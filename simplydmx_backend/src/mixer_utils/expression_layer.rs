@@ -0,0 +1,164 @@
+use rustc_hash::FxHashMap;
+
+use async_trait::async_trait;
+
+use crate::mixer_utils::state::FullMixerBlendingData;
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+	layer::MixerLayer,
+	state::{
+		BlenderValue,
+		SubmasterData,
+		FullMixerOutput,
+	},
+	data_sources::LayerDataSourcesLocked,
+	default_blender::blend_layer,
+	expression::{Expression, ExpressionError, ExpressionInputs},
+	migration::AttributeMigration,
+};
+
+#[portable]
+/// Defines a submaster whose values are calculated from expressions every frame.
+///
+/// Each expression produces a value from `0` to `1`, which is scaled to the attribute's range.
+pub struct ExpressionLayer {
+	pub name: String,
+
+	/// The fixtures controlled by this layer. A fixture's position in this list is its `index`.
+	pub fixtures: Vec<Uuid>,
+
+	/// The source of the expression used for each attribute
+	pub expressions: FxHashMap<String, String>,
+
+	/// Parsed versions of `expressions`
+	#[serde(skip)]
+	compiled: FxHashMap<String, Expression>,
+}
+impl ExpressionLayer {
+	pub fn new(name: String) -> ExpressionLayer {
+		return ExpressionLayer {
+			name,
+			fixtures: Vec::new(),
+			expressions: FxHashMap::default(),
+			compiled: FxHashMap::default(),
+		};
+	}
+
+	/// Replaces the fixtures and expressions in this layer. Nothing is changed if an expression is invalid.
+	pub fn set_contents(&mut self, fixtures: Vec<Uuid>, expressions: FxHashMap<String, String>) -> Result<(), ExpressionLayerError> {
+		let mut compiled = FxHashMap::default();
+		for (attribute_id, source) in expressions.iter() {
+			match Expression::parse(source) {
+				Ok(expression) => {
+					compiled.insert(attribute_id.clone(), expression);
+				}
+				Err(error) => {
+					return Err(ExpressionLayerError::InvalidExpression(attribute_id.clone(), error));
+				}
+			}
+		}
+
+		self.fixtures = fixtures;
+		self.expressions = expressions;
+		self.compiled = compiled;
+		return Ok(());
+	}
+
+	/// Parses the layer's expression sources. This must be called after the layer is loaded from a file.
+	///
+	/// Invalid expressions are skipped.
+	pub fn compile(&mut self) {
+		self.compiled = self
+			.expressions
+			.iter()
+			.filter_map(|(attribute_id, source)| {
+				Expression::parse(source)
+					.ok()
+					.map(|expression| (attribute_id.clone(), expression))
+			})
+			.collect();
+	}
+
+	/// Evaluates the layer's expressions for the current frame
+	fn evaluate(&self, data_sources: &LayerDataSourcesLocked) -> SubmasterData {
+		let frame = data_sources.frame();
+		let mut values = SubmasterData::default();
+		for (index, fixture_id) in self.fixtures.iter().enumerate() {
+			let fixture_blending_data = match data_sources.blending_data().get(fixture_id) {
+				Some(fixture_blending_data) => fixture_blending_data,
+				None => continue,
+			};
+			let (x, y) = data_sources
+				.visualization()
+				.get(fixture_id)
+				.map_or((0.0, 0.0), |info| (info.x as f64 / u16::MAX as f64, info.y as f64 / u16::MAX as f64));
+			let inputs = ExpressionInputs {
+				time: frame.time,
+				beat: frame.beat,
+				index: index as f64,
+				count: self.fixtures.len() as f64,
+				x,
+				y,
+				layer_opacities: &frame.layer_opacities,
+			};
+
+			let mut fixture_values = FxHashMap::default();
+			for (attribute_id, expression) in self.compiled.iter() {
+				if let Some(blending_data) = fixture_blending_data.get(attribute_id) {
+					let amount = expression.evaluate(&inputs).clamp(0.0, 1.0);
+					let range = blending_data.max_value.saturating_sub(blending_data.min_value) as f64;
+					let value = blending_data.min_value as f64 + range * amount;
+					fixture_values.insert(attribute_id.clone(), BlenderValue::Static(value.round() as u16));
+				}
+			}
+			values.insert(*fixture_id, fixture_values);
+		}
+		return values;
+	}
+}
+
+#[async_trait]
+impl MixerLayer for ExpressionLayer {
+	fn animated(&self) -> bool {
+		return self.compiled.values().any(|expression| expression.is_animated());
+	}
+	async fn cleanup(&mut self, patcher_data: &(FullMixerOutput, FullMixerBlendingData)) {
+		// Delete fixtures that no longer exist
+		self.fixtures.retain(|fixture_id| patcher_data.0.contains_key(fixture_id));
+	}
+	fn migrate_fixture(&mut self, fixture_id: &Uuid, migration: &AttributeMigration) {
+		if !self.fixtures.contains(fixture_id) {
+			return;
+		}
+
+		// Expressions are shared by every fixture in the layer, so they are copied to the new attribute
+		// rather than moved. Expressions produce a 0-1 value, so they don't need to be scaled.
+		for (old_attribute, new_attribute) in migration.attribute_map.iter() {
+			if !self.expressions.contains_key(new_attribute) {
+				if let Some(source) = self.expressions.get(old_attribute).cloned() {
+					if let Some(expression) = self.compiled.get(old_attribute).cloned() {
+						self.compiled.insert(new_attribute.clone(), expression);
+					}
+					self.expressions.insert(new_attribute.clone(), source);
+				}
+			}
+		}
+	}
+	async fn blend(&self, cumulative_layer: &mut FullMixerOutput, data_sources: &LayerDataSourcesLocked, opacity: u16) {
+		let values = self.evaluate(data_sources);
+		blend_layer(cumulative_layer, data_sources, opacity, &values);
+	}
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while editing an expression layer
+pub enum ExpressionLayerError {
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("The expression for attribute {0:?} is invalid: {1}")]
+	InvalidExpression(String, ExpressionError),
+}
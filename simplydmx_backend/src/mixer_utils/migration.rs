@@ -0,0 +1,55 @@
+use rustc_hash::FxHashMap;
+
+use super::state::{AbstractLayerLight, BlenderValue, BlendingData};
+
+/// Describes how a fixture's layer data moves to new attributes when its fixture type is replaced
+pub struct AttributeMigration<'a> {
+	/// Old attribute ID --> New attribute ID
+	pub attribute_map: &'a FxHashMap<String, String>,
+
+	/// Blending data for the fixture's attributes before the change
+	pub old_blending_data: Option<&'a FxHashMap<String, BlendingData>>,
+
+	/// Blending data for the fixture's attributes after the change
+	pub new_blending_data: Option<&'a FxHashMap<String, BlendingData>>,
+}
+
+impl AttributeMigration<'_> {
+	/// Moves values to their new attribute IDs, scaling them to the new attribute's range.
+	///
+	/// Values for attributes that aren't in the map are dropped.
+	pub fn migrate_values(&self, values: &mut AbstractLayerLight) {
+		let old_values = std::mem::take(values);
+		for (old_attribute, value) in old_values {
+			if let Some(new_attribute) = self.attribute_map.get(&old_attribute) {
+				let value = self.scale_value(&old_attribute, new_attribute, value);
+				values.insert(new_attribute.clone(), value);
+			}
+		}
+	}
+
+	/// Scales a value from the old attribute's range to the new attribute's range
+	fn scale_value(&self, old_attribute: &str, new_attribute: &str, value: BlenderValue) -> BlenderValue {
+		let (old, new) = match (
+			self.old_blending_data.and_then(|data| data.get(old_attribute)),
+			self.new_blending_data.and_then(|data| data.get(new_attribute)),
+		) {
+			(Some(old), Some(new)) => (old, new),
+			_ => return value,
+		};
+		let old_range = old.max_value.saturating_sub(old.min_value) as f64;
+		let new_range = new.max_value.saturating_sub(new.min_value) as f64;
+		if old_range == 0.0 || (old.min_value == new.min_value && old.max_value == new.max_value) {
+			return value;
+		}
+
+		return match value {
+			BlenderValue::None => BlenderValue::None,
+			BlenderValue::Static(value) => {
+				let amount = (value.clamp(old.min_value, old.max_value) - old.min_value) as f64 / old_range;
+				BlenderValue::Static((new.min_value as f64 + amount * new_range).round() as u16)
+			}
+			BlenderValue::Offset(offset) => BlenderValue::Offset((offset as f64 * new_range / old_range).round() as i32),
+		};
+	}
+}
@@ -2,4 +2,7 @@ pub mod state;
 pub mod data_sources;
 pub mod layer;
 pub mod static_layer;
+pub mod expression;
+pub mod expression_layer;
 pub mod default_blender;
+pub mod migration;
@@ -14,6 +14,7 @@ use super::{
 	},
 	data_sources::LayerDataSourcesLocked,
 	default_blender::blend_layer,
+	migration::AttributeMigration,
 };
 
 #[portable]
@@ -54,6 +55,11 @@ impl MixerLayer for StaticLayer {
 			}
 		}
 	}
+	fn migrate_fixture(&mut self, fixture_id: &Uuid, migration: &AttributeMigration) {
+		if let Some(fixture_data) = self.values.get_mut(fixture_id) {
+			migration.migrate_values(fixture_data);
+		}
+	}
 	async fn blend(&self, cumulative_layer: &mut FullMixerOutput, data_sources: &LayerDataSourcesLocked, opacity: u16) {
 		blend_layer(cumulative_layer, data_sources, opacity, &self.values);
 	}
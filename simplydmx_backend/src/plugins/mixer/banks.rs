@@ -0,0 +1,164 @@
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[portable]
+/// A playback bank, mapping a fixed set of fader slots to layers across several pages.
+///
+/// Banks are the basis for control surfaces. A physical controller with 8 faders would
+/// use a bank with 8 slots, and page through the layers assigned to those slots.
+pub struct FaderBank {
+	/// The ID of the bank
+	pub id: Uuid,
+
+	/// A human-readable name for the bank
+	pub name: String,
+
+	/// The number of fader slots available on every page of this bank
+	pub slot_count: u16,
+
+	/// The pages available in this bank
+	pub pages: Vec<FaderPage>,
+
+	/// The index of the page currently loaded onto the faders
+	pub current_page: usize,
+
+	/// Determines how physical faders take control of a layer after a page switch
+	pub pickup_mode: PickupMode,
+}
+
+impl FaderBank {
+	pub fn new(id: Uuid, name: String, slot_count: u16) -> Self {
+		return FaderBank {
+			id,
+			name,
+			slot_count,
+			pages: vec![FaderPage::new(String::from("Page 1"), slot_count)],
+			current_page: 0,
+			pickup_mode: PickupMode::Pickup,
+		};
+	}
+
+	/// Gets the layer assigned to a slot on the current page
+	pub fn current_assignment(&self, slot: u16) -> Option<Uuid> {
+		return self
+			.pages
+			.get(self.current_page)
+			.and_then(|page| page.assignments.get(slot as usize).cloned().flatten());
+	}
+
+	/// Removes all assignments referencing the given layer
+	pub fn unassign_layer(&mut self, layer_id: &Uuid) {
+		for page in self.pages.iter_mut() {
+			for assignment in page.assignments.iter_mut() {
+				if assignment.as_ref() == Some(layer_id) {
+					*assignment = None;
+				}
+			}
+		}
+	}
+}
+
+#[portable]
+/// A single page of fader assignments within a bank
+pub struct FaderPage {
+	/// A human-readable name for the page
+	pub name: String,
+
+	/// The layer assigned to each slot. This always has `slot_count` entries.
+	pub assignments: Vec<Option<Uuid>>,
+}
+
+impl FaderPage {
+	pub fn new(name: String, slot_count: u16) -> Self {
+		return FaderPage {
+			name,
+			assignments: vec![None; slot_count as usize],
+		};
+	}
+}
+
+#[portable]
+/// Determines how a physical fader behaves when its position differs from the level
+/// of the layer it controls.
+pub enum PickupMode {
+	/// The layer jumps to the fader's position as soon as the fader is moved
+	Jump,
+
+	/// The fader is ignored until it passes through the layer's current level
+	Pickup,
+}
+
+#[portable]
+#[derive(Default)]
+/// Runtime tracking data for a physical fader. This is not saved with the show.
+pub struct FaderPickupState {
+	/// Whether the fader is currently in control of its layer
+	pub engaged: bool,
+
+	/// The last position reported by the physical fader
+	pub last_position: Option<u16>,
+}
+
+impl FaderPickupState {
+	/// Updates the pickup state with a new physical position, returning whether or not
+	/// the fader should take control of the layer.
+	///
+	/// `layer_level` is the current opacity of the layer assigned to the fader.
+	pub fn update(&mut self, mode: &PickupMode, position: u16, layer_level: u16) -> bool {
+		if !self.engaged {
+			self.engaged = match mode {
+				PickupMode::Jump => true,
+				PickupMode::Pickup => match self.last_position {
+					// The fader passed through the layer's level since the last update
+					Some(last_position) => {
+						(last_position.min(position)..=last_position.max(position)).contains(&layer_level)
+					}
+					None => position == layer_level,
+				},
+			};
+		}
+		self.last_position = Some(position);
+		return self.engaged;
+	}
+
+	/// Releases control of the layer, forcing the fader to be picked up again
+	pub fn release(&mut self) {
+		self.engaged = false;
+	}
+}
+
+#[portable]
+/// The current state of a fader slot, used to provide feedback to control surfaces
+pub struct FaderSlotState {
+	/// The layer assigned to the slot on the current page
+	pub layer_id: Option<Uuid>,
+
+	/// The effective opacity of the assigned layer, after links to master layers.
+	///
+	/// Pickup is still judged against the layer's own opacity, so this can differ from the
+	/// fader's position while the layer is linked.
+	pub opacity: u16,
+
+	/// Whether or not the physical fader is in control of the layer
+	pub engaged: bool,
+
+	/// The last position reported by the physical fader
+	pub physical_position: Option<u16>,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while manipulating fader banks
+pub enum BankError {
+	#[error("The requested bank does not exist")]
+	BankMissing,
+	#[error("The requested page does not exist in this bank")]
+	PageMissing,
+	#[error("The requested slot does not exist in this bank")]
+	SlotMissing,
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("A bank must contain at least one page")]
+	LastPage,
+}
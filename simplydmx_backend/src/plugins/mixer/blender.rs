@@ -15,12 +15,27 @@ use tokio::{
 
 use crate::{
 	mixer_utils::{
-		data_sources::LayerDataSources,
+		data_sources::{FrameInfo, LayerDataSources},
 		layer::MixerLayer,
-		state::{FullMixerBlendingData, FullMixerOutput},
+		state::{BlendingData, BlendingScheme, FullMixerBlendingData, FullMixerOutput},
 	},
-	plugins::patcher::PatcherInterface,
+	plugins::patcher::{PatcherInterface, VisualizationInfo},
 };
+use uuid::Uuid;
+
+/// Scales the HTP attributes of every fixture in `cumulative_layer` by a zone's grand master
+fn apply_grand_master(cumulative_layer: &mut FullMixerOutput, blending_data: &FullMixerBlendingData, grand_master: u16) {
+	for (fixture_id, fixture_data) in cumulative_layer.iter_mut() {
+		if let Some(fixture_blending_data) = blending_data.get(fixture_id) {
+			for (attribute_id, value) in fixture_data.iter_mut() {
+				if let Some(BlendingData { scheme: BlendingScheme::HTP, min_value, .. }) = fixture_blending_data.get(attribute_id) {
+					let scaled = (*value as u32 * grand_master as u32 / u16::MAX as u32) as u16;
+					*value = scaled.max(*min_value);
+				}
+			}
+		}
+	}
+}
 
 /// Start the blending engine
 ///
@@ -43,11 +58,25 @@ pub async fn start_blender(
 		let data_sources = LayerDataSources {
 			base_layer: RwLock::new(Arc::new(base_layer)),
 			blending_data: RwLock::new(Arc::new(blending_data)),
+			visualization: RwLock::new(Arc::new(patcher_interface.get_visualization_data().await)),
+			frame: RwLock::new(Arc::new(FrameInfo::default())),
 		};
 
+		// Cells are blended in their parent fixture's zone
+		let mut cell_parents = patcher_interface.get_cell_parents().await;
+
+		// Clock used by animated layers
+		let blender_start = Instant::now();
+		let mut last_frame = blender_start;
+		let mut beat_position = 0.0;
+
 		// Set up patch updated listener
-		match plugin_context.listen::<()>(String::from("patcher.patch_updated"), FilterCriteria::None).await {
-			Ok(mut listener) => {
+		let listeners = (
+			plugin_context.listen::<()>(String::from("patcher.patch_updated"), FilterCriteria::None).await,
+			plugin_context.listen::<(Uuid, VisualizationInfo)>(String::from("patcher.visualization_updated"), FilterCriteria::None).await,
+		);
+		match listeners {
+			(Ok(mut listener), Ok(mut visualization_listener)) => {
 				loop {
 					let start = Instant::now();
 
@@ -57,29 +86,74 @@ pub async fn start_blender(
 					// Unlock context
 					let ctx_read = ctx.read().await;
 
+					// Advance the clock. The beat is accumulated so tempo changes don't cause jumps.
+					let delta = start.duration_since(last_frame).as_secs_f64();
+					beat_position = (beat_position + delta * ctx_read.tempo / 60.0).fract();
+					last_frame = start;
+
+					// Resolve layer links up front so each layer is blended at its effective opacity
+					let layer_opacities = ctx_read.effective_opacities();
+					let frame = Arc::new(FrameInfo {
+						time: start.duration_since(blender_start).as_secs_f64(),
+						delta,
+						beat: beat_position,
+						layer_opacities: layer_opacities.clone(),
+					});
+					*data_sources.frame.write().await = Arc::clone(&frame);
+
 					let locked_data_sources = data_sources.lock().await;
 
 					#[cfg(feature = "blender-benchmark")]
 					let start_bench = Instant::now();
-					let mut cumulative_layer: FullMixerOutput = locked_data_sources.base_layer().clone();
-					for layer_id in ctx_read.default_context.layer_order.iter() {
-						if let Some(opacity) = ctx_read.default_context.layer_opacities.get(layer_id) {
-							if *opacity == 0 { continue } // Skip if opacity is 0
-							if let Some(layer) = ctx_read.default_context.user_submasters.get(layer_id) {
-								if layer.animated() {
-									animated = true;
+					let mut final_output = FullMixerOutput::default();
+					for (zone_id, zone) in ctx_read.iter_zones() {
+						// Each zone only blends onto the fixtures assigned to it
+						let mut cumulative_layer: FullMixerOutput = locked_data_sources
+							.base_layer()
+							.iter()
+							.filter(|(fixture_id, _)| {
+								let zone_fixture_id = cell_parents.get(fixture_id).unwrap_or(fixture_id);
+								ctx_read.fixture_zones.get(zone_fixture_id) == zone_id.as_ref()
+							})
+							.map(|(fixture_id, fixture_data)| (*fixture_id, fixture_data.clone()))
+							.collect();
+						if cumulative_layer.is_empty() {
+							continue;
+						}
+
+						for layer_id in zone.default_context.layer_order.iter() {
+							if let Some(opacity) = layer_opacities.get(layer_id) {
+								if *opacity == 0 { continue } // Skip if opacity is 0
+								if let Some(layer) = zone.default_context.user_submasters.get(layer_id) {
+									if layer.animated() {
+										animated = true;
+									}
+									layer.blend(&mut cumulative_layer, &locked_data_sources, *opacity).await;
+								} else if let Some(layer) = zone.default_context.expression_layers.get(layer_id) {
+									if layer.animated() {
+										animated = true;
+									}
+									layer.blend(&mut cumulative_layer, &locked_data_sources, *opacity).await;
 								}
-								layer.blend(&mut cumulative_layer, &locked_data_sources, *opacity).await;
 							}
 						}
+
+						if zone.grand_master != u16::MAX {
+							apply_grand_master(&mut cumulative_layer, locked_data_sources.blending_data(), zone.grand_master);
+						}
+						final_output.extend(cumulative_layer);
 					}
+					let cumulative_layer = final_output;
 					#[cfg(feature = "blender-benchmark")]
 					eprintln!("Blender took {:?}", start_bench.elapsed());
 
 					let result = Arc::new(cumulative_layer);
 					// TODO: These events don't need to be so frequent
 					plugin_context.emit_borrowed("mixer.final_output".into(), FilterCriteria::None, Arc::clone(&result)).await;
-					patcher_interface.write_values(Arc::clone(&result)).await;
+					// Software strobes are generated in the patcher, so they also hold the blender active
+					if patcher_interface.write_values(Arc::clone(&result), &frame).await {
+						animated = true;
+					}
 
 					drop(ctx_read);
 
@@ -93,6 +167,15 @@ pub async fn start_blender(
 								ctx.write().await.cleanup(&patcher_data).await;
 								*data_sources.base_layer.write().await = Arc::new(patcher_data.0);
 								*data_sources.blending_data.write().await = Arc::new(patcher_data.1);
+								cell_parents = patcher_interface.get_cell_parents().await;
+							},
+							Event::Shutdown => break,
+						},
+
+						// Fixture positions are used by expression layers
+						msg = visualization_listener.receive() => match msg {
+							Event::Msg { .. } => {
+								*data_sources.visualization.write().await = Arc::new(patcher_interface.get_visualization_data().await);
 							},
 							Event::Shutdown => break,
 						},
@@ -110,7 +193,7 @@ pub async fn start_blender(
 					}
 				}
 			},
-			Err(error) => {
+			(Err(error), _) | (_, Err(error)) => {
 				log_error!(plugin_context, "[CRITICAL] An error occurred when setting up the blender: {:?}", error);
 			},
 		}
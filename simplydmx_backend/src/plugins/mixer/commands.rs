@@ -1,7 +1,21 @@
-use super::MixerInterface;
-use crate::mixer_utils::{state::SubmasterData, static_layer::StaticLayer};
+use super::{
+	banks::{BankError, FaderBank, FaderSlotState, PickupMode},
+	layer_operations::{LayerContextTarget, LayerOperationError, LayerSplit, MergeRule},
+	links::{LayerLink, LinkError, LinkMode},
+	zones::{ZoneDescription, ZoneError},
+	MixerInterface, PhysicalValueError, SegmentSelectionError,
+};
+use crate::plugins::patcher::{driver_plugin_api::PhysicalValue, PatcherInterface, ReplaceFixtureTypeError};
+use crate::mixer_utils::{
+	expression::ExpressionError,
+	expression_layer::{ExpressionLayer, ExpressionLayerError},
+	state::SubmasterData,
+	static_layer::StaticLayer,
+};
+use rustc_hash::FxHashMap;
 use simplydmx_plugin_framework::*;
 
+use std::collections::HashMap;
 use uuid::Uuid;
 
 // ┌──────────────────────────┐
@@ -19,9 +33,11 @@ impl EnterBlindMode {
 		Self(mixer_interface)
 	}
 
-	#[service_main()]
-	async fn main(self) -> () {
-		return self.0.enter_blind_mode().await;
+	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
+	)]
+	async fn main(self, zone_id: Option::<Uuid>) -> () {
+		return self.0.enter_blind_mode(zone_id).await;
 	}
 }
 
@@ -37,10 +53,11 @@ impl SetBlindOpacity {
 	}
 
 	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
 		("The desired opacity of the layer bin"),
 	)]
-	async fn main(self, opacity: u16) {
-		return self.0.set_blind_opacity(opacity).await;
+	async fn main(self, zone_id: Option::<Uuid>, opacity: u16) {
+		return self.0.set_blind_opacity(zone_id, opacity).await;
 	}
 }
 
@@ -56,10 +73,11 @@ impl GetBlindOpacity {
 	}
 
 	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
 		("The opacity of the blind layer bin. This will be None or null if blind mode is inactive"),
 	)]
-	async fn main(self) -> Option<u16> {
-		return self.0.get_blind_opacity().await;
+	async fn main(self, zone_id: Option::<Uuid>) -> Option<u16> {
+		return self.0.get_blind_opacity(zone_id).await;
 	}
 }
 
@@ -74,9 +92,11 @@ impl RevertBlind {
 		Self(mixer_interface)
 	}
 
-	#[service_main()]
-	async fn main(self) {
-		return self.0.revert_blind().await;
+	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
+	)]
+	async fn main(self, zone_id: Option::<Uuid>) {
+		return self.0.revert_blind(zone_id).await;
 	}
 }
 
@@ -91,9 +111,233 @@ impl CommitBlind {
 		Self(mixer_interface)
 	}
 
-	#[service_main()]
-	async fn main(self) {
-		return self.0.commit_blind().await;
+	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
+	)]
+	async fn main(self, zone_id: Option::<Uuid>) {
+		return self.0.commit_blind(zone_id).await;
+	}
+}
+
+// ┌─────────────────────┐
+// │    Zone Commands    │
+// └─────────────────────┘
+
+#[interpolate_service(
+	"create_zone",
+	"Create Zone",
+	"Creates a new mixing zone with its own layer stack, blind mode, and grand master"
+)]
+impl CreateZone {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The name of the new zone"),
+		("The ID of the new zone"),
+	)]
+	async fn main(self, name: String) -> Uuid {
+		return self.0.create_zone(name).await;
+	}
+}
+
+#[interpolate_service(
+	"rename_zone",
+	"Rename Zone",
+	"Renames a mixing zone"
+)]
+impl RenameZone {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
+		("The new name of the zone"),
+		("The result of the operation"),
+	)]
+	async fn main(self, zone_id: Option::<Uuid>, name: String) -> Result<(), ZoneError> {
+		return self.0.rename_zone(zone_id, name).await;
+	}
+}
+
+#[interpolate_service(
+	"delete_zone",
+	"Delete Zone",
+	"Deletes a mixing zone, moving its layers and fixtures back to the main zone"
+)]
+impl DeleteZone {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The zone to delete", "mixer::zone_id"),
+		("Whether or not the zone was deleted. Zones can't be deleted while they are in blind mode."),
+	)]
+	async fn main(self, zone_id: Uuid) -> Result<(), ZoneError> {
+		return self.0.delete_zone(zone_id).await;
+	}
+}
+
+#[interpolate_service(
+	"list_zones",
+	"List Zones",
+	"Lists all mixing zones, starting with the main zone"
+)]
+impl ListZones {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The mixing zones in the show"),
+	)]
+	async fn main(self) -> Vec<ZoneDescription> {
+		return self.0.list_zones().await;
+	}
+}
+
+#[interpolate_service(
+	"set_fixture_zone",
+	"Set Fixture Zone",
+	"Assigns fixtures to a mixing zone. Fixtures only receive values from layers in their own zone."
+)]
+impl SetFixtureZone {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The fixtures to assign"),
+		("The zone to assign the fixtures to. None or null refers to the main zone", "mixer::zone_id"),
+		("The result of the operation"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>, zone_id: Option::<Uuid>) -> Result<(), ZoneError> {
+		return self.0.set_fixture_zone(fixture_ids, zone_id).await;
+	}
+}
+
+#[interpolate_service(
+	"get_fixture_zones",
+	"Get Fixture Zones",
+	"Gets the zone assignment of every fixture that is not in the main zone"
+)]
+impl GetFixtureZones {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("A map of fixture IDs to zone IDs"),
+	)]
+	async fn main(self) -> HashMap<Uuid, Uuid> {
+		return self.0.get_fixture_zones().await;
+	}
+}
+
+#[interpolate_service(
+	"move_layer_to_zone",
+	"Move Layer to Zone",
+	"Moves a layer to another mixing zone, keeping its level"
+)]
+impl MoveLayerToZone {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The layer to move", "mixer::layer_id"),
+		("The zone to move the layer to. None or null refers to the main zone", "mixer::zone_id"),
+		("The result of the operation"),
+	)]
+	async fn main(self, submaster_id: Uuid, zone_id: Option::<Uuid>) -> Result<(), ZoneError> {
+		return self.0.move_layer_to_zone(submaster_id, zone_id).await;
+	}
+}
+
+#[interpolate_service(
+	"set_grand_master",
+	"Set Grand Master",
+	"Sets the grand master of a zone, which scales the intensity of every fixture in the zone"
+)]
+impl SetGrandMaster {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
+		("The new grand master level"),
+		("The result of the operation"),
+	)]
+	async fn main(self, zone_id: Option::<Uuid>, level: u16) -> Result<(), ZoneError> {
+		return self.0.set_grand_master(zone_id, level).await;
+	}
+}
+
+#[interpolate_service(
+	"get_grand_master",
+	"Get Grand Master",
+	"Gets the grand master level of a zone"
+)]
+impl GetGrandMaster {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The zone to operate on. None or null refers to the main zone", "mixer::zone_id"),
+		("The grand master level. This will be None or null if the zone does not exist"),
+	)]
+	async fn main(self, zone_id: Option::<Uuid>) -> Option<u16> {
+		return self.0.get_grand_master(zone_id).await;
+	}
+}
+
+// ┌────────────────────────┐
+// │    Fixture Commands    │
+// └────────────────────────┘
+
+#[interpolate_service(
+	"replace_fixture_type",
+	"Replace Fixture Type",
+	"Swaps existing fixtures to a different fixture type, migrating their layer data to the new type's attributes"
+)]
+impl ReplaceFixtureType {
+	#![inner_raw(MixerInterface, PatcherInterface)]
+	pub fn new(mixer_interface: MixerInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(mixer_interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The fixture instances to change"),
+		("The fixture type to switch to", "fixture-type-uuid"),
+		("The personality of the new fixture type to use"),
+		("Maps old attribute IDs to new ones. Attributes not listed here are matched by ID."),
+		("Whether or not the fixture type was replaced"),
+	)]
+	async fn main(
+		self,
+		instance_ids: Vec::<Uuid>,
+		fixture_type: Uuid,
+		personality: String,
+		attribute_map: FxHashMap::<String, String>,
+	) -> Result<(), ReplaceFixtureTypeError> {
+		return self
+			.0
+			.replace_fixture_type(&self.1, instance_ids, fixture_type, personality, attribute_map)
+			.await;
 	}
 }
 
@@ -184,6 +428,70 @@ impl SetLayerContents {
 	}
 }
 
+#[interpolate_service(
+	"set_layer_segment",
+	"Set Layer Segment",
+	"Sets an attribute to a named segment, such as a gobo or color, across a selection of fixtures"
+)]
+impl SetLayerSegment {
+	#![inner_raw(MixerInterface, PatcherInterface)]
+	pub fn new(mixer_interface: MixerInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(mixer_interface, patcher_interface)
+	}
+
+	#[service_main(
+		("UUID value to identify the submaster", "mixer::layer_id"),
+		("The UUIDs of the selected fixtures or cells"),
+		("The ID of the attribute to set, as used in the mixer"),
+		("The name of the segment, ignoring case"),
+		("The fixtures that were set, in selection order"),
+	)]
+	async fn main(
+		self,
+		submaster_id: Uuid,
+		fixture_ids: Vec::<Uuid>,
+		attribute_id: String,
+		segment_name: String,
+	) -> Result<Vec<Uuid>, SegmentSelectionError> {
+		return self
+			.0
+			.set_layer_segment(&self.1, submaster_id, fixture_ids, attribute_id, segment_name)
+			.await;
+	}
+}
+
+#[interpolate_service(
+	"set_layer_physical",
+	"Set Layer Physical Value",
+	"Sets an attribute to a physical value, such as 90 degrees of pan or 3200K, across a selection of fixtures"
+)]
+impl SetLayerPhysical {
+	#![inner_raw(MixerInterface, PatcherInterface)]
+	pub fn new(mixer_interface: MixerInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(mixer_interface, patcher_interface)
+	}
+
+	#[service_main(
+		("UUID value to identify the submaster", "mixer::layer_id"),
+		("The UUIDs of the selected fixtures or cells"),
+		("The ID of the attribute to set, as used in the mixer"),
+		("The physical value to set"),
+		("The fixtures that were set, in selection order"),
+	)]
+	async fn main(
+		self,
+		submaster_id: Uuid,
+		fixture_ids: Vec::<Uuid>,
+		attribute_id: String,
+		value: PhysicalValue,
+	) -> Result<Vec<Uuid>, PhysicalValueError> {
+		return self
+			.0
+			.set_layer_physical(&self.1, submaster_id, fixture_ids, attribute_id, value)
+			.await;
+	}
+}
+
 #[interpolate_service(
 	"get_layer_contents",
 	"Get Layer Contents",
@@ -265,6 +573,72 @@ impl DeleteLayer {
 	}
 }
 
+#[interpolate_service(
+	"duplicate_layer",
+	"Duplicate Layer",
+	"Duplicates a layer, inserting the copy into the live or blind context"
+)]
+impl DuplicateLayer {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the submaster to duplicate", "mixer::layer_id"),
+		("The name to give the copy. Defaults to the original name with \"(copy)\" appended"),
+		("The context the copy should be inserted into"),
+		("The UUID of the new submaster", "mixer::layer_id"),
+	)]
+	async fn main(self, submaster_id: Uuid, name: Option::<String>, target: LayerContextTarget) -> Result<Uuid, LayerOperationError> {
+		return self.0.duplicate_layer(submaster_id, name, target).await;
+	}
+}
+
+#[interpolate_service(
+	"merge_layers",
+	"Merge Layers",
+	"Merges several layers into a new one, resolving conflicting values with the given rule"
+)]
+impl MergeLayers {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUIDs of the submasters to merge, in priority order"),
+		("The name to assign to the merged submaster"),
+		("The rule used to resolve conflicting values"),
+		("Whether or not the original submasters should be deleted"),
+		("The UUID of the merged submaster", "mixer::layer_id"),
+	)]
+	async fn main(self, submaster_ids: Vec::<Uuid>, name: String, rule: MergeRule, delete_sources: bool) -> Result<Uuid, LayerOperationError> {
+		return self.0.merge_layers(submaster_ids, name, rule, delete_sources).await;
+	}
+}
+
+#[interpolate_service(
+	"split_layer",
+	"Split Layer",
+	"Splits a layer into several new layers by fixture or attribute. Unmatched values remain in the original layer."
+)]
+impl SplitLayer {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the submaster to split", "mixer::layer_id"),
+		("Describes the groups of fixtures or attributes to move into new layers"),
+		("The UUIDs of the new submasters, in the order their groups were given"),
+	)]
+	async fn main(self, submaster_id: Uuid, split: LayerSplit) -> Result<Vec<Uuid>, LayerOperationError> {
+		return self.0.split_layer(submaster_id, split).await;
+	}
+}
+
 #[interpolate_service(
 	"request_blend",
 	"Request Reblend",
@@ -281,3 +655,464 @@ impl RequestBlend {
 		return self.0.request_blend();
 	}
 }
+
+#[interpolate_service(
+	"set_layer_link",
+	"Set Layer Link",
+	"Links a layer's opacity to one or more master layers. The effective opacity is calculated by the blender."
+)]
+impl SetLayerLink {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the layer to be controlled", "mixer::layer_id"),
+		("The UUIDs of the master layers. An empty list removes the link."),
+		("How the layer's opacity is combined with the masters' opacities"),
+		("The result of the operation"),
+	)]
+	async fn main(self, submaster_id: Uuid, masters: Vec::<Uuid>, mode: LinkMode) -> Result<(), LinkError> {
+		return self.0.set_layer_link(submaster_id, masters, mode).await;
+	}
+}
+
+#[interpolate_service(
+	"get_layer_link",
+	"Get Layer Link",
+	"Gets the master layers controlling a layer's opacity"
+)]
+impl GetLayerLink {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID that identifies the submaster in question", "mixer::layer_id"),
+		("The layer's link, or None or null if it is not linked"),
+	)]
+	async fn main(self, submaster_id: Uuid) -> Option<LayerLink> {
+		return self.0.get_layer_link(submaster_id).await;
+	}
+}
+
+#[interpolate_service(
+	"get_effective_opacity",
+	"Get Effective Opacity",
+	"Gets the opacity of a layer after links to master layers have been applied"
+)]
+impl GetEffectiveOpacity {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID that identifies the submaster in question", "mixer::layer_id"),
+		("The effective opacity of the layer. This will be None or null if the layer has no opacity set"),
+	)]
+	async fn main(self, submaster_id: Uuid) -> Option<u16> {
+		return self.0.get_effective_opacity(submaster_id).await;
+	}
+}
+
+// ┌─────────────────────────────────┐
+// │    Expression Layer Commands    │
+// └─────────────────────────────────┘
+
+#[interpolate_service(
+	"create_expression_layer",
+	"Create Expression Layer",
+	"Creates a new layer whose values are calculated from expressions every frame"
+)]
+impl CreateExpressionLayer {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The name of the new layer"),
+		("UUID value that should be used from this point forward to identify the submaster", "mixer::layer_id"),
+	)]
+	async fn main(self, name: String) -> Uuid {
+		return self.0.create_expression_layer(name).await;
+	}
+}
+
+#[interpolate_service(
+	"set_expression_layer_contents",
+	"Set Expression Layer Contents",
+	"Replaces the fixtures and per-attribute expressions of an expression layer. Expressions should produce values from 0 to 1."
+)]
+impl SetExpressionLayerContents {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID that identifies the submaster to be changed", "mixer::layer_id"),
+		("The fixtures controlled by the layer, in index order"),
+		("The expression source for each attribute ID"),
+		("The result of the operation. Nothing is changed if an expression is invalid."),
+	)]
+	async fn main(self, submaster_id: Uuid, fixtures: Vec::<Uuid>, expressions: FxHashMap::<String, String>) -> Result<(), ExpressionLayerError> {
+		return self.0.set_expression_layer_contents(submaster_id, fixtures, expressions).await;
+	}
+}
+
+#[interpolate_service(
+	"get_expression_layer",
+	"Get Expression Layer",
+	"Gets the fixtures and expressions of an expression layer"
+)]
+impl GetExpressionLayer {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID that identifies the submaster in question", "mixer::layer_id"),
+		("The expression layer, or None or null if it does not exist"),
+	)]
+	async fn main(self, submaster_id: Uuid) -> Option<ExpressionLayer> {
+		return self.0.get_expression_layer(submaster_id).await;
+	}
+}
+
+#[interpolate_service(
+	"validate_expression",
+	"Validate Expression",
+	"Checks if an expression is valid without assigning it to a layer"
+)]
+impl ValidateExpression {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The expression source to check"),
+		("The result of parsing the expression"),
+	)]
+	async fn main(self, source: String) -> Result<(), ExpressionError> {
+		return self.0.validate_expression(&source);
+	}
+}
+
+#[interpolate_service(
+	"set_tempo",
+	"Set Tempo",
+	"Sets the tempo used for the `beat` variable in expression layers"
+)]
+impl SetTempo {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The tempo in beats per minute"),
+	)]
+	async fn main(self, bpm: f64) {
+		return self.0.set_tempo(bpm).await;
+	}
+}
+
+#[interpolate_service(
+	"get_tempo",
+	"Get Tempo",
+	"Gets the tempo used for the `beat` variable in expression layers"
+)]
+impl GetTempo {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The tempo in beats per minute"),
+	)]
+	async fn main(self) -> f64 {
+		return self.0.get_tempo().await;
+	}
+}
+
+// ┌─────────────────────┐
+// │    Bank Commands    │
+// └─────────────────────┘
+
+#[interpolate_service(
+	"create_bank",
+	"Create Fader Bank",
+	"Creates a new fader bank with a single, empty page"
+)]
+impl CreateBank {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The name to assign to the bank"),
+		("The number of fader slots available on each page of the bank"),
+		("The UUID of the new bank", "mixer::bank_id"),
+	)]
+	async fn main(self, name: String, slot_count: u16) -> Uuid {
+		return self.0.create_bank(name, slot_count).await;
+	}
+}
+
+#[interpolate_service(
+	"rename_bank",
+	"Rename Fader Bank",
+	"Renames a fader bank"
+)]
+impl RenameBank {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank to rename", "mixer::bank_id"),
+		("The new name for the bank"),
+		("Result indicating whether or not the bank was renamed"),
+	)]
+	async fn main(self, bank_id: Uuid, name: String) -> Result<(), BankError> {
+		return self.0.rename_bank(bank_id, name).await;
+	}
+}
+
+#[interpolate_service(
+	"delete_bank",
+	"Delete Fader Bank",
+	"Deletes a fader bank. Layers assigned to the bank are not affected."
+)]
+impl DeleteBank {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank to delete", "mixer::bank_id"),
+		("Whether or not the bank existed"),
+	)]
+	async fn main(self, bank_id: Uuid) -> bool {
+		return self.0.delete_bank(bank_id).await;
+	}
+}
+
+#[interpolate_service(
+	"list_banks",
+	"List Fader Banks",
+	"Lists all fader banks"
+)]
+impl ListBanks {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("An array of (id, name) tuples representing the fader banks in the mixer"),
+	)]
+	async fn main(self) -> Vec<(Uuid, String)> {
+		return self.0.list_banks().await;
+	}
+}
+
+#[interpolate_service(
+	"get_bank",
+	"Get Fader Bank",
+	"Retrieves the pages and assignments of a fader bank"
+)]
+impl GetBank {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The bank's configuration, or None/null if it does not exist"),
+	)]
+	async fn main(self, bank_id: Uuid) -> Option<FaderBank> {
+		return self.0.get_bank(bank_id).await;
+	}
+}
+
+#[interpolate_service(
+	"add_bank_page",
+	"Add Bank Page",
+	"Adds a new, empty page to the end of a fader bank"
+)]
+impl AddBankPage {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The name to assign to the page"),
+		("The index of the new page"),
+	)]
+	async fn main(self, bank_id: Uuid, name: String) -> Result<usize, BankError> {
+		return self.0.add_bank_page(bank_id, name).await;
+	}
+}
+
+#[interpolate_service(
+	"delete_bank_page",
+	"Delete Bank Page",
+	"Deletes a page from a fader bank. The last remaining page cannot be deleted."
+)]
+impl DeleteBankPage {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The index of the page to delete"),
+		("Result indicating whether or not the page was deleted"),
+	)]
+	async fn main(self, bank_id: Uuid, page: usize) -> Result<(), BankError> {
+		return self.0.delete_bank_page(bank_id, page).await;
+	}
+}
+
+#[interpolate_service(
+	"assign_fader",
+	"Assign Fader",
+	"Assigns a layer to a fader slot on a page of a bank"
+)]
+impl AssignFader {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The index of the page to modify"),
+		("The index of the fader slot to assign"),
+		("The UUID of the layer to assign, or None/null to clear the slot"),
+		("Result indicating whether or not the assignment succeeded"),
+	)]
+	async fn main(self, bank_id: Uuid, page: usize, slot: u16, layer_id: Option::<Uuid>) -> Result<(), BankError> {
+		return self.0.assign_fader(bank_id, page, slot, layer_id).await;
+	}
+}
+
+#[interpolate_service(
+	"set_bank_page",
+	"Set Bank Page",
+	"Switches the page that is loaded onto a bank's faders"
+)]
+impl SetBankPage {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The index of the page to load"),
+		("Result indicating whether or not the page was loaded"),
+	)]
+	async fn main(self, bank_id: Uuid, page: usize) -> Result<(), BankError> {
+		return self.0.set_bank_page(bank_id, page).await;
+	}
+}
+
+#[interpolate_service(
+	"step_bank_page",
+	"Step Bank Page",
+	"Moves a bank forward or backward by the given number of pages, wrapping around at either end"
+)]
+impl StepBankPage {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The number of pages to move. Negative values move backwards."),
+		("The index of the newly-loaded page"),
+	)]
+	async fn main(self, bank_id: Uuid, delta: i32) -> Result<usize, BankError> {
+		return self.0.step_bank_page(bank_id, delta).await;
+	}
+}
+
+#[interpolate_service(
+	"set_bank_pickup_mode",
+	"Set Bank Pickup Mode",
+	"Sets how a bank's physical faders take control of layers whose level differs from the fader position"
+)]
+impl SetBankPickupMode {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The pickup mode to use"),
+		("Result indicating whether or not the mode was set"),
+	)]
+	async fn main(self, bank_id: Uuid, pickup_mode: PickupMode) -> Result<(), BankError> {
+		return self.0.set_bank_pickup_mode(bank_id, pickup_mode).await;
+	}
+}
+
+#[interpolate_service(
+	"move_fader",
+	"Move Fader",
+	"Reports the position of a physical fader, setting the opacity of its assigned layer once the fader has been picked up"
+)]
+impl MoveFader {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The index of the fader slot that moved"),
+		("The new position of the fader, from 0 to 65535"),
+		("The state of the fader after the move, for feedback"),
+	)]
+	async fn main(self, bank_id: Uuid, slot: u16, position: u16) -> Result<FaderSlotState, BankError> {
+		return self.0.move_fader(bank_id, slot, position).await;
+	}
+}
+
+#[interpolate_service(
+	"get_bank_state",
+	"Get Bank State",
+	"Gets the state of every fader slot on a bank's current page, for control surface feedback"
+)]
+impl GetBankState {
+	#![inner_raw(MixerInterface)]
+	pub fn new(mixer_interface: MixerInterface) -> Self {
+		Self(mixer_interface)
+	}
+
+	#[service_main(
+		("The UUID of the bank", "mixer::bank_id"),
+		("The state of each fader slot, in slot order"),
+	)]
+	async fn main(self, bank_id: Uuid) -> Result<Vec<FaderSlotState>, BankError> {
+		return self.0.get_bank_state(bank_id).await;
+	}
+}
@@ -0,0 +1,167 @@
+use crate::mixer_utils::state::{BlenderValue, SubmasterData};
+use rustc_hash::FxHashMap;
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[portable]
+/// Selects which mixing context an operation should apply to
+pub enum LayerContextTarget {
+	/// Applies to the live look. If blind mode is active, the change is made to both the
+	/// live and blind contexts so it survives reverting or committing the blind changes.
+	Live,
+
+	/// Applies only to the blind context. The change is discarded if blind mode is reverted.
+	Blind,
+}
+
+#[portable]
+/// The rule used to resolve conflicts when merging layers that contain values for the same attribute
+pub enum MergeRule {
+	/// Latest Takes Priority. Values from layers later in the list replace earlier ones.
+	#[serde(rename = "LTP")]
+	Ltp,
+
+	/// Highest Takes Priority. The highest static value or offset is kept. Static values
+	/// take priority over offsets.
+	#[serde(rename = "HTP")]
+	Htp,
+
+	/// Lowest Takes Priority. The lowest static value or offset is kept. Static values
+	/// take priority over offsets.
+	#[serde(rename = "LoTP")]
+	Lotp,
+}
+
+#[portable]
+/// Describes how a layer should be split into several new layers
+pub enum LayerSplit {
+	/// Moves the values for each group of fixtures into its own layer
+	Fixtures(Vec<FixtureSplitGroup>),
+
+	/// Moves the values for each group of attributes into its own layer
+	Attributes(Vec<AttributeSplitGroup>),
+}
+
+#[portable]
+/// A group of fixtures to be moved into a new layer
+pub struct FixtureSplitGroup {
+	/// The name to assign to the new layer
+	pub name: String,
+
+	/// The fixtures whose values should be moved into the new layer
+	pub fixtures: Vec<Uuid>,
+}
+
+#[portable]
+/// A family of attributes to be moved into a new layer
+pub struct AttributeSplitGroup {
+	/// The name to assign to the new layer
+	pub name: String,
+
+	/// The attribute IDs whose values should be moved into the new layer
+	pub attributes: Vec<String>,
+}
+
+/// Merges the values of `source` into `target` using the given rule
+pub fn merge_values(target: &mut SubmasterData, source: &SubmasterData, rule: &MergeRule) {
+	for (fixture_id, source_fixture) in source.iter() {
+		let target_fixture = target.entry(*fixture_id).or_default();
+		for (attribute_id, source_value) in source_fixture.iter() {
+			if let BlenderValue::None = source_value {
+				continue;
+			}
+			let merged_value = match target_fixture.get(attribute_id) {
+				Some(target_value) => merge_value(target_value, source_value, rule),
+				None => source_value.clone(),
+			};
+			target_fixture.insert(attribute_id.clone(), merged_value);
+		}
+	}
+}
+
+/// Resolves a conflict between two values. `current` comes from an earlier layer than `new`.
+fn merge_value(current: &BlenderValue, new: &BlenderValue, rule: &MergeRule) -> BlenderValue {
+	return match rule {
+		MergeRule::Ltp => new.clone(),
+		MergeRule::Htp | MergeRule::Lotp => {
+			let prefer_high = matches!(rule, MergeRule::Htp);
+			match (current, new) {
+				(BlenderValue::None, value) | (value, BlenderValue::None) => value.clone(),
+				(BlenderValue::Static(current_value), BlenderValue::Static(new_value)) => {
+					BlenderValue::Static(if prefer_high {
+						*current_value.max(new_value)
+					} else {
+						*current_value.min(new_value)
+					})
+				}
+				(BlenderValue::Offset(current_value), BlenderValue::Offset(new_value)) => {
+					BlenderValue::Offset(if prefer_high {
+						*current_value.max(new_value)
+					} else {
+						*current_value.min(new_value)
+					})
+				}
+				(BlenderValue::Static(value), BlenderValue::Offset(_))
+				| (BlenderValue::Offset(_), BlenderValue::Static(value)) => BlenderValue::Static(*value),
+			}
+		}
+	};
+}
+
+/// Removes the values for the given fixtures from `source`, returning them
+pub fn take_fixtures(source: &mut SubmasterData, fixtures: &[Uuid]) -> SubmasterData {
+	let mut taken = SubmasterData::default();
+	for fixture_id in fixtures {
+		if let Some(fixture_data) = source.remove(fixture_id) {
+			taken.insert(*fixture_id, fixture_data);
+		}
+	}
+	return taken;
+}
+
+/// Removes the values for the given attributes from `source`, returning them
+pub fn take_attributes(source: &mut SubmasterData, attributes: &[String]) -> SubmasterData {
+	let mut taken = SubmasterData::default();
+	for (fixture_id, fixture_data) in source.iter_mut() {
+		let mut taken_fixture = FxHashMap::default();
+		for attribute_id in attributes {
+			if let Some(value) = fixture_data.remove(attribute_id) {
+				taken_fixture.insert(attribute_id.clone(), value);
+			}
+		}
+		if !taken_fixture.is_empty() {
+			taken.insert(*fixture_id, taken_fixture);
+		}
+	}
+	source.retain(|_, fixture_data| !fixture_data.is_empty());
+	return taken;
+}
+
+/// Builds a delta for the `mixer.submaster_updated` event that clears the values in `removed`
+pub fn removal_delta(removed: &SubmasterData) -> SubmasterData {
+	return removed
+		.iter()
+		.map(|(fixture_id, fixture_data)| {
+			(
+				*fixture_id,
+				fixture_data
+					.keys()
+					.map(|attribute_id| (attribute_id.clone(), BlenderValue::None))
+					.collect(),
+			)
+		})
+		.collect();
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while duplicating, merging, or splitting layers
+pub enum LayerOperationError {
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("Blind mode is not active")]
+	BlindInactive,
+	#[error("At least one layer must be specified")]
+	NoLayers,
+}
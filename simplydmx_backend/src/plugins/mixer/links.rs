@@ -0,0 +1,110 @@
+use rustc_hash::FxHashMap;
+use simplydmx_plugin_framework::*;
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// The maximum length of a chain of linked layers. Cycles are rejected when links are created,
+/// so this only guards against corrupted show files.
+const MAX_LINK_DEPTH: usize = 16;
+
+#[portable]
+/// Links a layer's opacity to the opacity of one or more master layers
+pub struct LayerLink {
+	/// The layers whose opacities drive this layer
+	pub masters: Vec<Uuid>,
+
+	/// How the layer's own opacity is combined with its masters' opacities
+	pub mode: LinkMode,
+}
+
+#[portable]
+/// Determines how a linked layer's opacity is combined with its masters' opacities
+pub enum LinkMode {
+	/// Multiplies the layer's opacity by each master's opacity. This is the classic "scene master".
+	Multiply,
+
+	/// Uses the lowest of the layer's opacity and its masters' opacities
+	Min,
+
+	/// Uses the highest of the layer's opacity and its masters' opacities.
+	///
+	/// Layers are only blended while they are in the stack, so a master can only raise the
+	/// level of a layer that has been inserted.
+	Max,
+}
+
+/// Calculates the effective opacity of every layer, resolving links to master layers.
+///
+/// `opacities` and `links` must contain the layers of every zone, since masters may live in
+/// a different zone than the layers they control. Each layer is resolved once, so masters shared
+/// by many layers don't get walked again for every layer they control.
+pub fn effective_opacities(
+	opacities: &FxHashMap<Uuid, u16>,
+	links: &HashMap<Uuid, &LayerLink>,
+) -> FxHashMap<Uuid, u16> {
+	let mut resolved = FxHashMap::<Uuid, u16>::default();
+	for layer_id in opacities.keys() {
+		effective_opacity(layer_id, opacities, links, &mut resolved, 0);
+	}
+	resolved.retain(|layer_id, _| opacities.contains_key(layer_id));
+	return resolved;
+}
+
+fn effective_opacity(
+	layer_id: &Uuid,
+	opacities: &FxHashMap<Uuid, u16>,
+	links: &HashMap<Uuid, &LayerLink>,
+	resolved: &mut FxHashMap<Uuid, u16>,
+	depth: usize,
+) -> u16 {
+	if let Some(opacity) = resolved.get(layer_id) {
+		return *opacity;
+	}
+
+	let own_opacity = opacities.get(layer_id).cloned().unwrap_or(0);
+	let opacity = match links.get(layer_id) {
+		Some(link) if depth < MAX_LINK_DEPTH => link.masters.iter().fold(own_opacity, |opacity, master_id| {
+			let master_opacity = effective_opacity(master_id, opacities, links, resolved, depth + 1);
+			match link.mode {
+				LinkMode::Multiply => (opacity as u32 * master_opacity as u32 / u16::MAX as u32) as u16,
+				LinkMode::Min => opacity.min(master_opacity),
+				LinkMode::Max => opacity.max(master_opacity),
+			}
+		}),
+		_ => own_opacity,
+	};
+	resolved.insert(*layer_id, opacity);
+	return opacity;
+}
+
+/// Checks if linking `layer_id` to `masters` would cause a layer to control itself
+pub fn creates_cycle(layer_id: &Uuid, masters: &[Uuid], links: &HashMap<Uuid, &LayerLink>) -> bool {
+	let mut pending: Vec<Uuid> = masters.to_vec();
+	let mut visited: Vec<Uuid> = Vec::new();
+	while let Some(master_id) = pending.pop() {
+		if &master_id == layer_id {
+			return true;
+		}
+		if visited.contains(&master_id) {
+			continue;
+		}
+		visited.push(master_id);
+		if let Some(link) = links.get(&master_id) {
+			pending.extend(link.masters.iter());
+		}
+	}
+	return false;
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while linking layers
+pub enum LinkError {
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("A master layer does not exist")]
+	MasterMissing,
+	#[error("A layer cannot be linked to itself, directly or through other links")]
+	Cycle,
+}
@@ -1,23 +1,38 @@
+mod banks;
 mod blender;
 mod commands;
+mod layer_operations;
+mod links;
 mod state;
+mod zones;
 
 use super::{
-	patcher::PatcherInterface,
+	patcher::{
+		driver_plugin_api::{PhysicalUnit, PhysicalValue},
+		PatcherInterface, ReplaceFixtureTypeError,
+	},
 	saver::{Savable, SaverInterface},
 };
 use crate::mixer_utils::{
+	expression::{Expression, ExpressionError},
+	expression_layer::{ExpressionLayer, ExpressionLayerError},
+	migration::AttributeMigration,
 	state::{BlenderValue, FullMixerOutput, SubmasterData},
 	static_layer::StaticLayer,
 };
 use async_trait::async_trait;
+use banks::{BankError, FaderBank, FaderPage, FaderSlotState, PickupMode};
+use layer_operations::{LayerContextTarget, LayerOperationError, LayerSplit, MergeRule};
+use links::{LayerLink, LinkError, LinkMode};
 use rustc_hash::FxHashMap;
 use simplydmx_plugin_framework::*;
-use state::MixerContext;
+use state::{MixerContext, MixingZone};
 use std::sync::Arc;
 use thiserror::Error;
+use std::collections::HashMap;
 use tokio::sync::{RwLock, Notify};
 use uuid::Uuid;
+use zones::{ZoneDescription, ZoneError};
 
 pub async fn initialize_mixer(
 	plugin_context: PluginContext,
@@ -40,9 +55,9 @@ pub async fn initialize_mixer(
 	// Declare events
 
 	plugin_context
-		.declare_event::<()>(
+		.declare_event::<Option<Uuid>>(
 			"mixer.blind".into(),
-			Some("Emitted when blind mode is enabled or disabled".into()),
+			Some("Emitted with the zone ID when a zone enters or leaves blind mode. The main zone is None or null.".into()),
 		)
 		.await
 		.unwrap();
@@ -71,6 +86,62 @@ pub async fn initialize_mixer(
 		.await
 		.unwrap();
 
+	plugin_context
+		.declare_event::<()>(
+			"mixer.banks_updated".into(),
+			Some("Emitted when a fader bank is created, renamed, or deleted".into()),
+		)
+		.await
+		.unwrap();
+
+	plugin_context
+		.declare_event::<Vec<FaderSlotState>>(
+			"mixer.bank_updated".into(),
+			Some("Emitted when a fader bank's pages or assignments change. Filter is the UUID of the bank.".into()),
+		)
+		.await
+		.unwrap();
+
+	plugin_context
+		.declare_event::<()>(
+			"mixer.expression_layer_updated".into(),
+			Some("Emitted when the fixtures or expressions of an expression layer change. Filtered by layer ID.".into()),
+		)
+		.await
+		.unwrap();
+
+	plugin_context
+		.declare_event::<f64>(
+			"mixer.tempo".into(),
+			Some("Emitted when the tempo used by expression layers changes".into()),
+		)
+		.await
+		.unwrap();
+
+	plugin_context
+		.declare_event::<()>(
+			"mixer.layer_links_updated".into(),
+			Some("Emitted when a layer is linked to or unlinked from master layers".into()),
+		)
+		.await
+		.unwrap();
+
+	plugin_context
+		.declare_event::<()>(
+			"mixer.zones_updated".into(),
+			Some("Emitted when zones are created, renamed, or deleted, or when fixtures or layers move between zones".into()),
+		)
+		.await
+		.unwrap();
+
+	plugin_context
+		.declare_event::<(Option<Uuid>, u16)>(
+			"mixer.grand_master".into(),
+			Some("Emitted when a zone's grand master changes. The zone ID is None/null for the main zone.".into()),
+		)
+		.await
+		.unwrap();
+
 	plugin_context
 		.declare_event::<FullMixerOutput>(
 			"mixer.final_output".into(),
@@ -81,7 +152,7 @@ pub async fn initialize_mixer(
 
 	// Start blender task
 	let update_sender =
-		blender::start_blender(plugin_context.clone(), Arc::clone(&mixer_context), patcher).await;
+		blender::start_blender(plugin_context.clone(), Arc::clone(&mixer_context), patcher.clone()).await;
 
 	// Send kickstart to blender task to recover any data that was saved
 	// TODO: Verify this is no longer needed due to the switch from wait-then-run rate-limiting to run-then-wait
@@ -131,6 +202,14 @@ pub async fn initialize_mixer(
 		.register_service(true, commands::GetLayerContents::new(interface.clone()))
 		.await
 		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetLayerSegment::new(interface.clone(), patcher.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetLayerPhysical::new(interface.clone(), patcher.clone()))
+		.await
+		.unwrap();
 	plugin_context
 		.register_service(true, commands::SetLayerOpacity::new(interface.clone()))
 		.await
@@ -143,10 +222,152 @@ pub async fn initialize_mixer(
 		.register_service(true, commands::DeleteLayer::new(interface.clone()))
 		.await
 		.unwrap();
+	plugin_context
+		.register_service(true, commands::DuplicateLayer::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::MergeLayers::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SplitLayer::new(interface.clone()))
+		.await
+		.unwrap();
 	plugin_context
 		.register_service(true, commands::RequestBlend::new(interface.clone()))
 		.await
 		.unwrap();
+	plugin_context
+		.register_service(true, commands::CreateBank::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::RenameBank::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::DeleteBank::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::ListBanks::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetBank::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::AddBankPage::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::DeleteBankPage::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::AssignFader::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetBankPage::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::StepBankPage::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetBankPickupMode::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::MoveFader::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetBankState::new(interface.clone()))
+		.await
+		.unwrap();
+
+	plugin_context
+		.register_service(true, commands::SetLayerLink::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetLayerLink::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetEffectiveOpacity::new(interface.clone()))
+		.await
+		.unwrap();
+
+	plugin_context
+		.register_service(true, commands::CreateExpressionLayer::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetExpressionLayerContents::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetExpressionLayer::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::ValidateExpression::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetTempo::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetTempo::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::CreateZone::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::RenameZone::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::DeleteZone::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::ListZones::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetFixtureZone::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetFixtureZones::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::MoveLayerToZone::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::SetGrandMaster::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::GetGrandMaster::new(interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, commands::ReplaceFixtureType::new(interface.clone(), patcher.clone()))
+		.await
+		.unwrap();
 
 	// Register type specifiers
 	plugin_context
@@ -156,6 +377,20 @@ pub async fn initialize_mixer(
 		)
 		.await
 		.unwrap();
+	plugin_context
+		.register_service_type_specifier(
+			"fader_banks".into(),
+			FaderBankTypeSpecifier(interface.clone()),
+		)
+		.await
+		.unwrap();
+	plugin_context
+		.register_service_type_specifier(
+			"mixer_zones".into(),
+			ZoneTypeSpecifier(interface.clone()),
+		)
+		.await
+		.unwrap();
 
 	// Register saving mechanism
 	saver
@@ -179,39 +414,44 @@ impl MixerInterface {
 
 	/// Copies the default layer bin to a new one with 0 opacity, setting it as the new default.
 	///
-	/// Returns The UUID for the old layer bin. Returns None/null if blind mode is already active.
-	pub async fn enter_blind_mode(&self) -> () {
+	/// `zone_id` selects the zone to enter blind mode in. `None` refers to the main zone.
+	pub async fn enter_blind_mode(&self, zone_id: Option<Uuid>) -> () {
 		// Get resources
 		let mut ctx = self.1.write().await;
 
-		if ctx.frozen_context.is_none() {
-			ctx.blind_opacity = 0;
-			ctx.frozen_context = Some(ctx.default_context.clone());
+		if let Some(zone) = ctx.zone_mut(zone_id.as_ref()) {
+			if zone.frozen_context.is_none() {
+				zone.blind_opacity = 0;
+				zone.frozen_context = Some(zone.default_context.clone());
 
-			self.0
-				.emit("mixer.blind".into(), FilterCriteria::None, true)
-				.await;
-			// self.2.send(()).await; // Not needed because changes would not be visible
+				self.0
+					.emit("mixer.blind".into(), FilterCriteria::None, zone_id)
+					.await;
+				// self.2.send(()).await; // Not needed because changes would not be visible
+			}
 		}
 	}
 
 	/// Sets the opacity of the blind layer
-	pub async fn set_blind_opacity(&self, opacity: u16) {
+	pub async fn set_blind_opacity(&self, zone_id: Option<Uuid>, opacity: u16) {
 		let mut ctx = self.1.write().await;
 
-		if ctx.frozen_context.is_some() {
-			ctx.blind_opacity = opacity;
-			self.2.notify_one();
+		if let Some(zone) = ctx.zone_mut(zone_id.as_ref()) {
+			if zone.frozen_context.is_some() {
+				zone.blind_opacity = opacity;
+				self.2.notify_one();
+			}
 		}
 	}
 
 	/// Gets the opacity of the blind layer
 	/// Returns the opacity of the blind layer bin. This will be None or null if blind mode is inactive
-	pub async fn get_blind_opacity(&self) -> Option<u16> {
+	pub async fn get_blind_opacity(&self, zone_id: Option<Uuid>) -> Option<u16> {
 		let ctx = self.1.read().await;
+		let zone = ctx.zone(zone_id.as_ref())?;
 
-		if ctx.frozen_context.is_some() {
-			return Some(ctx.blind_opacity);
+		if zone.frozen_context.is_some() {
+			return Some(zone.blind_opacity);
 		} else {
 			return None;
 		}
@@ -219,67 +459,82 @@ impl MixerInterface {
 
 	/// Reverts all changes made in blind mode. Changes are made instantly.
 	/// Use `set_blind_opacity` to fade.
-	pub async fn revert_blind(&self) {
+	pub async fn revert_blind(&self, zone_id: Option<Uuid>) {
 		// Get resources
 		let mut ctx = self.1.write().await;
 
-		if let Some(mixing_context) = ctx.frozen_context.take() {
-			ctx.default_context = mixing_context;
-			ctx.blind_opacity = 0;
-			self.2.notify_one();
+		if let Some(zone) = ctx.zone_mut(zone_id.as_ref()) {
+			if let Some(mixing_context) = zone.frozen_context.take() {
+				zone.default_context = mixing_context;
+				zone.blind_opacity = 0;
+				self.0
+					.emit("mixer.blind".into(), FilterCriteria::None, zone_id)
+					.await;
+				self.2.notify_one();
+			}
 		}
 	}
 
 	/// Commits all changes made in blind mode, deleting the previous look.
 	/// Changes are made instantly. Use `set_blind_opacity` to fade.
-	pub async fn commit_blind(&self) {
+	pub async fn commit_blind(&self, zone_id: Option<Uuid>) {
 		// Get resources
 		let mut ctx = self.1.write().await;
 
-		if ctx.frozen_context.is_some() {
-			ctx.frozen_context = None;
-			ctx.blind_opacity = 0;
-			self.2.notify_one();
+		if let Some(zone) = ctx.zone_mut(zone_id.as_ref()) {
+			if zone.frozen_context.is_some() {
+				zone.frozen_context = None;
+				zone.blind_opacity = 0;
+				self.0
+					.emit("mixer.blind".into(), FilterCriteria::None, zone_id)
+					.await;
+				self.2.notify_one();
+			}
 		}
 	}
 
-	/// Lists all static layers (submasters)
+	/// Lists all layers (submasters) in every zone
 	pub async fn list_submasters(&self) -> Vec<Uuid> {
 		let ctx = self.1.read().await;
-		return ctx.default_context.user_submaster_order.clone();
+		return ctx
+			.iter_zones()
+			.flat_map(|(_, zone)| zone.default_context.user_submaster_order.iter().cloned())
+			.collect();
 	}
 
-	/// Lists all static layers (submasters) with names
+	/// Lists all layers (submasters) in every zone with names
 	pub async fn list_submasters_with_names(&self) -> Vec<(Uuid, String)> {
 		let ctx = self.1.read().await;
 		return ctx
-			.default_context
-			.user_submaster_order
-			.iter()
-			.map(|id| {
-				(
-					id.clone(),
-					if let Some(submaster) = ctx.default_context.user_submasters.get(id) {
-						submaster.name.clone()
-					} else {
-						"ERROR: Broken submaster reference".into()
-					},
-				)
+			.iter_zones()
+			.flat_map(|(_, zone)| {
+				zone.default_context
+					.user_submaster_order
+					.iter()
+					.map(|id| {
+						(
+							*id,
+							if let Some(name) = zone.default_context.layer_name(id) {
+								name.clone()
+							} else {
+								"ERROR: Broken submaster reference".into()
+							},
+						)
+					})
 			})
 			.collect();
 	}
 
-	/// Creates a new submaster that can be used for blending
+	/// Creates a new submaster in the main zone that can be used for blending
 	///
 	/// Returns the ID of the new submaster
 	pub async fn create_layer(&self, name: String) -> Uuid {
 		let mut ctx = self.1.write().await;
 		let submaster_id = Uuid::new_v4();
 
-		ctx.default_context
-			.user_submasters
-			.insert(submaster_id.clone(), StaticLayer::new(name));
-		ctx.default_context.user_submaster_order.push(submaster_id.clone());
+		ctx.main_zone
+			.default_context
+			.insert_layer(submaster_id, StaticLayer::new(name));
 
 		self.0
 			.emit(
@@ -296,13 +551,20 @@ impl MixerInterface {
 	pub async fn rename_layer(&self, submaster_id: Uuid, new_name: String) -> () {
 		let mut ctx = self.1.write().await;
 
-		if let Some(ref mut blind_context) = ctx.frozen_context {
-			if let Some(submaster) = blind_context.user_submasters.get_mut(&submaster_id) {
-				submaster.name = new_name.clone();
+		if let Some(zone) = ctx.layer_zone_mut(&submaster_id) {
+			if let Some(ref mut blind_context) = zone.frozen_context {
+				if let Some(submaster) = blind_context.user_submasters.get_mut(&submaster_id) {
+					submaster.name = new_name.clone();
+				}
+				if let Some(submaster) = blind_context.expression_layers.get_mut(&submaster_id) {
+					submaster.name = new_name.clone();
+				}
+			}
+			if let Some(submaster) = zone.default_context.user_submasters.get_mut(&submaster_id) {
+				submaster.name = new_name;
+			} else if let Some(submaster) = zone.default_context.expression_layers.get_mut(&submaster_id) {
+				submaster.name = new_name;
 			}
-		}
-		if let Some(submaster) = ctx.default_context.user_submasters.get_mut(&submaster_id) {
-			submaster.name = new_name;
 		}
 
 		self.0
@@ -319,7 +581,11 @@ impl MixerInterface {
 		let mut ctx = self.1.write().await;
 
 		// Check if the specified submaster exists
-		if let Some(submaster) = ctx.default_context.user_submasters.get_mut(&submaster_id) {
+		let zone = match ctx.layer_zone_mut(&submaster_id) {
+			Some(zone) => zone,
+			None => return false,
+		};
+		if let Some(submaster) = zone.default_context.user_submasters.get_mut(&submaster_id) {
 			// Loop through fixtures in the delta
 			for (fixture_id, fixture_data) in submaster_delta.iter() {
 				// If fixture doesn't exist in the submaster, create it, then get the mutable data
@@ -348,7 +614,7 @@ impl MixerInterface {
 				submaster_delta,
 			).await;
 
-			if let Some(opacity) = ctx.default_context.layer_opacities.get(&submaster_id) {
+			if let Some(opacity) = zone.default_context.layer_opacities.get(&submaster_id) {
 				if *opacity > 0 {
 					self.2.notify_one();
 				}
@@ -363,12 +629,93 @@ impl MixerInterface {
 	pub async fn get_layer_contents(&self, submaster_id: Uuid) -> Option<StaticLayer> {
 		let ctx = self.1.read().await;
 		return ctx
+			.layer_zone(&submaster_id)?
 			.default_context
 			.user_submasters
 			.get(&submaster_id)
 			.cloned();
 	}
 
+	/// Creates a new expression layer in the main zone
+	///
+	/// Returns the ID of the new submaster
+	pub async fn create_expression_layer(&self, name: String) -> Uuid {
+		let mut ctx = self.1.write().await;
+		let submaster_id = Uuid::new_v4();
+
+		ctx.main_zone
+			.default_context
+			.insert_expression_layer(submaster_id, ExpressionLayer::new(name));
+
+		self.0
+			.emit(
+				"mixer.new_submaster".into(),
+				FilterCriteria::None,
+				submaster_id,
+			)
+			.await;
+
+		return submaster_id;
+	}
+
+	/// Replaces the fixtures and expressions in an expression layer
+	pub async fn set_expression_layer_contents(
+		&self,
+		submaster_id: Uuid,
+		fixtures: Vec<Uuid>,
+		expressions: FxHashMap<String, String>,
+	) -> Result<(), ExpressionLayerError> {
+		let mut ctx = self.1.write().await;
+
+		let layer = ctx
+			.layer_zone_mut(&submaster_id)
+			.and_then(|zone| zone.default_context.expression_layers.get_mut(&submaster_id))
+			.ok_or(ExpressionLayerError::LayerMissing)?;
+		layer.set_contents(fixtures, expressions)?;
+
+		self.0
+			.emit(
+				"mixer.expression_layer_updated".into(),
+				FilterCriteria::Uuid(submaster_id),
+				(),
+			)
+			.await;
+		self.2.notify_one();
+		return Ok(());
+	}
+
+	/// Retrieves the contents of an expression layer
+	pub async fn get_expression_layer(&self, submaster_id: Uuid) -> Option<ExpressionLayer> {
+		let ctx = self.1.read().await;
+		return ctx
+			.layer_zone(&submaster_id)?
+			.default_context
+			.expression_layers
+			.get(&submaster_id)
+			.cloned();
+	}
+
+	/// Checks if an expression is valid without assigning it to a layer
+	pub fn validate_expression(&self, source: &str) -> Result<(), ExpressionError> {
+		return Expression::parse(source).map(|_| ());
+	}
+
+	/// Sets the tempo used for the `beat` variable in expression layers
+	pub async fn set_tempo(&self, bpm: f64) {
+		let mut ctx = self.1.write().await;
+		ctx.tempo = bpm.max(0.0);
+
+		self.0
+			.emit("mixer.tempo".into(), FilterCriteria::None, ctx.tempo)
+			.await;
+	}
+
+	/// Gets the tempo used for the `beat` variable in expression layers
+	pub async fn get_tempo(&self) -> f64 {
+		let ctx = self.1.read().await;
+		return ctx.tempo;
+	}
+
 	/// Sets the opacity of a layer (Optionally within a specific bin)
 	///
 	/// If auto-insert is true, the layer will be automatically inserted if `opacity > 0` and it isn't in the stack.
@@ -377,23 +724,12 @@ impl MixerInterface {
 	/// Returns a boolean indicating if the operation was successful (this can be safely ignored)
 	pub async fn set_layer_opacity(&self, submaster_id: Uuid, opacity: u16, auto_insert: bool) -> bool {
 		let mut ctx = self.1.write().await;
-		if ctx
-			.default_context
-			.user_submasters
-			.contains_key(&submaster_id)
-		{
-			ctx.default_context
-				.layer_opacities
-				.insert(submaster_id, opacity);
-			if auto_insert {
-				if opacity > 0 && !ctx.default_context.layer_order.contains(&submaster_id) {
-					ctx.default_context.layer_order.push(submaster_id.clone())
-				} else if opacity == 0 && ctx.default_context.layer_order.contains(&submaster_id) {
-					ctx.default_context
-						.layer_order
-						.retain(|x| *x != submaster_id);
-				}
-			}
+		if ctx.layer_zone_mut(&submaster_id).is_some_and(|zone| {
+			zone.default_context
+				.set_layer_opacity(submaster_id, opacity, auto_insert)
+		}) {
+			// The layer's fader no longer matches its level, so it needs to be picked up again
+			ctx.release_faders(&submaster_id);
 			// TODO: Send this event only if the opacity *changes*
 			self.2.notify_one();
 			return true;
@@ -407,39 +743,802 @@ impl MixerInterface {
 	/// Returns the opacity of the layer, or `None` if it is not in the stack
 	pub async fn get_layer_opacity(&self, submaster_id: Uuid) -> Option<u16> {
 		let ctx = self.1.read().await;
-		return match ctx.default_context.layer_opacities.get(&submaster_id) {
-			Some(opacity) => Some(*opacity),
-			None => None,
-		};
+		return ctx
+			.layer_zone(&submaster_id)?
+			.default_context
+			.layer_opacities
+			.get(&submaster_id)
+			.cloned();
 	}
 
-	/// Deletes a layer from the registry
+	/// Links a layer's opacity to one or more master layers, which may be in any zone.
 	///
-	/// Returns a boolean indicating if the operation was successful (this can be safely ignored).
-	pub async fn delete_layer(&self, submaster_id: Uuid) -> bool {
+	/// Passing an empty list of masters removes the link.
+	pub async fn set_layer_link(&self, submaster_id: Uuid, masters: Vec<Uuid>, mode: LinkMode) -> Result<(), LinkError> {
 		let mut ctx = self.1.write().await;
 
-		// Remove submaster
-		let was_removed = ctx
-			.default_context
-			.user_submasters
-			.remove(&submaster_id)
-			.is_some();
+		if ctx.find_layer_zone(&submaster_id).is_none() {
+			return Err(LinkError::LayerMissing);
+		}
+		if masters.iter().any(|master_id| ctx.find_layer_zone(master_id).is_none()) {
+			return Err(LinkError::MasterMissing);
+		}
+		if links::creates_cycle(&submaster_id, &masters, &ctx.all_layer_links()) {
+			return Err(LinkError::Cycle);
+		}
 
-		// Remove references
-		ctx.default_context
-			.layer_order
-			.retain(|x| x != &submaster_id);
-		ctx.default_context.layer_opacities.remove(&submaster_id);
-		ctx.default_context.user_submaster_order.retain(|item| item != &submaster_id);
+		let mixing_context = &mut ctx
+			.layer_zone_mut(&submaster_id)
+			.ok_or(LinkError::LayerMissing)?
+			.default_context;
+		if masters.is_empty() {
+			mixing_context.layer_links.remove(&submaster_id);
+		} else {
+			mixing_context
+				.layer_links
+				.insert(submaster_id, LayerLink { masters, mode });
+		}
 
-		// Signal to update everything and return
+		self.0
+			.emit(
+				"mixer.layer_links_updated".into(),
+				FilterCriteria::None,
+				(),
+			)
+			.await;
 		self.2.notify_one();
-		return was_removed;
+		return Ok(());
 	}
 
-	pub fn request_blend(&self) {
+	/// Gets the link controlling a layer's opacity, if any
+	pub async fn get_layer_link(&self, submaster_id: Uuid) -> Option<LayerLink> {
+		let ctx = self.1.read().await;
+		return ctx
+			.layer_zone(&submaster_id)?
+			.default_context
+			.layer_links
+			.get(&submaster_id)
+			.cloned();
+	}
+
+	/// Gets the opacity of a layer after links to master layers have been applied
+	///
+	/// Returns the effective opacity of the layer, or `None` if it has no opacity set
+	pub async fn get_effective_opacity(&self, submaster_id: Uuid) -> Option<u16> {
+		let ctx = self.1.read().await;
+		return ctx.effective_opacities().get(&submaster_id).cloned();
+	}
+
+	/// Deletes a layer from the registry
+	///
+	/// Returns a boolean indicating if the operation was successful (this can be safely ignored).
+	pub async fn delete_layer(&self, submaster_id: Uuid) -> bool {
+		let mut ctx = self.1.write().await;
+		let was_removed = ctx.remove_layer(&submaster_id);
+
+		// Signal to update everything and return
+		self.2.notify_one();
+		return was_removed;
+	}
+
+	/// Duplicates a layer within its zone, inserting the copy into the live or blind context
+	///
+	/// Returns the ID of the new layer
+	pub async fn duplicate_layer(
+		&self,
+		submaster_id: Uuid,
+		name: Option<String>,
+		target: LayerContextTarget,
+	) -> Result<Uuid, LayerOperationError> {
+		let mut ctx = self.1.write().await;
+
+		let zone = ctx
+			.layer_zone_mut(&submaster_id)
+			.ok_or(LayerOperationError::LayerMissing)?;
+		let mut layer = zone
+			.default_context
+			.user_submasters
+			.get(&submaster_id)
+			.cloned()
+			.ok_or(LayerOperationError::LayerMissing)?;
+		layer.name = name.unwrap_or_else(|| format!("{} (copy)", layer.name));
+		let new_id = Uuid::new_v4();
+
+		match target {
+			LayerContextTarget::Live => {
+				if let Some(ref mut frozen_context) = zone.frozen_context {
+					frozen_context.insert_layer(new_id, layer.clone());
+				}
+				zone.default_context.insert_layer(new_id, layer);
+			}
+			LayerContextTarget::Blind => {
+				if zone.frozen_context.is_none() {
+					return Err(LayerOperationError::BlindInactive);
+				}
+				zone.default_context.insert_layer(new_id, layer);
+			}
+		}
+
+		self.0
+			.emit("mixer.new_submaster".into(), FilterCriteria::None, new_id)
+			.await;
+
+		return Ok(new_id);
+	}
+
+	/// Merges several layers into a new one, resolving conflicting values with the given rule.
+	///
+	/// Layers are merged in the order given, and the new layer is placed in the zone of the first one.
+	/// If `delete_sources` is true, the original layers are deleted.
+	///
+	/// Returns the ID of the new layer
+	pub async fn merge_layers(
+		&self,
+		submaster_ids: Vec<Uuid>,
+		name: String,
+		rule: MergeRule,
+		delete_sources: bool,
+	) -> Result<Uuid, LayerOperationError> {
+		let mut ctx = self.1.write().await;
+
+		if submaster_ids.is_empty() {
+			return Err(LayerOperationError::NoLayers);
+		}
+
+		let mut merged_layer = StaticLayer::new(name);
+		for submaster_id in submaster_ids.iter() {
+			let layer = ctx
+				.layer_zone(submaster_id)
+				.and_then(|zone| zone.default_context.user_submasters.get(submaster_id))
+				.ok_or(LayerOperationError::LayerMissing)?;
+			layer_operations::merge_values(&mut merged_layer.values, &layer.values, &rule);
+		}
+
+		let new_id = Uuid::new_v4();
+		ctx.layer_zone_mut(&submaster_ids[0])
+			.ok_or(LayerOperationError::LayerMissing)?
+			.default_context
+			.insert_layer(new_id, merged_layer);
+
+		if delete_sources {
+			for submaster_id in submaster_ids.iter() {
+				ctx.remove_layer(submaster_id);
+			}
+			self.2.notify_one();
+		}
+
+		self.0
+			.emit("mixer.new_submaster".into(), FilterCriteria::None, new_id)
+			.await;
+
+		return Ok(new_id);
+	}
+
+	/// Splits a layer into several new layers by fixture or attribute. New layers are placed in
+	/// the same zone as the original.
+	///
+	/// Values matched by a group are moved into a new layer for that group. Values that
+	/// are not matched by any group remain in the original layer.
+	///
+	/// Returns the IDs of the new layers, in the order the groups were given
+	pub async fn split_layer(
+		&self,
+		submaster_id: Uuid,
+		split: LayerSplit,
+	) -> Result<Vec<Uuid>, LayerOperationError> {
+		let mut ctx = self.1.write().await;
+
+		let zone = ctx
+			.layer_zone_mut(&submaster_id)
+			.ok_or(LayerOperationError::LayerMissing)?;
+		let source = zone
+			.default_context
+			.user_submasters
+			.get_mut(&submaster_id)
+			.ok_or(LayerOperationError::LayerMissing)?;
+
+		let mut new_layers = Vec::new();
+		let mut removed_values = SubmasterData::default();
+		match split {
+			LayerSplit::Fixtures(groups) => {
+				for group in groups {
+					let mut layer = StaticLayer::new(group.name);
+					layer.values = layer_operations::take_fixtures(&mut source.values, &group.fixtures);
+					layer_operations::merge_values(&mut removed_values, &layer.values, &MergeRule::Ltp);
+					new_layers.push((Uuid::new_v4(), layer));
+				}
+			}
+			LayerSplit::Attributes(groups) => {
+				for group in groups {
+					let mut layer = StaticLayer::new(group.name);
+					layer.values = layer_operations::take_attributes(&mut source.values, &group.attributes);
+					layer_operations::merge_values(&mut removed_values, &layer.values, &MergeRule::Ltp);
+					new_layers.push((Uuid::new_v4(), layer));
+				}
+			}
+		}
+
+		let mut new_ids = Vec::new();
+		for (new_id, layer) in new_layers {
+			zone.default_context.insert_layer(new_id, layer);
+			self.0
+				.emit("mixer.new_submaster".into(), FilterCriteria::None, new_id)
+				.await;
+			new_ids.push(new_id);
+		}
+
+		// Emit the change to the original layer for the frontend
+		self.0
+			.emit(
+				"mixer.submaster_updated".into(),
+				FilterCriteria::Uuid(submaster_id),
+				layer_operations::removal_delta(&removed_values),
+			)
+			.await;
+
+		if let Some(opacity) = zone.default_context.layer_opacities.get(&submaster_id) {
+			if *opacity > 0 {
+				self.2.notify_one();
+			}
+		}
+
+		return Ok(new_ids);
+	}
+
+	pub fn request_blend(&self) {
+		self.2.notify_one();
+	}
+
+	/// Replaces the fixture type of several fixtures, migrating their layer data to the new type's attributes.
+	///
+	/// Attributes are matched by ID unless overridden in `attribute_map`. Values are scaled to the new
+	/// attribute's range, and values for attributes without a match are dropped. The patcher migrates the
+	/// fixtures' transforms and dimmer curves in the same operation.
+	///
+	/// The mixer's lock is held while calling into the patcher, which takes its own lock. This follows the
+	/// lock order used throughout the backend (mixer, then patcher, then output drivers), so the patcher
+	/// must never call back into the mixer while holding its lock.
+	pub async fn replace_fixture_type(
+		&self,
+		patcher: &PatcherInterface,
+		instance_ids: Vec<Uuid>,
+		fixture_type: Uuid,
+		personality: String,
+		attribute_map: FxHashMap<String, String>,
+	) -> Result<(), ReplaceFixtureTypeError> {
+		// Hold the lock so the blender can't clean up layer data before it has been migrated.
+		// Lock order: mixer, then patcher.
+		let mut ctx = self.1.write().await;
+
+		let (_, old_blending_data) = patcher.get_base_layer().await;
+		let attribute_maps = patcher
+			.replace_fixture_type(&instance_ids, &fixture_type, personality, &attribute_map)
+			.await?;
+		let (_, new_blending_data) = patcher.get_base_layer().await;
+
+		for (fixture_id, attribute_map) in attribute_maps.iter() {
+			ctx.migrate_fixture(
+				fixture_id,
+				&AttributeMigration {
+					attribute_map,
+					old_blending_data: old_blending_data.get(fixture_id),
+					new_blending_data: new_blending_data.get(fixture_id),
+				},
+			);
+		}
+
+		self.2.notify_one();
+		return Ok(());
+	}
+
+	/// Sets an attribute to a named segment, such as a gobo or color, across a selection of fixtures.
+	///
+	/// The segment's value is resolved separately for each fixture type. Returns the fixtures that were set, in
+	/// selection order. Fixtures without a matching segment are left untouched.
+	pub async fn set_layer_segment(
+		&self,
+		patcher: &PatcherInterface,
+		submaster_id: Uuid,
+		fixture_ids: Vec<Uuid>,
+		attribute_id: String,
+		segment_name: String,
+	) -> Result<Vec<Uuid>, SegmentSelectionError> {
+		let values = patcher.resolve_segment_values(&fixture_ids, &attribute_id, &segment_name).await;
+		if values.is_empty() {
+			return Err(SegmentSelectionError::SegmentMissing(segment_name));
+		}
+
+		if !self.set_layer_attribute(submaster_id, &attribute_id, &values).await {
+			return Err(SegmentSelectionError::LayerMissing);
+		}
+
+		return Ok(fixture_ids.into_iter().filter(|fixture_id| values.contains_key(fixture_id)).collect());
+	}
+
+	/// Sets an attribute to a physical value, such as 90 degrees of pan, across a selection of fixtures.
+	///
+	/// The raw value is resolved separately for each fixture type. Returns the fixtures that were set, in selection
+	/// order. Fixtures whose attribute doesn't use the value's unit are left untouched.
+	pub async fn set_layer_physical(
+		&self,
+		patcher: &PatcherInterface,
+		submaster_id: Uuid,
+		fixture_ids: Vec<Uuid>,
+		attribute_id: String,
+		value: PhysicalValue,
+	) -> Result<Vec<Uuid>, PhysicalValueError> {
+		let values = patcher.resolve_physical_values(&fixture_ids, &attribute_id, &value).await;
+		if values.is_empty() {
+			return Err(PhysicalValueError::UnitUnsupported(value.unit));
+		}
+		if !self.set_layer_attribute(submaster_id, &attribute_id, &values).await {
+			return Err(PhysicalValueError::LayerMissing);
+		}
+
+		return Ok(fixture_ids.into_iter().filter(|fixture_id| values.contains_key(fixture_id)).collect());
+	}
+
+	/// Sets one attribute of several fixtures in a layer to static values
+	async fn set_layer_attribute(&self, submaster_id: Uuid, attribute_id: &str, values: &FxHashMap<Uuid, u16>) -> bool {
+		let delta: SubmasterData = values
+			.iter()
+			.map(|(fixture_id, value)| {
+				let mut fixture_data = FxHashMap::default();
+				fixture_data.insert(attribute_id.to_owned(), BlenderValue::Static(*value));
+				return (*fixture_id, fixture_data);
+			})
+			.collect();
+		return self.set_layer_contents(submaster_id, delta).await;
+	}
+
+	/// Creates a new, empty mixing zone
+	///
+	/// Returns the ID of the new zone
+	pub async fn create_zone(&self, name: String) -> Uuid {
+		let mut ctx = self.1.write().await;
+		let zone_id = Uuid::new_v4();
+
+		ctx.zones.insert(zone_id, MixingZone::new(name));
+		ctx.zone_order.push(zone_id);
+
+		self.0
+			.emit("mixer.zones_updated".into(), FilterCriteria::None, ())
+			.await;
+
+		return zone_id;
+	}
+
+	/// Renames a mixing zone. `None` refers to the main zone.
+	pub async fn rename_zone(&self, zone_id: Option<Uuid>, name: String) -> Result<(), ZoneError> {
+		let mut ctx = self.1.write().await;
+		ctx.zone_mut(zone_id.as_ref()).ok_or(ZoneError::ZoneMissing)?.name = name;
+
+		self.0
+			.emit("mixer.zones_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Deletes a mixing zone. Its layers are moved to the main zone, and its fixtures
+	/// are returned to the main zone.
+	///
+	/// Zones in blind mode can't be deleted, since their pending blind changes would be lost.
+	pub async fn delete_zone(&self, zone_id: Uuid) -> Result<(), ZoneError> {
+		let mut ctx = self.1.write().await;
+
+		// Blind changes can't be moved into the main zone, so they would be lost
+		match ctx.zones.get(&zone_id) {
+			Some(zone) if zone.frozen_context.is_some() => return Err(ZoneError::BlindActive),
+			Some(_) => {}
+			None => return Err(ZoneError::ZoneMissing),
+		}
+		let zone = ctx.zones.remove(&zone_id).unwrap();
+		ctx.zone_order.retain(|id| id != &zone_id);
+		ctx.fixture_zones.retain(|_, fixture_zone| fixture_zone != &zone_id);
+
+		// Move layers into the main zone, preserving their levels
+		let mixing_context = zone.default_context;
+		for submaster_id in mixing_context.user_submaster_order.iter() {
+			if let Some(layer) = mixing_context.user_submasters.get(submaster_id) {
+				ctx.main_zone.default_context.insert_layer(*submaster_id, layer.clone());
+			} else if let Some(layer) = mixing_context.expression_layers.get(submaster_id) {
+				ctx.main_zone.default_context.insert_expression_layer(*submaster_id, layer.clone());
+			}
+		}
+		for (submaster_id, opacity) in mixing_context.layer_opacities.iter() {
+			ctx.main_zone.default_context.layer_opacities.insert(*submaster_id, *opacity);
+		}
+		ctx.main_zone.default_context.layer_order.extend(mixing_context.layer_order.iter());
+		ctx.main_zone.default_context.layer_links.extend(mixing_context.layer_links);
+
+		self.0
+			.emit("mixer.zones_updated".into(), FilterCriteria::None, ())
+			.await;
+		self.2.notify_one();
+		return Ok(());
+	}
+
+	/// Lists all mixing zones, starting with the main zone
+	pub async fn list_zones(&self) -> Vec<ZoneDescription> {
+		let ctx = self.1.read().await;
+		return ctx
+			.iter_zones()
+			.map(|(zone_id, zone)| ZoneDescription {
+				id: zone_id,
+				name: zone.name.clone(),
+				grand_master: zone.grand_master,
+				blind: zone.frozen_context.is_some(),
+			})
+			.collect();
+	}
+
+	/// Assigns fixtures to a mixing zone. `None` assigns them to the main zone.
+	///
+	/// A fixture's cells are always blended in the fixture's zone, so assigning a cell on its own has no effect.
+	pub async fn set_fixture_zone(&self, fixture_ids: Vec<Uuid>, zone_id: Option<Uuid>) -> Result<(), ZoneError> {
+		let mut ctx = self.1.write().await;
+
+		match zone_id {
+			Some(zone_id) => {
+				if !ctx.zones.contains_key(&zone_id) {
+					return Err(ZoneError::ZoneMissing);
+				}
+				for fixture_id in fixture_ids {
+					ctx.fixture_zones.insert(fixture_id, zone_id);
+				}
+			}
+			None => {
+				for fixture_id in fixture_ids.iter() {
+					ctx.fixture_zones.remove(fixture_id);
+				}
+			}
+		}
+
+		self.0
+			.emit("mixer.zones_updated".into(), FilterCriteria::None, ())
+			.await;
 		self.2.notify_one();
+		return Ok(());
+	}
+
+	/// Gets the zone assignments of every fixture that isn't in the main zone
+	pub async fn get_fixture_zones(&self) -> HashMap<Uuid, Uuid> {
+		let ctx = self.1.read().await;
+		return ctx.fixture_zones.clone();
+	}
+
+	/// Moves a layer to another zone, keeping its level. `None` refers to the main zone.
+	///
+	/// Layers cannot be moved in or out of a zone that is in blind mode.
+	pub async fn move_layer_to_zone(&self, submaster_id: Uuid, zone_id: Option<Uuid>) -> Result<(), ZoneError> {
+		let mut ctx = self.1.write().await;
+
+		let source_zone_id = ctx.find_layer_zone(&submaster_id).ok_or(ZoneError::LayerMissing)?;
+		if source_zone_id == zone_id {
+			return Ok(());
+		}
+		let target_zone = ctx.zone(zone_id.as_ref()).ok_or(ZoneError::ZoneMissing)?;
+		let source_zone = ctx.zone(source_zone_id.as_ref()).ok_or(ZoneError::ZoneMissing)?;
+		if target_zone.frozen_context.is_some() || source_zone.frozen_context.is_some() {
+			return Err(ZoneError::BlindActive);
+		}
+
+		let source_context = &mut ctx
+			.zone_mut(source_zone_id.as_ref())
+			.ok_or(ZoneError::ZoneMissing)?
+			.default_context;
+		let static_layer = source_context.remove_layer(&submaster_id);
+		let expression_layer = source_context.remove_expression_layer(&submaster_id);
+		let link = source_context.layer_links.remove(&submaster_id);
+		let target_context = &mut ctx
+			.zone_mut(zone_id.as_ref())
+			.ok_or(ZoneError::ZoneMissing)?
+			.default_context;
+		if let Some(link) = link {
+			target_context.layer_links.insert(submaster_id, link);
+		}
+		let opacity = if let Some((layer, opacity)) = static_layer {
+			target_context.insert_layer(submaster_id, layer);
+			opacity
+		} else if let Some((layer, opacity)) = expression_layer {
+			target_context.insert_expression_layer(submaster_id, layer);
+			opacity
+		} else {
+			return Err(ZoneError::LayerMissing);
+		};
+		if let Some(opacity) = opacity {
+			target_context.set_layer_opacity(submaster_id, opacity, true);
+		}
+
+		self.0
+			.emit("mixer.zones_updated".into(), FilterCriteria::None, ())
+			.await;
+		self.2.notify_one();
+		return Ok(());
+	}
+
+	/// Sets the grand master of a zone. `None` refers to the main zone.
+	pub async fn set_grand_master(&self, zone_id: Option<Uuid>, level: u16) -> Result<(), ZoneError> {
+		let mut ctx = self.1.write().await;
+		ctx.zone_mut(zone_id.as_ref()).ok_or(ZoneError::ZoneMissing)?.grand_master = level;
+
+		self.0
+			.emit(
+				"mixer.grand_master".into(),
+				FilterCriteria::None,
+				(zone_id, level),
+			)
+			.await;
+		self.2.notify_one();
+		return Ok(());
+	}
+
+	/// Gets the grand master of a zone. `None` refers to the main zone.
+	///
+	/// Returns the grand master level, or `None` if the zone does not exist
+	pub async fn get_grand_master(&self, zone_id: Option<Uuid>) -> Option<u16> {
+		let ctx = self.1.read().await;
+		return ctx.zone(zone_id.as_ref()).map(|zone| zone.grand_master);
+	}
+
+	/// Creates a new fader bank with a single, empty page
+	///
+	/// Returns the ID of the new bank
+	pub async fn create_bank(&self, name: String, slot_count: u16) -> Uuid {
+		let mut ctx = self.1.write().await;
+		let bank_id = Uuid::new_v4();
+
+		ctx.banks.insert(bank_id, FaderBank::new(bank_id, name, slot_count));
+		ctx.bank_order.push(bank_id);
+
+		self.0
+			.emit("mixer.banks_updated".into(), FilterCriteria::None, ())
+			.await;
+
+		return bank_id;
+	}
+
+	/// Renames a fader bank
+	pub async fn rename_bank(&self, bank_id: Uuid, name: String) -> Result<(), BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		bank.name = name;
+
+		self.0
+			.emit("mixer.banks_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Deletes a fader bank. Layers assigned to the bank are not affected.
+	///
+	/// Returns a boolean indicating if the bank existed
+	pub async fn delete_bank(&self, bank_id: Uuid) -> bool {
+		let mut ctx = self.1.write().await;
+
+		let was_removed = ctx.banks.remove(&bank_id).is_some();
+		ctx.bank_order.retain(|id| id != &bank_id);
+		ctx.fader_pickup.remove(&bank_id);
+
+		self.0
+			.emit("mixer.banks_updated".into(), FilterCriteria::None, ())
+			.await;
+		return was_removed;
+	}
+
+	/// Lists all fader banks with names
+	pub async fn list_banks(&self) -> Vec<(Uuid, String)> {
+		let ctx = self.1.read().await;
+		return ctx
+			.bank_order
+			.iter()
+			.filter_map(|id| ctx.banks.get(id).map(|bank| (*id, bank.name.clone())))
+			.collect();
+	}
+
+	/// Retrieves the full configuration of a fader bank
+	pub async fn get_bank(&self, bank_id: Uuid) -> Option<FaderBank> {
+		let ctx = self.1.read().await;
+		return ctx.banks.get(&bank_id).cloned();
+	}
+
+	/// Adds a new, empty page to the end of a fader bank
+	///
+	/// Returns the index of the new page
+	pub async fn add_bank_page(&self, bank_id: Uuid, name: String) -> Result<usize, BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		bank.pages.push(FaderPage::new(name, bank.slot_count));
+		let page_index = bank.pages.len() - 1;
+
+		self.emit_bank_update(&ctx, bank_id).await;
+		return Ok(page_index);
+	}
+
+	/// Deletes a page from a fader bank. The last remaining page cannot be deleted.
+	pub async fn delete_bank_page(&self, bank_id: Uuid, page: usize) -> Result<(), BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		if page >= bank.pages.len() {
+			return Err(BankError::PageMissing);
+		}
+		if bank.pages.len() == 1 {
+			return Err(BankError::LastPage);
+		}
+		bank.pages.remove(page);
+		let page_changed = bank.current_page >= page;
+		if page_changed && bank.current_page > 0 {
+			bank.current_page -= 1;
+		}
+
+		if page_changed {
+			Self::release_bank_faders(&mut ctx, &bank_id);
+		}
+		self.emit_bank_update(&ctx, bank_id).await;
+		return Ok(());
+	}
+
+	/// Assigns a layer to a fader slot on a page of a bank. Passing `None` clears the slot.
+	pub async fn assign_fader(
+		&self,
+		bank_id: Uuid,
+		page: usize,
+		slot: u16,
+		layer_id: Option<Uuid>,
+	) -> Result<(), BankError> {
+		let mut ctx = self.1.write().await;
+		if let Some(ref layer_id) = layer_id {
+			if ctx.find_layer_zone(layer_id).is_none() {
+				return Err(BankError::LayerMissing);
+			}
+		}
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		let is_current_page = bank.current_page == page;
+		let assignment = bank
+			.pages
+			.get_mut(page)
+			.ok_or(BankError::PageMissing)?
+			.assignments
+			.get_mut(slot as usize)
+			.ok_or(BankError::SlotMissing)?;
+		*assignment = layer_id;
+
+		if is_current_page {
+			ctx.get_fader_pickup(&bank_id, slot).release();
+		}
+		self.emit_bank_update(&ctx, bank_id).await;
+		return Ok(());
+	}
+
+	/// Switches the page that is loaded onto a bank's faders.
+	///
+	/// Faders need to be picked up again after a page switch if the bank is in pickup mode.
+	pub async fn set_bank_page(&self, bank_id: Uuid, page: usize) -> Result<(), BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		if page >= bank.pages.len() {
+			return Err(BankError::PageMissing);
+		}
+		bank.current_page = page;
+
+		Self::release_bank_faders(&mut ctx, &bank_id);
+		self.emit_bank_update(&ctx, bank_id).await;
+		return Ok(());
+	}
+
+	/// Moves a bank forward or backward by the given number of pages, wrapping around at either end.
+	///
+	/// Returns the index of the newly-selected page
+	pub async fn step_bank_page(&self, bank_id: Uuid, delta: i32) -> Result<usize, BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		let page_count = bank.pages.len() as i64;
+		let page = (bank.current_page as i64 + delta as i64).rem_euclid(page_count) as usize;
+		bank.current_page = page;
+
+		Self::release_bank_faders(&mut ctx, &bank_id);
+		self.emit_bank_update(&ctx, bank_id).await;
+		return Ok(page);
+	}
+
+	/// Sets the pickup mode for a bank's faders
+	pub async fn set_bank_pickup_mode(&self, bank_id: Uuid, pickup_mode: PickupMode) -> Result<(), BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get_mut(&bank_id).ok_or(BankError::BankMissing)?;
+		bank.pickup_mode = pickup_mode;
+
+		Self::release_bank_faders(&mut ctx, &bank_id);
+		self.emit_bank_update(&ctx, bank_id).await;
+		return Ok(());
+	}
+
+	/// Handles movement of a physical fader, setting the opacity of the layer assigned to
+	/// its slot on the current page if the fader is in control of the layer.
+	///
+	/// Returns the state of the fader after the move, which can be used for feedback
+	pub async fn move_fader(&self, bank_id: Uuid, slot: u16, position: u16) -> Result<FaderSlotState, BankError> {
+		let mut ctx = self.1.write().await;
+		let bank = ctx.banks.get(&bank_id).ok_or(BankError::BankMissing)?;
+		if slot >= bank.slot_count {
+			return Err(BankError::SlotMissing);
+		}
+		let layer_id = bank.current_assignment(slot);
+		let pickup_mode = bank.pickup_mode.clone();
+		let layer_level = layer_id
+			.and_then(|layer_id| ctx.layer_zone(&layer_id)?.default_context.layer_opacities.get(&layer_id).cloned())
+			.unwrap_or(0);
+
+		let pickup_state = ctx.get_fader_pickup(&bank_id, slot);
+		let engaged = pickup_state.update(&pickup_mode, position, layer_level);
+
+		if let (Some(layer_id), true) = (layer_id, engaged) {
+			if position != layer_level
+				&& ctx.layer_zone_mut(&layer_id).is_some_and(|zone| {
+					zone.default_context.set_layer_opacity(layer_id, position, true)
+				})
+			{
+				self.2.notify_one();
+			}
+		}
+
+		// Report the level the layer is actually blended at, after links to master layers
+		let opacity = layer_id
+			.and_then(|layer_id| ctx.effective_opacities().get(&layer_id).cloned())
+			.unwrap_or(0);
+		return Ok(FaderSlotState {
+			layer_id,
+			opacity,
+			engaged,
+			physical_position: Some(position),
+		});
+	}
+
+	/// Gets the state of every fader slot on a bank's current page, for control surface feedback
+	pub async fn get_bank_state(&self, bank_id: Uuid) -> Result<Vec<FaderSlotState>, BankError> {
+		let ctx = self.1.read().await;
+		return Self::get_bank_state_locked(&ctx, &bank_id);
+	}
+
+	fn get_bank_state_locked(ctx: &MixerContext, bank_id: &Uuid) -> Result<Vec<FaderSlotState>, BankError> {
+		let bank = ctx.banks.get(bank_id).ok_or(BankError::BankMissing)?;
+		let pickup_states = ctx.fader_pickup.get(bank_id);
+		let opacities = ctx.effective_opacities();
+		return Ok((0..bank.slot_count)
+			.map(|slot| {
+				let layer_id = bank.current_assignment(slot);
+				let pickup_state = pickup_states.and_then(|states| states.get(slot as usize));
+				FaderSlotState {
+					layer_id,
+					opacity: layer_id
+						.and_then(|layer_id| opacities.get(&layer_id).cloned())
+						.unwrap_or(0),
+					engaged: pickup_state.is_some_and(|state| state.engaged),
+					physical_position: pickup_state.and_then(|state| state.last_position),
+				}
+			})
+			.collect());
+	}
+
+	/// Releases all faders in a bank so they need to be picked up again
+	fn release_bank_faders(ctx: &mut MixerContext, bank_id: &Uuid) {
+		if let Some(pickup_states) = ctx.fader_pickup.get_mut(bank_id) {
+			for pickup_state in pickup_states.iter_mut() {
+				pickup_state.release();
+			}
+		}
+	}
+
+	/// Emits the new state of a bank after a configuration change
+	async fn emit_bank_update(&self, ctx: &MixerContext, bank_id: Uuid) {
+		if let Ok(bank_state) = Self::get_bank_state_locked(ctx, &bank_id) {
+			self.0
+				.emit(
+					"mixer.bank_updated".into(),
+					FilterCriteria::Uuid(bank_id),
+					bank_state,
+				)
+				.await;
+		}
 	}
 }
 
@@ -456,21 +1555,13 @@ struct SubmasterTypeSpecifier(MixerInterface);
 #[async_trait]
 impl TypeSpecifier for SubmasterTypeSpecifier {
 	async fn get_options(&self) -> Vec<DropdownOptionNative> {
-		let submasters = self.0.list_submasters().await;
-		let mixer_internals = self.0.1.read().await;
-
-		return submasters
+		return self
+			.0
+			.list_submasters_with_names()
+			.await
 			.into_iter()
-			.map(|submaster_id| DropdownOptionNative {
-				name: if let Some(submaster) = mixer_internals
-					.default_context
-					.user_submasters
-					.get(&submaster_id)
-				{
-					submaster.name.clone()
-				} else {
-					"ERROR: Broken submaster reference".into()
-				},
+			.map(|(submaster_id, name)| DropdownOptionNative {
+				name,
 				description: None,
 				value: Box::new(submaster_id),
 			})
@@ -478,6 +1569,63 @@ impl TypeSpecifier for SubmasterTypeSpecifier {
 	}
 }
 
+struct FaderBankTypeSpecifier(MixerInterface);
+
+#[async_trait]
+impl TypeSpecifier for FaderBankTypeSpecifier {
+	async fn get_options(&self) -> Vec<DropdownOptionNative> {
+		return self
+			.0
+			.list_banks()
+			.await
+			.into_iter()
+			.map(|(bank_id, name)| DropdownOptionNative {
+				name,
+				description: None,
+				value: Box::new(bank_id),
+			})
+			.collect();
+	}
+}
+
+struct ZoneTypeSpecifier(MixerInterface);
+
+#[async_trait]
+impl TypeSpecifier for ZoneTypeSpecifier {
+	async fn get_options(&self) -> Vec<DropdownOptionNative> {
+		return self
+			.0
+			.list_zones()
+			.await
+			.into_iter()
+			.map(|zone| DropdownOptionNative {
+				name: zone.name,
+				description: None,
+				value: Box::new(zone.id),
+			})
+			.collect();
+	}
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while setting a layer's attribute by segment name
+pub enum SegmentSelectionError {
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("None of the selected fixtures have a segment named {0:?}")]
+	SegmentMissing(String),
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while setting a layer's attribute to a physical value
+pub enum PhysicalValueError {
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("None of the selected fixtures can be set in {0:?} for this attribute")]
+	UnitUnsupported(PhysicalUnit),
+}
 
 #[portable]
 #[derive(Debug, Error)]
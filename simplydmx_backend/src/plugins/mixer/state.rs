@@ -1,8 +1,15 @@
+use super::{
+	banks::{FaderBank, FaderPickupState},
+	links::{self, LayerLink},
+};
 use crate::mixer_utils::{
 	layer::MixerLayer,
+	migration::AttributeMigration,
 	state::{FullMixerBlendingData, FullMixerOutput},
 	static_layer::StaticLayer,
+	expression_layer::ExpressionLayer,
 };
+use rustc_hash::FxHashMap;
 use simplydmx_plugin_framework::*;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -12,24 +19,173 @@ use uuid::Uuid;
 #[portable]
 /// Data used by the mixer to blend submasters and produce a final result
 pub struct MixerContext {
-	/// The default context, where changes are made
-	pub default_context: MixingContext,
+	/// The main mixing zone. Fixtures that aren't assigned to another zone are mixed here.
+	#[serde(flatten)]
+	pub main_zone: MixingZone,
 
-	/// A frozen copy of the default context used in blind mode
-	pub frozen_context: Option<MixingContext>,
+	/// Additional mixing zones, each driving its own set of fixtures
+	#[serde(default)]
+	pub zones: HashMap<Uuid, MixingZone>,
 
-	/// The opacity of `default_context` when `frozen_context.is_some()`
-	pub blind_opacity: u16,
+	/// The order in which additional zones should be displayed
+	#[serde(default)]
+	pub zone_order: Vec<Uuid>,
+
+	/// The zone each fixture is assigned to. Fixtures not listed here belong to the main zone.
+	#[serde(default)]
+	pub fixture_zones: HashMap<Uuid, Uuid>,
+
+	/// The tempo, in beats per minute, used for the `beat` variable in expression layers
+	#[serde(default = "default_tempo")]
+	pub tempo: f64,
+
+	/// Playback banks used to map fader slots to layers
+	#[serde(default)]
+	pub banks: HashMap<Uuid, FaderBank>,
+
+	/// The order in which banks should be displayed
+	#[serde(default)]
+	pub bank_order: Vec<Uuid>,
+
+	/// Pickup state of the physical faders in each bank. This is runtime-only data.
+	#[serde(skip)]
+	pub fader_pickup: HashMap<Uuid, Vec<FaderPickupState>>,
 }
 
 impl MixerContext {
 	pub async fn cleanup(&mut self, patcher_data: &(FullMixerOutput, FullMixerBlendingData)) {
-		for submaster in self.default_context.user_submasters.iter_mut() {
-			submaster.1.cleanup(patcher_data).await;
+		for zone in std::iter::once(&mut self.main_zone).chain(self.zones.values_mut()) {
+			zone.default_context.cleanup(patcher_data).await;
+			if let Some(ref mut mixing_context) = zone.frozen_context {
+				mixing_context.cleanup(patcher_data).await;
+			}
 		}
-		if let Some(ref mut mixing_context) = self.frozen_context {
-			for submaster in mixing_context.user_submasters.iter_mut() {
-				submaster.1.cleanup(patcher_data).await;
+		self.fixture_zones.retain(|fixture_id, _| patcher_data.0.contains_key(fixture_id));
+	}
+
+	/// Moves a fixture's layer data to new attributes in every zone after its fixture type is replaced
+	pub fn migrate_fixture(&mut self, fixture_id: &Uuid, migration: &AttributeMigration) {
+		for zone in std::iter::once(&mut self.main_zone).chain(self.zones.values_mut()) {
+			zone.default_context.migrate_fixture(fixture_id, migration);
+			if let Some(ref mut mixing_context) = zone.frozen_context {
+				mixing_context.migrate_fixture(fixture_id, migration);
+			}
+		}
+	}
+
+	/// Gets a zone by ID. `None` refers to the main zone.
+	pub fn zone(&self, zone_id: Option<&Uuid>) -> Option<&MixingZone> {
+		return match zone_id {
+			Some(zone_id) => self.zones.get(zone_id),
+			None => Some(&self.main_zone),
+		};
+	}
+
+	/// Gets a mutable reference to a zone by ID. `None` refers to the main zone.
+	pub fn zone_mut(&mut self, zone_id: Option<&Uuid>) -> Option<&mut MixingZone> {
+		return match zone_id {
+			Some(zone_id) => self.zones.get_mut(zone_id),
+			None => Some(&mut self.main_zone),
+		};
+	}
+
+	/// Iterates over every zone in display order, starting with the main zone.
+	pub fn iter_zones(&self) -> impl Iterator<Item = (Option<Uuid>, &MixingZone)> {
+		return std::iter::once((None, &self.main_zone)).chain(
+			self.zone_order
+				.iter()
+				.filter_map(|zone_id| self.zones.get(zone_id).map(|zone| (Some(*zone_id), zone))),
+		);
+	}
+
+	/// Gets the ID of the zone containing a layer. The outer `Option` indicates if the layer
+	/// was found, and the inner one is `None` for the main zone.
+	pub fn find_layer_zone(&self, submaster_id: &Uuid) -> Option<Option<Uuid>> {
+		return self
+			.iter_zones()
+			.find(|(_, zone)| zone.default_context.contains_layer(submaster_id))
+			.map(|(zone_id, _)| zone_id);
+	}
+
+	/// Gets the zone containing a layer
+	pub fn layer_zone(&self, submaster_id: &Uuid) -> Option<&MixingZone> {
+		let zone_id = self.find_layer_zone(submaster_id)?;
+		return self.zone(zone_id.as_ref());
+	}
+
+	/// Gets a mutable reference to the zone containing a layer
+	pub fn layer_zone_mut(&mut self, submaster_id: &Uuid) -> Option<&mut MixingZone> {
+		let zone_id = self.find_layer_zone(submaster_id)?;
+		return self.zone_mut(zone_id.as_ref());
+	}
+
+	/// Removes a layer from the default context of its zone, along with any references to it
+	///
+	/// Returns a boolean indicating if the layer existed
+	pub fn remove_layer(&mut self, submaster_id: &Uuid) -> bool {
+		let was_removed = match self.layer_zone_mut(submaster_id) {
+			Some(zone) => {
+				zone.default_context.remove_layer(submaster_id).is_some()
+					|| zone.default_context.remove_expression_layer(submaster_id).is_some()
+			}
+			None => false,
+		};
+		for bank in self.banks.values_mut() {
+			bank.unassign_layer(submaster_id);
+		}
+
+		// Remove links to and from the layer
+		for zone in std::iter::once(&mut self.main_zone).chain(self.zones.values_mut()) {
+			zone.default_context.unlink_layer(submaster_id);
+			if let Some(ref mut mixing_context) = zone.frozen_context {
+				mixing_context.unlink_layer(submaster_id);
+			}
+		}
+
+		return was_removed;
+	}
+
+	/// Gets the links of every layer in every zone
+	pub fn all_layer_links(&self) -> HashMap<Uuid, &LayerLink> {
+		return self
+			.iter_zones()
+			.flat_map(|(_, zone)| zone.default_context.layer_links.iter())
+			.map(|(layer_id, link)| (*layer_id, link))
+			.collect();
+	}
+
+	/// Calculates the effective opacity of every layer in every zone, taking links into account
+	pub fn effective_opacities(&self) -> FxHashMap<Uuid, u16> {
+		let opacities: FxHashMap<Uuid, u16> = self
+			.iter_zones()
+			.flat_map(|(_, zone)| zone.default_context.layer_opacities.iter())
+			.map(|(layer_id, opacity)| (*layer_id, *opacity))
+			.collect();
+		return links::effective_opacities(&opacities, &self.all_layer_links());
+	}
+
+	/// Gets the pickup state of a fader, creating it if necessary
+	pub fn get_fader_pickup(&mut self, bank_id: &Uuid, slot: u16) -> &mut FaderPickupState {
+		let slot_count = self.banks.get(bank_id).map_or(0, |bank| bank.slot_count as usize);
+		let pickup_states = self.fader_pickup.entry(*bank_id).or_default();
+		if pickup_states.len() < slot_count.max(slot as usize + 1) {
+			pickup_states.resize_with(slot_count.max(slot as usize + 1), Default::default);
+		}
+		return &mut pickup_states[slot as usize];
+	}
+
+	/// Releases any physical faders currently controlling the given layer.
+	///
+	/// This is used when a layer's opacity is changed by something other than its fader,
+	/// so the fader has to be picked up again before it can take control.
+	pub fn release_faders(&mut self, layer_id: &Uuid) {
+		for (bank_id, bank) in self.banks.iter() {
+			if let Some(pickup_states) = self.fader_pickup.get_mut(bank_id) {
+				for (slot, pickup_state) in pickup_states.iter_mut().enumerate() {
+					if bank.current_assignment(slot as u16).as_ref() == Some(layer_id) {
+						pickup_state.release();
+					}
+				}
 			}
 		}
 	}
@@ -38,9 +194,14 @@ impl MixerContext {
 impl Default for MixerContext {
 	fn default() -> Self {
 		return MixerContext {
-			default_context: MixingContext::default(),
-			frozen_context: None,
-			blind_opacity: 0,
+			main_zone: MixingZone::new(main_zone_name()),
+			zones: HashMap::new(),
+			zone_order: Vec::new(),
+			fixture_zones: HashMap::new(),
+			tempo: default_tempo(),
+			banks: HashMap::new(),
+			bank_order: Vec::new(),
+			fader_pickup: HashMap::new(),
 		};
 	}
 }
@@ -50,11 +211,69 @@ impl MixerContext {
 		MixerContext::default()
 	}
 
-	pub fn from_file(mixer_context: MixerContext) -> MixerContext {
+	pub fn from_file(mut mixer_context: MixerContext) -> MixerContext {
+		// Expressions are saved as source, so they need to be parsed again
+		for zone in std::iter::once(&mut mixer_context.main_zone).chain(mixer_context.zones.values_mut()) {
+			for layer in zone.default_context.expression_layers.values_mut() {
+				layer.compile();
+			}
+			if let Some(ref mut mixing_context) = zone.frozen_context {
+				for layer in mixing_context.expression_layers.values_mut() {
+					layer.compile();
+				}
+			}
+		}
 		return mixer_context;
 	}
 }
 
+#[portable]
+/// A mixing zone, with its own layer stack, blind state, and grand master.
+///
+/// Zones allow separate areas of a venue (such as a stage and lobby) to be controlled independently.
+pub struct MixingZone {
+	/// A human-readable name for the zone
+	#[serde(default = "main_zone_name")]
+	pub name: String,
+
+	/// The default context, where changes are made
+	pub default_context: MixingContext,
+
+	/// A frozen copy of the default context used in blind mode
+	pub frozen_context: Option<MixingContext>,
+
+	/// The opacity of `default_context` when `frozen_context.is_some()`
+	pub blind_opacity: u16,
+
+	/// Scales the output of every HTP (intensity) attribute in the zone
+	#[serde(default = "full_opacity")]
+	pub grand_master: u16,
+}
+
+impl MixingZone {
+	pub fn new(name: String) -> Self {
+		return MixingZone {
+			name,
+			default_context: MixingContext::default(),
+			frozen_context: None,
+			blind_opacity: 0,
+			grand_master: u16::MAX,
+		};
+	}
+}
+
+fn main_zone_name() -> String {
+	return String::from("Main");
+}
+
+fn default_tempo() -> f64 {
+	return 120.0;
+}
+
+fn full_opacity() -> u16 {
+	return u16::MAX;
+}
+
 #[portable]
 /// Describes a single mixer instance, with its own internal state for driving layers and effects
 ///
@@ -64,7 +283,114 @@ pub struct MixingContext {
 	pub layer_opacities: HashMap<Uuid, u16>,
 	pub user_submaster_order: Vec<Uuid>,
 	pub user_submasters: HashMap<Uuid, StaticLayer>,
+
+	/// Layers whose values are calculated from expressions. These share `user_submaster_order`,
+	/// `layer_order`, and `layer_opacities` with the static layers.
+	#[serde(default)]
+	pub expression_layers: HashMap<Uuid, ExpressionLayer>,
+
+	/// Links that drive the opacity of layers in this context from master layers, which may be in
+	/// any zone. Keyed by the ID of the controlled layer.
+	#[serde(default)]
+	pub layer_links: HashMap<Uuid, LayerLink>,
 }
+impl MixingContext {
+	async fn cleanup(&mut self, patcher_data: &(FullMixerOutput, FullMixerBlendingData)) {
+		for submaster in self.user_submasters.values_mut() {
+			submaster.cleanup(patcher_data).await;
+		}
+		for submaster in self.expression_layers.values_mut() {
+			submaster.cleanup(patcher_data).await;
+		}
+	}
+
+	/// Moves a fixture's data in every layer to new attributes after its fixture type is replaced
+	fn migrate_fixture(&mut self, fixture_id: &Uuid, migration: &AttributeMigration) {
+		for submaster in self.user_submasters.values_mut() {
+			submaster.migrate_fixture(fixture_id, migration);
+		}
+		for submaster in self.expression_layers.values_mut() {
+			submaster.migrate_fixture(fixture_id, migration);
+		}
+	}
+
+	/// Checks if a static or expression layer exists in this context
+	pub fn contains_layer(&self, submaster_id: &Uuid) -> bool {
+		return self.user_submasters.contains_key(submaster_id) || self.expression_layers.contains_key(submaster_id);
+	}
+
+	/// Gets the name of a static or expression layer
+	pub fn layer_name(&self, submaster_id: &Uuid) -> Option<&String> {
+		return self
+			.user_submasters
+			.get(submaster_id)
+			.map(|layer| &layer.name)
+			.or_else(|| self.expression_layers.get(submaster_id).map(|layer| &layer.name));
+	}
+
+	/// Adds an expression layer to the end of the user's submaster list
+	pub fn insert_expression_layer(&mut self, submaster_id: Uuid, layer: ExpressionLayer) {
+		self.expression_layers.insert(submaster_id, layer);
+		self.user_submaster_order.push(submaster_id);
+	}
+
+	/// Removes an expression layer from this context along with any references to it, returning
+	/// the layer and its opacity
+	pub fn remove_expression_layer(&mut self, submaster_id: &Uuid) -> Option<(ExpressionLayer, Option<u16>)> {
+		let layer = self.expression_layers.remove(submaster_id)?;
+		return Some((layer, self.remove_references(submaster_id)));
+	}
+
+	/// Adds a layer to the end of the user's submaster list
+	pub fn insert_layer(&mut self, submaster_id: Uuid, layer: StaticLayer) {
+		self.user_submasters.insert(submaster_id, layer);
+		self.user_submaster_order.push(submaster_id);
+	}
+
+	/// Removes a layer from this context along with any references to it, returning the layer
+	/// and its opacity
+	pub fn remove_layer(&mut self, submaster_id: &Uuid) -> Option<(StaticLayer, Option<u16>)> {
+		let layer = self.user_submasters.remove(submaster_id)?;
+		return Some((layer, self.remove_references(submaster_id)));
+	}
+
+	/// Removes the layer's own link, and removes it from the masters of other links
+	fn unlink_layer(&mut self, submaster_id: &Uuid) {
+		self.layer_links.remove(submaster_id);
+		for link in self.layer_links.values_mut() {
+			link.masters.retain(|master_id| master_id != submaster_id);
+		}
+		self.layer_links.retain(|_, link| !link.masters.is_empty());
+	}
+
+	/// Removes a layer from the stack and submaster list, returning its opacity
+	fn remove_references(&mut self, submaster_id: &Uuid) -> Option<u16> {
+		let opacity = self.layer_opacities.remove(submaster_id);
+		self.layer_order.retain(|x| x != submaster_id);
+		self.user_submaster_order.retain(|item| item != submaster_id);
+		return opacity;
+	}
+
+	/// Sets the opacity of a layer within this context.
+	///
+	/// See `MixerInterface::set_layer_opacity` for details on `auto_insert`.
+	pub fn set_layer_opacity(&mut self, submaster_id: Uuid, opacity: u16, auto_insert: bool) -> bool {
+		if self.contains_layer(&submaster_id) {
+			self.layer_opacities.insert(submaster_id, opacity);
+			if auto_insert {
+				if opacity > 0 && !self.layer_order.contains(&submaster_id) {
+					self.layer_order.push(submaster_id.clone())
+				} else if opacity == 0 && self.layer_order.contains(&submaster_id) {
+					self.layer_order.retain(|x| *x != submaster_id);
+				}
+			}
+			return true;
+		} else {
+			return false;
+		}
+	}
+}
+
 impl Default for MixingContext {
 	fn default() -> Self {
 		return MixingContext {
@@ -72,6 +398,8 @@ impl Default for MixingContext {
 			layer_opacities: HashMap::new(),
 			user_submaster_order: Vec::new(),
 			user_submasters: HashMap::new(),
+			expression_layers: HashMap::new(),
+			layer_links: HashMap::new(),
 		};
 	}
 }
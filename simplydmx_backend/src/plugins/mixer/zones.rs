@@ -0,0 +1,31 @@
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+#[portable]
+/// Summary of a mixing zone, for display in the UI
+pub struct ZoneDescription {
+	/// The ID of the zone. This is `None` for the main zone.
+	pub id: Option<Uuid>,
+
+	/// The name of the zone
+	pub name: String,
+
+	/// The level of the zone's grand master
+	pub grand_master: u16,
+
+	/// Whether or not the zone is in blind mode
+	pub blind: bool,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while managing mixing zones
+pub enum ZoneError {
+	#[error("The requested zone does not exist")]
+	ZoneMissing,
+	#[error("The requested layer does not exist")]
+	LayerMissing,
+	#[error("This operation cannot be performed while blind mode is active in an affected zone")]
+	BlindActive,
+}
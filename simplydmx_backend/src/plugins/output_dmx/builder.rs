@@ -0,0 +1,572 @@
+//! Fixture builder: creates and edits DMX fixture types one step at a time.
+//!
+//! Work in progress is kept as a draft, which may be incomplete (for example, it may not have any personalities
+//! yet). Each step is rejected if it would introduce a new validation error, so a draft never gets further from
+//! being valid. Drafts are imported into the show once they are finished.
+
+use std::collections::HashMap;
+
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+	impl_deserialize_err,
+	mixer_utils::state::BlendingScheme,
+	plugins::patcher::{
+		driver_plugin_api::{
+			format_validation_errors, validate_fixture_info, Channel, ChannelSize, ChannelType, ControlGroup,
+			ControlGroupData, FixtureCell, FixtureInfo, FixtureMeta, FixtureValidationError, Personality, PhysicalRange,
+			Position, Segment, SegmentDisplay,
+		},
+		ImportFixtureError,
+	},
+	utilities::{
+		forms::{FormDescriptor, NumberValidation},
+		serialized_data::SerializedData,
+	},
+};
+
+use super::{
+	fixture_types::{DMXByteOrder, DMXChannelMapping, DMXFixtureData, DMXPersonalityData},
+	DMX_DRIVER_ID,
+};
+
+#[portable]
+/// A fixture type that is being built or edited
+pub struct FixtureDraft {
+	/// The fixture type's generic definition
+	pub info: FixtureInfo,
+
+	/// The fixture type's DMX-specific definition
+	pub dmx: DMXFixtureData,
+}
+
+#[portable]
+/// Descriptive details of a fixture type, as entered in the details form
+pub struct DraftDetails {
+	pub name: String,
+	#[serde(default)]
+	pub short_name: Option<String>,
+	#[serde(default)]
+	pub manufacturer: Option<String>,
+	#[serde(default)]
+	pub family: Option<String>,
+	#[serde(default)]
+	pub manual_link: Option<String>,
+}
+
+#[portable]
+/// A channel to add to a draft, as entered in the channel form
+pub struct NewChannel {
+	pub id: String,
+	pub size: ChannelSize,
+	#[serde(default)]
+	pub default: u16,
+
+	/// Whether the channel is split into named segments, such as the slots of a gobo wheel
+	pub kind: NewChannelKind,
+	pub priority: BlendingScheme,
+}
+
+#[portable]
+/// Whether a new channel is linear or segmented
+pub enum NewChannelKind {
+	Linear,
+	Segmented,
+}
+
+#[portable]
+/// A segment to add to a segmented channel, as entered in the segment form
+pub struct NewSegment {
+	pub channel: String,
+	pub name: String,
+	pub start: u16,
+	pub end: u16,
+}
+
+#[portable]
+/// A personality to add to a draft, as entered in the personality form
+pub struct NewPersonality {
+	pub id: String,
+}
+
+#[portable]
+/// A cell to add to a draft, as entered in the cell form
+pub struct NewCell {
+	pub id: String,
+	pub name: String,
+
+	/// The cell's position relative to the fixture's origin, in meters
+	#[serde(default)]
+	pub position: Position,
+}
+
+#[portable]
+/// The forms used to build a fixture type
+pub enum FixtureBuilderForm {
+	/// The fixture type's name, manufacturer, and other details. Also used to create a new draft.
+	Details,
+	Channel,
+	Segment,
+	Personality,
+	Cell,
+}
+
+#[portable]
+/// A single step in building a fixture type
+pub enum FixtureDraftEdit {
+	SetDetails(DraftDetails),
+	AddChannel(NewChannel),
+
+	/// Removes a channel, along with every reference to it
+	RemoveChannel(String),
+	AddSegment(NewSegment),
+	RemoveSegment { channel: String, index: usize },
+
+	/// Sets or clears the physical quantity a channel controls
+	SetPhysicalRange { channel: String, physical: Option<PhysicalRange> },
+
+	/// Adds an empty personality
+	AddPersonality(NewPersonality),
+	RemovePersonality(String),
+
+	/// Makes a channel available in a personality, outputting it after the personality's other channels
+	AddPersonalityChannel { personality: String, channel: String },
+	RemovePersonalityChannel { personality: String, channel: String },
+
+	/// Reorders the channels a personality outputs. The new order must contain the same channels.
+	SetDMXChannelOrder { personality: String, order: Vec<String> },
+
+	/// Outputs a personality's channel to specific slots instead of in channel order, replacing any existing mapping
+	MapPersonalityChannel { personality: String, channel: String, mapping: DMXChannelMapping },
+
+	/// Removes a channel's slot mapping, outputting it after the personality's other ordered channels
+	UnmapPersonalityChannel { personality: String, channel: String },
+	AddControlGroup { name: Option<String>, channels: ControlGroupData },
+	RemoveControlGroup(usize),
+	AddCell(NewCell),
+	RemoveCell(String),
+	SetCellPosition { cell: String, position: Position },
+
+	/// Controls one of the fixture's channels through a cell attribute, replacing the attribute's existing channel
+	SetCellAttribute { cell: String, attribute: String, channel: String },
+	RemoveCellAttribute { cell: String, attribute: String },
+}
+
+impl FixtureDraft {
+	/// Creates a draft of a new fixture type without any channels or personalities
+	pub fn new(details: DraftDetails) -> Result<Self, FixtureBuilderError> {
+		let mut draft = FixtureDraft {
+			info: FixtureInfo {
+				id: Uuid::new_v4(),
+				version: 0,
+				name: String::new(),
+				short_name: None,
+				manufacturer: None,
+				family: None,
+				metadata: FixtureMeta {
+					manufacturer: None,
+					manual_link: None,
+				},
+				channels: Default::default(),
+				personalities: Default::default(),
+				output_driver: DMX_DRIVER_ID.into(),
+				control_groups: Vec::new(),
+				cells: Vec::new(),
+			},
+			dmx: DMXFixtureData {
+				personalities: HashMap::new(),
+			},
+		};
+		draft.set_details(details)?;
+		return Ok(draft);
+	}
+
+	/// Lists every problem that would prevent the draft from being imported
+	pub fn validate(&self) -> Vec<FixtureValidationError> {
+		let mut errors = validate_fixture_info(&self.info);
+		errors.extend(self.dmx.validate(&self.info));
+		return errors;
+	}
+
+	/// Applies a step to the draft, leaving the draft unchanged if the step fails or introduces a validation error
+	pub fn apply(&mut self, edit: FixtureDraftEdit) -> Result<(), FixtureBuilderError> {
+		let existing_errors = self.validate();
+		let mut draft = self.clone();
+		draft.apply_unchecked(edit)?;
+
+		let new_errors: Vec<FixtureValidationError> = draft
+			.validate()
+			.into_iter()
+			.filter(|error| !existing_errors.contains(error))
+			.collect();
+		if !new_errors.is_empty() {
+			return Err(FixtureBuilderError::InvalidStep(new_errors));
+		}
+
+		*self = draft;
+		return Ok(());
+	}
+
+	fn apply_unchecked(&mut self, edit: FixtureDraftEdit) -> Result<(), FixtureBuilderError> {
+		match edit {
+			FixtureDraftEdit::SetDetails(details) => self.set_details(details)?,
+			FixtureDraftEdit::AddChannel(channel) => {
+				if self.info.channels.contains_key(&channel.id) {
+					return Err(FixtureBuilderError::DuplicateChannel(channel.id));
+				}
+				let ch_type = match channel.kind {
+					NewChannelKind::Linear => ChannelType::Linear { priority: channel.priority },
+					NewChannelKind::Segmented => ChannelType::Segmented {
+						segments: Vec::new(),
+						priority: channel.priority,
+						snapping: None,
+					},
+				};
+				self.info.channels.insert(
+					channel.id,
+					Channel {
+						intensity_emulation: None,
+						size: channel.size,
+						default: channel.default,
+						ch_type,
+						physical: None,
+					},
+				);
+			}
+			FixtureDraftEdit::RemoveChannel(channel_id) => {
+				if self.info.channels.remove(&channel_id).is_none() {
+					return Err(FixtureBuilderError::ChannelMissing(channel_id));
+				}
+				for channel in self.info.channels.values_mut() {
+					if let Some(ref mut emulated_channels) = channel.intensity_emulation {
+						emulated_channels.retain(|emulated_channel| emulated_channel != &channel_id);
+					}
+				}
+				for personality in self.info.personalities.values_mut() {
+					personality.available_channels.retain(|available_channel| available_channel != &channel_id);
+				}
+				for personality in self.dmx.personalities.values_mut() {
+					personality.dmx_channel_order.retain(|ordered_channel| ordered_channel != &channel_id);
+					personality.channel_mappings.remove(&channel_id);
+				}
+				self.info
+					.control_groups
+					.retain(|control_group| !control_group.get_channels().channel_ids().contains(&&channel_id));
+				for cell in self.info.cells.iter_mut() {
+					cell.attributes.retain(|_, cell_channel| cell_channel != &channel_id);
+				}
+			}
+			FixtureDraftEdit::AddSegment(segment) => {
+				let segments = self.get_segments_mut(&segment.channel)?;
+				segments.push(Segment {
+					start: segment.start,
+					end: segment.end,
+					name: segment.name,
+					display: SegmentDisplay::Other,
+				});
+				segments.sort_by_key(|segment| segment.start);
+			}
+			FixtureDraftEdit::RemoveSegment { channel, index } => {
+				let segments = self.get_segments_mut(&channel)?;
+				if index >= segments.len() {
+					return Err(FixtureBuilderError::SegmentMissing(index));
+				}
+				segments.remove(index);
+			}
+			FixtureDraftEdit::SetPhysicalRange { channel, physical } => {
+				let channel_info = self
+					.info
+					.channels
+					.get_mut(&channel)
+					.ok_or_else(|| FixtureBuilderError::ChannelMissing(channel.clone()))?;
+				channel_info.physical = physical;
+			}
+			FixtureDraftEdit::AddPersonality(personality) => {
+				if self.info.personalities.contains_key(&personality.id) {
+					return Err(FixtureBuilderError::DuplicatePersonality(personality.id));
+				}
+				self.info.personalities.insert(
+					personality.id.clone(),
+					Personality {
+						available_channels: Vec::new(),
+					},
+				);
+				self.dmx.personalities.insert(
+					personality.id,
+					DMXPersonalityData {
+						dmx_channel_order: Vec::new(),
+						channel_mappings: HashMap::new(),
+						byte_order: DMXByteOrder::MsbFirst,
+					},
+				);
+			}
+			FixtureDraftEdit::RemovePersonality(personality_id) => {
+				if self.info.personalities.remove(&personality_id).is_none() {
+					return Err(FixtureBuilderError::PersonalityMissing(personality_id));
+				}
+				self.dmx.personalities.remove(&personality_id);
+			}
+			FixtureDraftEdit::AddPersonalityChannel { personality, channel } => {
+				if !self.info.channels.contains_key(&channel) {
+					return Err(FixtureBuilderError::ChannelMissing(channel));
+				}
+				let (generic_personality, dmx_personality) = self.get_personality_mut(&personality)?;
+				if generic_personality.available_channels.contains(&channel) {
+					return Err(FixtureBuilderError::DuplicateChannel(channel));
+				}
+				generic_personality.available_channels.push(channel.clone());
+				dmx_personality.dmx_channel_order.push(channel);
+			}
+			FixtureDraftEdit::RemovePersonalityChannel { personality, channel } => {
+				let (generic_personality, dmx_personality) = self.get_personality_mut(&personality)?;
+				if !generic_personality.available_channels.contains(&channel) {
+					return Err(FixtureBuilderError::ChannelMissing(channel));
+				}
+				generic_personality.available_channels.retain(|available_channel| available_channel != &channel);
+				dmx_personality.dmx_channel_order.retain(|ordered_channel| ordered_channel != &channel);
+				dmx_personality.channel_mappings.remove(&channel);
+			}
+			FixtureDraftEdit::SetDMXChannelOrder { personality, order } => {
+				let (_, dmx_personality) = self.get_personality_mut(&personality)?;
+				let mut current_order = dmx_personality.dmx_channel_order.clone();
+				let mut new_order = order.clone();
+				current_order.sort();
+				new_order.sort();
+				if current_order != new_order {
+					return Err(FixtureBuilderError::InvalidChannelOrder);
+				}
+				dmx_personality.dmx_channel_order = order;
+			}
+			FixtureDraftEdit::MapPersonalityChannel { personality, channel, mapping } => {
+				let (generic_personality, dmx_personality) = self.get_personality_mut(&personality)?;
+				if !generic_personality.available_channels.contains(&channel) {
+					return Err(FixtureBuilderError::ChannelMissing(channel));
+				}
+				dmx_personality.dmx_channel_order.retain(|ordered_channel| ordered_channel != &channel);
+				dmx_personality.channel_mappings.insert(channel, mapping);
+			}
+			FixtureDraftEdit::UnmapPersonalityChannel { personality, channel } => {
+				let (_, dmx_personality) = self.get_personality_mut(&personality)?;
+				if dmx_personality.channel_mappings.remove(&channel).is_none() {
+					return Err(FixtureBuilderError::ChannelNotMapped(channel));
+				}
+				dmx_personality.dmx_channel_order.push(channel);
+			}
+			FixtureDraftEdit::AddControlGroup { name, channels } => {
+				self.info.control_groups.push(ControlGroup::new(name, channels));
+			}
+			FixtureDraftEdit::RemoveControlGroup(index) => {
+				if index >= self.info.control_groups.len() {
+					return Err(FixtureBuilderError::ControlGroupMissing(index));
+				}
+				self.info.control_groups.remove(index);
+			}
+			FixtureDraftEdit::AddCell(cell) => {
+				if self.info.cells.iter().any(|existing_cell| existing_cell.id == cell.id) {
+					return Err(FixtureBuilderError::DuplicateCell(cell.id));
+				}
+				self.info.cells.push(FixtureCell {
+					id: cell.id,
+					name: cell.name,
+					attributes: Default::default(),
+					position: cell.position,
+				});
+			}
+			FixtureDraftEdit::RemoveCell(cell_id) => {
+				let cell_count = self.info.cells.len();
+				self.info.cells.retain(|cell| cell.id != cell_id);
+				if self.info.cells.len() == cell_count {
+					return Err(FixtureBuilderError::CellMissing(cell_id));
+				}
+			}
+			FixtureDraftEdit::SetCellPosition { cell, position } => {
+				self.get_cell_mut(&cell)?.position = position;
+			}
+			FixtureDraftEdit::SetCellAttribute { cell, attribute, channel } => {
+				if !self.info.channels.contains_key(&channel) {
+					return Err(FixtureBuilderError::ChannelMissing(channel));
+				}
+				self.get_cell_mut(&cell)?.attributes.insert(attribute, channel);
+			}
+			FixtureDraftEdit::RemoveCellAttribute { cell, attribute } => {
+				if self.get_cell_mut(&cell)?.attributes.remove(&attribute).is_none() {
+					return Err(FixtureBuilderError::CellAttributeMissing(attribute));
+				}
+			}
+		}
+		return Ok(());
+	}
+
+	fn set_details(&mut self, details: DraftDetails) -> Result<(), FixtureBuilderError> {
+		let name = details.name.trim();
+		if name.is_empty() {
+			return Err(FixtureBuilderError::InvalidForm("The fixture type needs a name".into()));
+		}
+		let non_empty = |value: Option<String>| value.filter(|value| !value.trim().is_empty());
+
+		self.info.name = name.into();
+		self.info.short_name = non_empty(details.short_name);
+		self.info.manufacturer = non_empty(details.manufacturer);
+		self.info.family = non_empty(details.family);
+		self.info.metadata = FixtureMeta {
+			manufacturer: self.info.manufacturer.clone(),
+			manual_link: non_empty(details.manual_link),
+		};
+		return Ok(());
+	}
+
+	fn get_segments_mut(&mut self, channel_id: &str) -> Result<&mut Vec<Segment>, FixtureBuilderError> {
+		let channel = self
+			.info
+			.channels
+			.get_mut(channel_id)
+			.ok_or_else(|| FixtureBuilderError::ChannelMissing(channel_id.into()))?;
+		return match channel.ch_type {
+			ChannelType::Segmented { ref mut segments, .. } => Ok(segments),
+			ChannelType::Linear { .. } => Err(FixtureBuilderError::NotSegmented(channel_id.into())),
+		};
+	}
+
+	fn get_personality_mut(
+		&mut self,
+		personality_id: &str,
+	) -> Result<(&mut Personality, &mut DMXPersonalityData), FixtureBuilderError> {
+		let missing = || FixtureBuilderError::PersonalityMissing(personality_id.into());
+		let generic_personality = self.info.personalities.get_mut(personality_id).ok_or_else(missing)?;
+		let dmx_personality = self.dmx.personalities.get_mut(personality_id).ok_or_else(missing)?;
+		return Ok((generic_personality, dmx_personality));
+	}
+
+	fn get_cell_mut(&mut self, cell_id: &str) -> Result<&mut FixtureCell, FixtureBuilderError> {
+		return self
+			.info
+			.cells
+			.iter_mut()
+			.find(|cell| cell.id == cell_id)
+			.ok_or_else(|| FixtureBuilderError::CellMissing(cell_id.into()));
+	}
+
+	/// Gets one of the builder's forms. The details form is pre-filled with the draft's details.
+	pub fn get_form(draft: Option<&FixtureDraft>, form: &FixtureBuilderForm) -> FormDescriptor {
+		return match form {
+			FixtureBuilderForm::Details => {
+				let info = draft.map(|draft| &draft.info);
+				let text = |value: Option<&String>| value.cloned().unwrap_or_default();
+				FormDescriptor::new()
+					.textbox_prefilled("Name", "name", text(info.map(|info| &info.name)))
+					.textbox_prefilled("Short Name", "short_name", text(info.and_then(|info| info.short_name.as_ref())))
+					.textbox_prefilled("Manufacturer", "manufacturer", text(info.and_then(|info| info.manufacturer.as_ref())))
+					.textbox_prefilled("Family", "family", text(info.and_then(|info| info.family.as_ref())))
+					.textbox_prefilled(
+						"Manual Link",
+						"manual_link",
+						text(info.and_then(|info| info.metadata.manual_link.as_ref())),
+					)
+					.build()
+			}
+			FixtureBuilderForm::Channel => FormDescriptor::new()
+				.textbox("Channel ID", "id")
+				.dropdown_static("Size", "size", |options| options.add_item("8-bit", "U8").add_item("16-bit", "U16"))
+				.number_prefilled(
+					"Default Value",
+					"default",
+					NumberValidation::And(vec![
+						NumberValidation::Between(0.0, 65535.0),
+						NumberValidation::DivisibleBy(1.0),
+					]),
+					0.0,
+				)
+				.dropdown_static("Type", "kind", |options| {
+					options.add_item("Linear", "Linear").add_item("Segmented", "Segmented")
+				})
+				.dropdown_static("Blending", "priority", |options| {
+					options.add_item("Highest takes priority", "HTP").add_item("Latest takes priority", "LTP")
+				})
+				.build(),
+			FixtureBuilderForm::Segment => {
+				let mut segmented_channels: Vec<&String> = draft
+					.map(|draft| {
+						draft
+							.info
+							.channels
+							.iter()
+							.filter(|(_, channel)| matches!(channel.ch_type, ChannelType::Segmented { .. }))
+							.map(|(channel_id, _)| channel_id)
+							.collect()
+					})
+					.unwrap_or_default();
+				segmented_channels.sort();
+				let value_validation = NumberValidation::And(vec![
+					NumberValidation::Between(0.0, 65535.0),
+					NumberValidation::DivisibleBy(1.0),
+				]);
+				FormDescriptor::new()
+					.dropdown_static("Channel", "channel", |options| {
+						segmented_channels
+							.into_iter()
+							.fold(options, |options, channel_id| options.add_item(channel_id, channel_id))
+					})
+					.textbox("Name", "name")
+					.number("Start", "start", value_validation.clone())
+					.number("End", "end", value_validation)
+					.build()
+			}
+			FixtureBuilderForm::Personality => FormDescriptor::new().textbox("Personality ID", "id").build(),
+			FixtureBuilderForm::Cell => FormDescriptor::new().textbox("Cell ID", "id").textbox("Name", "name").build(),
+		};
+	}
+
+	/// Converts submitted form data into a step
+	pub fn parse_form(form: &FixtureBuilderForm, data: SerializedData) -> Result<FixtureDraftEdit, FixtureBuilderError> {
+		return Ok(match form {
+			FixtureBuilderForm::Details => FixtureDraftEdit::SetDetails(data.deserialize()?),
+			FixtureBuilderForm::Channel => FixtureDraftEdit::AddChannel(data.deserialize()?),
+			FixtureBuilderForm::Segment => FixtureDraftEdit::AddSegment(data.deserialize()?),
+			FixtureBuilderForm::Personality => FixtureDraftEdit::AddPersonality(data.deserialize()?),
+			FixtureBuilderForm::Cell => FixtureDraftEdit::AddCell(data.deserialize()?),
+		});
+	}
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while building a fixture type
+pub enum FixtureBuilderError {
+	#[error("The requested fixture draft does not exist")]
+	DraftMissing,
+	#[error("The requested fixture type does not exist in the show")]
+	FixtureTypeMissing,
+	#[error("The form data is invalid:\n{0}")]
+	InvalidForm(String),
+	#[error("Channel {0:?} already exists")]
+	DuplicateChannel(String),
+	#[error("Channel {0:?} does not exist")]
+	ChannelMissing(String),
+	#[error("Channel {0:?} is not segmented")]
+	NotSegmented(String),
+	#[error("Segment {0} does not exist")]
+	SegmentMissing(usize),
+	#[error("Personality {0:?} already exists")]
+	DuplicatePersonality(String),
+	#[error("Personality {0:?} does not exist")]
+	PersonalityMissing(String),
+	#[error("The new DMX channel order must contain exactly the channels already in the order")]
+	InvalidChannelOrder,
+	#[error("Channel {0:?} is not mapped to specific slots")]
+	ChannelNotMapped(String),
+	#[error("Control group {0} does not exist")]
+	ControlGroupMissing(usize),
+	#[error("Cell {0:?} already exists")]
+	DuplicateCell(String),
+	#[error("Cell {0:?} does not exist")]
+	CellMissing(String),
+	#[error("Cell attribute {0:?} does not exist")]
+	CellAttributeMissing(String),
+	#[error("This step would make the fixture definition invalid:\n{}", format_validation_errors(.0))]
+	InvalidStep(Vec<FixtureValidationError>),
+	#[error("The fixture type could not be imported:\n{0}")]
+	ImportFailed(ImportFixtureError),
+}
+impl_deserialize_err!(FixtureBuilderError, Self::InvalidForm);
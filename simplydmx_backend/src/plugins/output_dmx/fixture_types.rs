@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
+use anyhow::Context;
 use simplydmx_plugin_framework::*;
 
+use crate::plugins::patcher::driver_plugin_api::{ChannelSize, FixtureInfo, FixtureValidationError};
+
 #[portable]
 /// DMX-specific components of a fixture definition.
 ///
@@ -10,10 +13,207 @@ pub struct DMXFixtureData {
 	pub personalities: HashMap<String, DMXPersonalityData>,
 }
 
+impl DMXFixtureData {
+	/// Checks that every personality in the fixture definition has a DMX layout that outputs
+	/// exactly the channels available in that personality, without writing to any slot twice
+	/// or past the end of a universe
+	pub fn validate(&self, fixture_info: &FixtureInfo) -> Vec<FixtureValidationError> {
+		let mut errors = Vec::new();
+
+		let mut personality_ids: Vec<&String> = fixture_info.personalities.keys().collect();
+		personality_ids.sort();
+		for personality_id in personality_ids {
+			let available_channels = &fixture_info.personalities[personality_id].available_channels;
+			let personality = match self.personalities.get(personality_id) {
+				Some(personality) => personality,
+				None => {
+					errors.push(FixtureValidationError::DriverPersonalityMissing {
+						personality: personality_id.clone(),
+					});
+					continue;
+				}
+			};
+
+			let mut mapped_channels: Vec<&String> = personality.channel_mappings.keys().collect();
+			mapped_channels.sort();
+			let output_channels = personality.dmx_channel_order.iter().chain(mapped_channels.iter().copied());
+			for channel_id in output_channels {
+				if !available_channels.contains(channel_id) {
+					errors.push(FixtureValidationError::DriverChannelUnavailable {
+						personality: personality_id.clone(),
+						channel: channel_id.clone(),
+					});
+				}
+			}
+			for channel_id in available_channels.iter() {
+				if !personality.dmx_channel_order.contains(channel_id) && !personality.channel_mappings.contains_key(channel_id) {
+					errors.push(FixtureValidationError::DriverChannelUnmapped {
+						personality: personality_id.clone(),
+						channel: channel_id.clone(),
+					});
+				}
+			}
+
+			// Each slot can only be written by one channel
+			let mut used_slots = Vec::new();
+			for channel_id in mapped_channels {
+				let slots = match fixture_info.channels.get(channel_id) {
+					Some(channel) => personality.channel_mappings[channel_id].get_slots(&channel.size),
+					None => continue,
+				};
+				let slots = match slots {
+					Some(slots) => slots,
+					None => {
+						errors.push(FixtureValidationError::DriverSlotOutOfRange {
+							personality: personality_id.clone(),
+							channel: channel_id.clone(),
+						});
+						continue;
+					}
+				};
+				if personality.dmx_channel_order.contains(channel_id) || slots.iter().any(|slot| used_slots.contains(slot)) {
+					errors.push(FixtureValidationError::DriverSlotConflict {
+						personality: personality_id.clone(),
+						channel: channel_id.clone(),
+					});
+				}
+				used_slots.extend(slots);
+			}
+
+			// Every slot has to fit in a single universe. Layouts that can't be built have already been reported.
+			if let Ok(layout) = personality.layout(fixture_info) {
+				for channel_layout in layout {
+					if channel_layout.slots.iter().any(|slot| *slot >= 512) {
+						errors.push(FixtureValidationError::DriverSlotOutOfRange {
+							personality: personality_id.clone(),
+							channel: channel_layout.channel_id,
+						});
+					}
+				}
+			}
+		}
+
+		return errors;
+	}
+}
+
 #[portable]
 /// DMX-specific personality data
 ///
 /// This goes inside a `DMXFixtureData` instance
 pub struct DMXPersonalityData {
+	/// Channels output in order from the fixture's first address, skipping slots used by `channel_mappings`
 	pub dmx_channel_order: Vec<String>,
+
+	/// Channels whose bytes are output to specific slots, such as fixtures that place their fine channels
+	/// after all of their coarse channels
+	#[serde(default)]
+	pub channel_mappings: HashMap<String, DMXChannelMapping>,
+
+	/// The order that 16-bit channels in `dmx_channel_order` output their bytes in
+	#[serde(default)]
+	pub byte_order: DMXByteOrder,
+}
+
+impl DMXPersonalityData {
+	/// Works out which slots each channel is output to
+	pub fn layout(&self, fixture_info: &FixtureInfo) -> anyhow::Result<Vec<DMXChannelLayout>> {
+		let mut layout = Vec::with_capacity(self.dmx_channel_order.len() + self.channel_mappings.len());
+
+		// Mapped channels are placed first so ordered channels can fill the remaining slots
+		let mut mapped_slots = Vec::new();
+		for (channel_id, mapping) in self.channel_mappings.iter() {
+			let channel = fixture_info
+				.channels
+				.get(channel_id)
+				.context("Fixture definition referenced a channel that was not defined.")?;
+			let slots = mapping
+				.get_slots(&channel.size)
+				.context("Fixture definition mapped a channel past the last DMX slot.")?;
+			mapped_slots.extend(slots.iter().copied());
+			layout.push(DMXChannelLayout {
+				channel_id: channel_id.clone(),
+				slots,
+			});
+		}
+
+		let mut next_slot: u16 = 0;
+		for channel_id in self.dmx_channel_order.iter() {
+			let channel = fixture_info
+				.channels
+				.get(channel_id)
+				.context("Fixture definition referenced a channel that was not defined.")?;
+			let byte_count = match channel.size {
+				ChannelSize::U8 => 1,
+				ChannelSize::U16 => 2,
+			};
+			let mut slots = Vec::with_capacity(byte_count);
+			while slots.len() < byte_count {
+				if !mapped_slots.contains(&next_slot) {
+					slots.push(next_slot);
+				}
+				next_slot = next_slot.checked_add(1).context("Fixture definition has more channels than DMX slots.")?;
+			}
+			if self.byte_order == DMXByteOrder::LsbFirst {
+				slots.reverse();
+			}
+			layout.push(DMXChannelLayout {
+				channel_id: channel_id.clone(),
+				slots,
+			});
+		}
+
+		return Ok(layout);
+	}
+}
+
+/// The slots a channel is output to, relative to the fixture's first address, with the most significant byte first
+#[derive(Clone)]
+pub struct DMXChannelLayout {
+	pub channel_id: String,
+	pub slots: Vec<u16>,
+}
+
+#[portable]
+/// Outputs a channel's bytes to specific slots. Slots are numbered from 0 at the fixture's first address.
+///
+/// Values are scaled to the number of slots used, so 8-bit channels can drive 16-bit outputs and 16-bit channels
+/// can drive 24-bit outputs.
+pub struct DMXChannelMapping {
+	/// The slot of the most significant byte
+	pub coarse: u16,
+
+	/// The slot of the second byte. Defaults to the slot after `coarse` for 16-bit and 24-bit outputs.
+	#[serde(default)]
+	pub fine: Option<u16>,
+
+	/// The slot of the third byte, for 24-bit outputs
+	#[serde(default)]
+	pub ultra: Option<u16>,
+}
+
+impl DMXChannelMapping {
+	/// Gets the slots this mapping outputs to, with the most significant byte first.
+	///
+	/// Returns `None` if the default fine slot would come after the last possible slot.
+	pub fn get_slots(&self, size: &ChannelSize) -> Option<Vec<u16>> {
+		let fine = match self.fine {
+			Some(fine) => Some(fine),
+			None if matches!(size, ChannelSize::U16) || self.ultra.is_some() => Some(self.coarse.checked_add(1)?),
+			None => None,
+		};
+		return Some([Some(self.coarse), fine, self.ultra].into_iter().flatten().collect());
+	}
+}
+
+#[portable]
+#[derive(Default, Copy, PartialEq, Eq)]
+/// The order that a multi-byte channel outputs its bytes in
+pub enum DMXByteOrder {
+	/// The coarse byte comes first
+	#[default]
+	MsbFirst,
+
+	/// The fine byte comes first
+	LsbFirst,
 }
@@ -0,0 +1,761 @@
+//! Importer for GDTF (General Device Type Format) fixture definitions.
+//!
+//! A `.gdtf` file is a zip archive containing `description.xml` and any wheel media. Only the
+//! parts of the format that SimplyDMX can represent are imported, and everything else is
+//! reported back to the user as an `ImportWarning`.
+
+use std::{
+	collections::HashMap,
+	io::{Cursor, Read},
+};
+
+use roxmltree::{Document, Node};
+use rustc_hash::FxHashMap;
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+	mixer_utils::state::{BlendingScheme, SnapData},
+	plugins::{
+		output_dmx::{
+			fixture_types::{DMXByteOrder, DMXChannelMapping, DMXFixtureData, DMXPersonalityData},
+			DMX_DRIVER_ID,
+		},
+		patcher::driver_plugin_api::{
+			AssetDescriptor, Channel, ChannelSize, ChannelType, ControlGroup, ControlGroupData,
+			FixtureBundle, FixtureInfo, FixtureMeta, Personality, PhysicalRange, PhysicalUnit, Segment,
+			SegmentDisplay,
+		},
+		patcher::importers::{reserved_channel, ConvertedFixture, ImportWarning, RESERVED_CHANNEL},
+		patcher::CurveShape,
+	},
+	utilities::serialized_data::SerializedData,
+};
+
+#[portable]
+#[derive(Error)]
+/// An error that prevented a GDTF file from being imported
+pub enum GdtfImportError {
+	#[error("The file is not a valid GDTF archive: {0}")]
+	InvalidArchive(String),
+	#[error("The archive does not contain a description.xml file")]
+	MissingDescription,
+	#[error("description.xml could not be parsed: {0}")]
+	InvalidXml(String),
+	#[error("description.xml does not contain a FixtureType")]
+	MissingFixtureType,
+	#[error("The fixture type does not contain any DMX modes that can be imported")]
+	NoModes,
+	#[error("{0} uses DMX offset {1}, which is outside of the 1-512 range")]
+	InvalidOffset(String, u32),
+	#[error("The converted fixture could not be imported: {0}")]
+	ImportFailed(String),
+}
+
+/// A slot on a color or gobo wheel
+struct WheelSlot {
+	color: Option<(u8, u8, u8)>,
+	media: Option<AssetDescriptor>,
+}
+
+/// A channel function's range, used to build segments
+struct FunctionRange<'a, 'input> {
+	node: Node<'a, 'input>,
+	start: u16,
+}
+
+/// Converts the contents of a `.gdtf` file into a fixture bundle for the DMX output driver
+pub fn convert(data: &[u8]) -> Result<ConvertedFixture, GdtfImportError> {
+	let mut archive = zip::ZipArchive::new(Cursor::new(data))
+		.map_err(|error| GdtfImportError::InvalidArchive(error.to_string()))?;
+
+	// Load description.xml and wheel media up front
+	let mut description = String::new();
+	archive
+		.by_name("description.xml")
+		.map_err(|_| GdtfImportError::MissingDescription)?
+		.read_to_string(&mut description)
+		.map_err(|error| GdtfImportError::InvalidArchive(error.to_string()))?;
+	let mut media_files: HashMap<String, Vec<u8>> = HashMap::new();
+	for index in 0..archive.len() {
+		if let Ok(mut file) = archive.by_index(index) {
+			if file.is_file() && file.name().starts_with("wheels/") {
+				let name = file.name().to_owned();
+				let mut contents = Vec::new();
+				if file.read_to_end(&mut contents).is_ok() {
+					media_files.insert(name, contents);
+				}
+			}
+		}
+	}
+
+	let document = Document::parse(&description)
+		.map_err(|error| GdtfImportError::InvalidXml(error.to_string()))?;
+	let fixture_type = document
+		.descendants()
+		.find(|node| node.has_tag_name("FixtureType"))
+		.ok_or(GdtfImportError::MissingFixtureType)?;
+
+	let mut warnings = Vec::new();
+	let wheels = read_wheels(fixture_type, &media_files, &mut warnings);
+
+	let mut channels: FxHashMap<String, Channel> = FxHashMap::default();
+	let mut channel_sources: HashMap<String, (String, String)> = HashMap::new();
+	let mut personalities = FxHashMap::default();
+	let mut dmx_personalities = HashMap::new();
+
+	for mode in children(fixture_type, "DMXModes").flat_map(|modes| children(modes, "DMXMode")) {
+		let mode_name = mode.attribute("Name").unwrap_or("Default").to_owned();
+
+		// DMX slot (1-indexed) --> channel ID, for the first slot of each channel
+		let mut slot_starts: HashMap<u16, String> = HashMap::new();
+		let mut occupied_slots: Vec<u16> = Vec::new();
+
+		// Channels placed with explicit slot mappings, and the slots they use
+		let mut channel_mappings: HashMap<String, DMXChannelMapping> = HashMap::new();
+		let mut mapped_slots: Vec<u16> = Vec::new();
+
+		for dmx_channel in children(mode, "DMXChannels").flat_map(|dmx_channels| children(dmx_channels, "DMXChannel")) {
+			let geometry = dmx_channel.attribute("Geometry").unwrap_or("").to_owned();
+			let logical_channels: Vec<Node> = children(dmx_channel, "LogicalChannel").collect();
+			let logical_channel = match logical_channels.first() {
+				Some(logical_channel) => *logical_channel,
+				None => continue,
+			};
+			let attribute = logical_channel.attribute("Attribute").unwrap_or("NoFeature").to_owned();
+			let location = format!("{} / {}_{}", mode_name, geometry, attribute);
+
+			if logical_channels.len() > 1 {
+				warnings.push(ImportWarning::new(&location, "Only the first logical channel of a DMX channel is supported. The others were ignored."));
+			}
+
+			let dmx_break: u16 = dmx_channel.attribute("DMXBreak").and_then(|value| value.parse().ok()).unwrap_or(1);
+			if dmx_break != 1 {
+				warnings.push(ImportWarning::new(&location, "Channels in additional DMX breaks are not supported. The channel was skipped."));
+				continue;
+			}
+
+			let offsets: Vec<u32> = match dmx_channel.attribute("Offset") {
+				None | Some("None") | Some("") => {
+					warnings.push(ImportWarning::new(&location, "Virtual channels without a DMX offset are not supported. The channel was skipped."));
+					continue;
+				}
+				Some(offset) => match offset.split(',').map(|offset| offset.trim().parse()).collect() {
+					Ok(offsets) => offsets,
+					Err(_) => {
+						warnings.push(ImportWarning::new(&location, format!("Could not parse DMX offset {:?}. The channel was skipped.", offset)));
+						continue;
+					}
+				},
+			};
+			if let Some(offset) = offsets.iter().find(|offset| !(1..=512).contains(*offset)) {
+				return Err(GdtfImportError::InvalidOffset(location, *offset));
+			}
+			let offsets: Vec<u16> = offsets.into_iter().map(|offset| offset as u16).collect();
+			if offsets.is_empty() {
+				continue;
+			}
+
+			// Determine the channel size. Fine channels that don't directly follow their coarse channel, and channels
+			// with more than two bytes, are placed with an explicit slot mapping.
+			let adjacent = offsets.windows(2).all(|pair| pair[0].checked_add(1) == Some(pair[1]));
+			let size = if offsets.len() >= 2 { ChannelSize::U16 } else { ChannelSize::U8 };
+			let mapping = if offsets.len() > 2 || !adjacent {
+				if offsets.len() > 3 {
+					warnings.push(ImportWarning::new(&location, format!("{}-bit channels are not supported. Only the three most significant bytes are controlled.", offsets.len() * 8)));
+				}
+				Some(DMXChannelMapping {
+					coarse: offsets[0] - 1,
+					fine: offsets.get(1).map(|offset| offset - 1),
+					ultra: offsets.get(2).map(|offset| offset - 1),
+				})
+			} else {
+				None
+			};
+			let size_bytes: u32 = if let ChannelSize::U16 = size { 2 } else { 1 };
+
+			let channel = convert_channel(dmx_channel, logical_channel, &attribute, size, size_bytes, &wheels, &location, &mut warnings);
+
+			// Channels are shared between modes, so differing definitions need unique IDs
+			let mut channel_id = format!("{}_{}", geometry, attribute);
+			if let Some(existing) = channels.get(&channel_id) {
+				if serde_json::to_value(existing).ok() != serde_json::to_value(&channel).ok() {
+					channel_id = format!("{} ({})", channel_id, mode_name);
+				}
+			}
+			if slot_starts.values().chain(channel_mappings.keys()).any(|existing_id| existing_id == &channel_id) {
+				warnings.push(ImportWarning::new(&location, "The channel appears more than once in this mode. Duplicates were skipped."));
+				continue;
+			}
+			channel_sources.insert(channel_id.clone(), (geometry, attribute));
+			channels.insert(channel_id.clone(), channel);
+
+			match mapping {
+				Some(mapping) => {
+					occupied_slots.extend(offsets.iter().copied());
+					mapped_slots.extend(offsets.iter().take(3).copied());
+					channel_mappings.insert(channel_id, mapping);
+				}
+				None => {
+					slot_starts.insert(offsets[0], channel_id);
+					occupied_slots.extend(offsets.iter().copied());
+				}
+			}
+		}
+
+		if slot_starts.is_empty() && channel_mappings.is_empty() {
+			warnings.push(ImportWarning::new(&mode_name, "The mode has no channels that can be imported. It was skipped."));
+			continue;
+		}
+
+		// Lay the channels out in DMX order, filling gaps with the reserved channel. Mapped slots are skipped
+		// since ordered channels are placed around them.
+		let last_slot = occupied_slots.iter().cloned().max().unwrap_or(0);
+		let mut dmx_channel_order = Vec::new();
+		let mut available_channels: Vec<String> = channel_mappings.keys().cloned().collect();
+		available_channels.sort();
+		let mut filled_gap = false;
+		let mut next_slot = Some(1u16);
+		while let Some(slot) = next_slot.filter(|slot| *slot <= last_slot) {
+			let width = if mapped_slots.contains(&slot) {
+				1
+			} else if let Some(channel_id) = slot_starts.get(&slot) {
+				dmx_channel_order.push(channel_id.clone());
+				available_channels.push(channel_id.clone());
+				match channels.get(channel_id).map(|channel| &channel.size) {
+					Some(ChannelSize::U16) => 2,
+					_ => 1,
+				}
+			} else {
+				dmx_channel_order.push(RESERVED_CHANNEL.to_owned());
+				filled_gap = true;
+				1
+			};
+			next_slot = slot.checked_add(width);
+		}
+		if filled_gap {
+			warnings.push(ImportWarning::new(&mode_name, "Some DMX slots in this mode are unused or only partially supported. They were filled with a reserved channel that outputs 0."));
+			available_channels.push(RESERVED_CHANNEL.to_owned());
+			channels.entry(RESERVED_CHANNEL.to_owned()).or_insert_with(reserved_channel);
+		}
+
+		personalities.insert(mode_name.clone(), Personality { available_channels });
+		dmx_personalities.insert(
+			mode_name,
+			DMXPersonalityData {
+				dmx_channel_order,
+				channel_mappings,
+				byte_order: DMXByteOrder::MsbFirst,
+			},
+		);
+	}
+
+	if personalities.is_empty() {
+		return Err(GdtfImportError::NoModes);
+	}
+
+	let manufacturer = fixture_type.attribute("Manufacturer").map(String::from);
+	let fixture_info = FixtureInfo {
+		id: fixture_type
+			.attribute("FixtureTypeID")
+			.and_then(|id| Uuid::parse_str(id).ok())
+			.unwrap_or_else(Uuid::new_v4),
+		version: 0,
+		name: fixture_type
+			.attribute("LongName")
+			.filter(|name| !name.is_empty())
+			.or(fixture_type.attribute("Name"))
+			.unwrap_or("Unnamed GDTF fixture")
+			.to_owned(),
+		short_name: fixture_type.attribute("ShortName").filter(|name| !name.is_empty()).map(String::from),
+		manufacturer: manufacturer.clone(),
+		family: None,
+		metadata: FixtureMeta {
+			manufacturer,
+			manual_link: None,
+		},
+		control_groups: build_control_groups(&channel_sources, &mut warnings),
+		cells: Vec::new(),
+		channels,
+		personalities,
+		output_driver: String::from(DMX_DRIVER_ID),
+	};
+
+	let output_info = serde_json::to_value(DMXFixtureData {
+		personalities: dmx_personalities,
+	})
+	.map_err(|error| GdtfImportError::ImportFailed(error.to_string()))?;
+
+	return Ok(ConvertedFixture {
+		bundle: FixtureBundle {
+			fixture_info,
+			output_info: SerializedData::JSON(output_info),
+		},
+		warnings,
+	});
+}
+
+/// Iterates over the child elements of `node` with the given tag name
+fn children<'a, 'input: 'a>(node: Node<'a, 'input>, tag_name: &'a str) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+	return node.children().filter(move |child| child.has_tag_name(tag_name));
+}
+
+/// Reads the fixture's wheels, returning the slots of each wheel by name
+fn read_wheels(
+	fixture_type: Node,
+	media_files: &HashMap<String, Vec<u8>>,
+	warnings: &mut Vec<ImportWarning>,
+) -> HashMap<String, Vec<WheelSlot>> {
+	let mut wheels = HashMap::new();
+	for wheel in children(fixture_type, "Wheels").flat_map(|wheels| children(wheels, "Wheel")) {
+		let wheel_name = wheel.attribute("Name").unwrap_or("").to_owned();
+		let slots = children(wheel, "Slot")
+			.map(|slot| {
+				let media = slot.attribute("MediaFileName").filter(|name| !name.is_empty()).and_then(|file_name| {
+					if let Some(svg) = media_files.get(&format!("wheels/{}.svg", file_name)) {
+						Some(AssetDescriptor::SVGInline(String::from_utf8_lossy(svg).into_owned()))
+					} else if let Some(png) = media_files.get(&format!("wheels/{}.png", file_name)) {
+						Some(AssetDescriptor::PNGInline(png.clone()))
+					} else {
+						warnings.push(ImportWarning::new(
+							format!("Wheel {}", wheel_name),
+							format!("Could not find media file {:?} in the archive. The slot will be displayed without an image.", file_name),
+						));
+						None
+					}
+				});
+				WheelSlot {
+					color: slot.attribute("Color").and_then(cie_to_rgb),
+					media,
+				}
+			})
+			.collect();
+		wheels.insert(wheel_name, slots);
+	}
+	return wheels;
+}
+
+/// Parses a DMX value in GDTF's `value/bytes` notation, scaling it to a channel of `size_bytes` bytes.
+///
+/// Returns `None` if the value can't be parsed or uses a byte count other than 1-4.
+fn parse_dmx_value(value: &str, size_bytes: u32) -> Option<u16> {
+	let (value, bytes) = match value.split_once('/') {
+		Some((value, bytes)) => (value.trim().parse::<u64>().ok()?, bytes.trim().parse::<u32>().ok()?),
+		None => (value.trim().parse::<u64>().ok()?, 1),
+	};
+	if !(1..=4).contains(&bytes) {
+		return None;
+	}
+	let scaled = if bytes > size_bytes {
+		value.checked_shr(8 * (bytes - size_bytes))?
+	} else {
+		value.checked_shl(8 * (size_bytes - bytes))?
+	};
+	let max_value = (1u64 << (8 * size_bytes)) - 1;
+	return Some(scaled.min(max_value) as u16);
+}
+
+/// Converts a GDTF DMX channel into a SimplyDMX channel
+#[allow(clippy::too_many_arguments)]
+fn convert_channel(
+	dmx_channel: Node,
+	logical_channel: Node,
+	attribute: &str,
+	size: ChannelSize,
+	size_bytes: u32,
+	wheels: &HashMap<String, Vec<WheelSlot>>,
+	location: &str,
+	warnings: &mut Vec<ImportWarning>,
+) -> Channel {
+	let max_value = ((1u32 << (8 * size_bytes)) - 1) as u16;
+	let mut functions: Vec<FunctionRange> = children(logical_channel, "ChannelFunction")
+		.map(|node| FunctionRange {
+			node,
+			start: node.attribute("DMXFrom").and_then(|value| parse_dmx_value(value, size_bytes)).unwrap_or(0),
+		})
+		.collect();
+	functions.sort_by_key(|function| function.start);
+
+	// GDTF 1.0 stores the default on the DMX channel, while 1.1 stores it on the initial function
+	let initial_function = dmx_channel
+		.attribute("InitialFunction")
+		.and_then(|reference| reference.rsplit('.').next());
+	let default = dmx_channel
+		.attribute("Default")
+		.or_else(|| {
+			functions
+				.iter()
+				.find(|function| initial_function.is_some() && function.node.attribute("Name") == initial_function)
+				.or(functions.first())
+				.and_then(|function| function.node.attribute("Default"))
+		})
+		.and_then(|value| parse_dmx_value(value, size_bytes))
+		.unwrap_or(0);
+
+	let priority = if attribute == "Dimmer" { BlendingScheme::HTP } else { BlendingScheme::LTP };
+	let snapping = match logical_channel.attribute("Snap") {
+		Some("Yes") | Some("On") => Some(SnapData::SnapAt(u16::MAX / 2)),
+		_ => None,
+	};
+
+	let has_named_sets = functions.iter().any(|function| {
+		children(function.node, "ChannelSet").any(|set| set.attribute("Name").is_some_and(|name| !name.is_empty()))
+	});
+	if functions.len() <= 1 && !has_named_sets {
+		if snapping.is_some() {
+			warnings.push(ImportWarning::new(location, "Snapping is only supported on channels with named ranges. The channel will blend normally."));
+		}
+		return Channel {
+			intensity_emulation: None,
+			size,
+			default,
+			ch_type: ChannelType::Linear { priority },
+			physical: functions.first().and_then(|function| physical_range(function.node, attribute, None)),
+		};
+	}
+
+	// Build segments from the channel functions and their channel sets
+	let mut segments = Vec::new();
+	let mut physical_ranges = Vec::new();
+	for (index, function) in functions.iter().enumerate() {
+		let function_end = functions
+			.get(index + 1)
+			.map_or(max_value, |next| next.start.saturating_sub(1).max(function.start));
+		let function_name = function
+			.node
+			.attribute("Name")
+			.or(function.node.attribute("Attribute"))
+			.unwrap_or("")
+			.to_owned();
+		physical_ranges.extend(physical_range(function.node, attribute, Some((function.start, function_end))));
+		let wheel = function.node.attribute("Wheel").and_then(|wheel_name| wheels.get(wheel_name));
+		let is_gobo = function.node.attribute("Attribute").unwrap_or(attribute).starts_with("Gobo");
+
+		let mut sets: Vec<(u16, Node)> = children(function.node, "ChannelSet")
+			.map(|set| {
+				(
+					set.attribute("DMXFrom").and_then(|value| parse_dmx_value(value, size_bytes)).unwrap_or(function.start),
+					set,
+				)
+			})
+			.collect();
+		sets.sort_by_key(|(start, _)| *start);
+
+		if sets.is_empty() {
+			segments.push(Segment {
+				start: function.start,
+				end: function_end,
+				name: function_name,
+				display: SegmentDisplay::Other,
+			});
+			continue;
+		}
+
+		for (set_index, (start, set)) in sets.iter().enumerate() {
+			let end = sets
+				.get(set_index + 1)
+				.map_or(function_end, |(next_start, _)| next_start.saturating_sub(1).max(*start));
+			let name = set.attribute("Name").filter(|name| !name.is_empty()).map_or_else(|| function_name.clone(), String::from);
+			let slot = set
+				.attribute("WheelSlotIndex")
+				.and_then(|index| index.parse::<usize>().ok())
+				.and_then(|index| wheel.and_then(|slots| slots.get(index.checked_sub(1)?)));
+			let display = match slot {
+				Some(WheelSlot { media: Some(asset), .. }) if is_gobo => SegmentDisplay::Gobo { asset: asset.clone() },
+				Some(WheelSlot { media: Some(asset), .. }) => SegmentDisplay::Image { asset: asset.clone() },
+				Some(WheelSlot { color: Some((red, green, blue)), .. }) => SegmentDisplay::Color { red: *red, green: *green, blue: *blue },
+				_ => SegmentDisplay::Other,
+			};
+			segments.push(Segment {
+				start: *start,
+				end,
+				name,
+				display,
+			});
+		}
+	}
+
+	// A physical range is only kept if it's unambiguous, such as the strobe function of a shutter channel
+	let physical = if physical_ranges.len() == 1 { physical_ranges.pop() } else { None };
+
+	return Channel {
+		intensity_emulation: None,
+		size,
+		default,
+		ch_type: ChannelType::Segmented {
+			segments,
+			priority,
+			snapping,
+		},
+		physical,
+	};
+}
+
+/// Reads the physical range of a channel function, for attributes with a known unit
+fn physical_range(function: Node, attribute: &str, raw_range: Option<(u16, u16)>) -> Option<PhysicalRange> {
+	let unit = match function.attribute("Attribute").unwrap_or(attribute) {
+		"Pan" | "Tilt" | "Zoom" => PhysicalUnit::Degrees,
+		"CTO" | "CTC" | "CTB" => PhysicalUnit::Kelvin,
+		"StrobeFrequency" => PhysicalUnit::Hertz,
+		function_attribute if function_attribute.starts_with("Shutter") && function_attribute.ends_with("Strobe") => {
+			PhysicalUnit::Hertz
+		}
+		_ => return None,
+	};
+	let start = function.attribute("PhysicalFrom")?.parse::<f32>().ok()?;
+	let end = function.attribute("PhysicalTo")?.parse::<f32>().ok()?;
+	return Some(PhysicalRange {
+		unit,
+		start,
+		end,
+		raw_range,
+		curve: CurveShape::Linear,
+	});
+}
+
+/// Builds control groups from the channels of each geometry.
+///
+/// Groups are only named after their geometry when the same kind of control appears in several
+/// geometries, such as the cells of a multi-cell fixture.
+fn build_control_groups(
+	channel_sources: &HashMap<String, (String, String)>,
+	warnings: &mut Vec<ImportWarning>,
+) -> Vec<ControlGroup> {
+	// Geometry --> Attribute --> Channel ID
+	let mut geometries: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+	for (channel_id, (geometry, attribute)) in channel_sources.iter() {
+		geometries
+			.entry(geometry.as_str())
+			.or_default()
+			.insert(attribute.as_str(), channel_id.as_str());
+	}
+	let mut geometry_names: Vec<&str> = geometries.keys().cloned().collect();
+	geometry_names.sort();
+
+	// (Kind, Geometry, Data)
+	let mut standard_groups: Vec<(&str, &str, ControlGroupData)> = Vec::new();
+
+	// Pan and tilt usually live on different geometries (yoke and head), so pair them up first
+	let find_all = |geometries: &HashMap<&str, HashMap<&str, &str>>, attribute: &str| -> Vec<&str> {
+		return geometry_names
+			.iter()
+			.filter(|geometry| geometries[*geometry].contains_key(attribute))
+			.cloned()
+			.collect();
+	};
+	let (pan_geometries, tilt_geometries) = (find_all(&geometries, "Pan"), find_all(&geometries, "Tilt"));
+	if let ([pan_geometry], [tilt_geometry]) = (pan_geometries.as_slice(), tilt_geometries.as_slice()) {
+		let pan = geometries.get_mut(pan_geometry).and_then(|attributes| attributes.remove("Pan"));
+		let tilt = geometries.get_mut(tilt_geometry).and_then(|attributes| attributes.remove("Tilt"));
+		if let (Some(pan), Some(tilt)) = (pan, tilt) {
+			standard_groups.push(("PanTilt", pan_geometry, ControlGroupData::PanTilt { pan: pan.to_owned(), tilt: tilt.to_owned() }));
+		}
+	}
+
+	// (Attribute, Geometry, Channel ID)
+	let mut generic_inputs: Vec<(&str, &str, &str)> = Vec::new();
+	for geometry_name in geometry_names.iter().cloned() {
+		let mut attributes = geometries[geometry_name].clone();
+		let mut take = |attribute_names: &[&'static str]| -> Option<String> {
+			return attribute_names
+				.iter()
+				.find_map(|attribute| attributes.remove(attribute))
+				.map(String::from);
+		};
+
+		if let Some(channel_id) = take(&["Dimmer"]) {
+			standard_groups.push(("Intensity", geometry_name, ControlGroupData::Intensity(channel_id)));
+		}
+		if let (Some(red), Some(green), Some(blue)) = (
+			take(&["ColorAdd_R", "ColorRGB_Red"]),
+			take(&["ColorAdd_G", "ColorRGB_Green"]),
+			take(&["ColorAdd_B", "ColorRGB_Blue"]),
+		) {
+			standard_groups.push(("RGB", geometry_name, ControlGroupData::RGBGroup { red, green, blue }));
+		}
+		if let (Some(pan), Some(tilt)) = (take(&["Pan"]), take(&["Tilt"])) {
+			standard_groups.push(("PanTilt", geometry_name, ControlGroupData::PanTilt { pan, tilt }));
+		}
+		for gobo in ["Gobo1", "Gobo2", "Gobo3"] {
+			if let Some(channel_id) = take(&[gobo]) {
+				standard_groups.push((gobo, geometry_name, ControlGroupData::Gobo(channel_id)));
+			}
+		}
+		for color_wheel in ["Color1", "Color2", "Color3"] {
+			if let Some(channel_id) = take(&[color_wheel]) {
+				standard_groups.push((color_wheel, geometry_name, ControlGroupData::ColorWheel(channel_id)));
+			}
+		}
+		if let Some(channel_id) = take(&["Zoom"]) {
+			standard_groups.push(("Zoom", geometry_name, ControlGroupData::Zoom(channel_id)));
+		}
+		if ["ColorSub_C", "ColorSub_M", "ColorSub_Y"].iter().any(|attribute| attributes.contains_key(attribute)) {
+			warnings.push(ImportWarning::new(
+				geometry_name,
+				"CMY color mixing has no black channel, so it can't use a CMYK control. The channels will use generic controls.",
+			));
+		}
+
+		// Everything else gets a generic control
+		let mut remaining: Vec<(&str, &str)> = attributes.into_iter().collect();
+		remaining.sort();
+		generic_inputs.extend(remaining.into_iter().map(|(attribute, channel_id)| (attribute, geometry_name, channel_id)));
+	}
+
+	let mut control_groups = Vec::new();
+	for (kind, geometry_name, data) in standard_groups.iter() {
+		let shared = standard_groups.iter().filter(|(other_kind, ..)| other_kind == kind).count() > 1;
+		control_groups.push(ControlGroup::new(
+			if shared { Some(geometry_name.to_string()) } else { None },
+			data.clone(),
+		));
+	}
+	for (attribute, geometry_name, channel_id) in generic_inputs.iter() {
+		let shared = generic_inputs.iter().filter(|(other_attribute, ..)| other_attribute == attribute).count() > 1;
+		control_groups.push(ControlGroup::new(
+			Some(if shared { format!("{} {}", geometry_name, attribute) } else { attribute.to_string() }),
+			ControlGroupData::GenericInput(channel_id.to_string()),
+		));
+	}
+	return control_groups;
+}
+
+/// Converts a GDTF color (CIE 1931 xyY, as `x,y,Y`) to an sRGB color for display
+fn cie_to_rgb(color: &str) -> Option<(u8, u8, u8)> {
+	let components: Vec<f64> = color.split(',').filter_map(|component| component.trim().parse().ok()).collect();
+	if components.len() < 2 || components[1] <= 0.0 {
+		return None;
+	}
+	let (x, y) = (components[0], components[1]);
+
+	// Use full luminance, since only the hue is displayed
+	let big_x = x / y;
+	let big_z = (1.0 - x - y) / y;
+	let linear = [
+		3.2406 * big_x - 1.5372 - 0.4986 * big_z,
+		-0.9689 * big_x + 1.8758 + 0.0415 * big_z,
+		0.0557 * big_x - 0.2040 + 1.0570 * big_z,
+	];
+	let brightest = linear.iter().cloned().fold(0.0, f64::max);
+	if brightest <= 0.0 {
+		return Some((0, 0, 0));
+	}
+	let [red, green, blue] = linear.map(|component| {
+		let component = (component / brightest).max(0.0);
+		let gamma_corrected = if component <= 0.0031308 {
+			12.92 * component
+		} else {
+			1.055 * component.powf(1.0 / 2.4) - 0.055
+		};
+		(gamma_corrected * 255.0).round() as u8
+	});
+	return Some((red, green, blue));
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::plugins::patcher::driver_plugin_api::validate_fixture_info;
+	use std::io::Write;
+
+	/// Builds a GDTF archive with a single mode containing `dmx_channels`
+	fn archive(dmx_channels: &[String]) -> Vec<u8> {
+		let description = format!(
+			r#"<?xml version="1.0" encoding="UTF-8"?>
+<GDTF DataVersion="1.1">
+	<FixtureType Name="Spot" LongName="Test Spot" Manufacturer="Acme" FixtureTypeID="8c3b4a7e-2f1d-4e6a-9b5c-0d1e2f3a4b5c">
+		<DMXModes>
+			<DMXMode Name="Standard" Geometry="Body">
+				<DMXChannels>{}</DMXChannels>
+			</DMXMode>
+		</DMXModes>
+	</FixtureType>
+</GDTF>"#,
+			dmx_channels.concat()
+		);
+		let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+		let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+		writer.start_file("description.xml", options).unwrap();
+		writer.write_all(description.as_bytes()).unwrap();
+		return writer.finish().unwrap().into_inner();
+	}
+
+	fn dmx_channel(offset: &str, attribute: &str) -> String {
+		return format!(
+			r#"<DMXChannel DMXBreak="1" Offset="{offset}" Geometry="Body">
+				<LogicalChannel Attribute="{attribute}">
+					<ChannelFunction Name="{attribute}" Attribute="{attribute}" DMXFrom="0/1" Default="0/1"/>
+				</LogicalChannel>
+			</DMXChannel>"#
+		);
+	}
+
+	fn convert_channels(dmx_channels: &[String]) -> (FixtureInfo, DMXFixtureData, Vec<ImportWarning>) {
+		let converted = convert(&archive(dmx_channels)).ok().unwrap();
+		let dmx_data: DMXFixtureData = converted.bundle.output_info.deserialize().ok().unwrap();
+		return (converted.bundle.fixture_info, dmx_data, converted.warnings);
+	}
+
+	#[test]
+	fn dmx_values() {
+		assert_eq!(parse_dmx_value("128", 1), Some(128));
+		assert_eq!(parse_dmx_value("128/1", 2), Some(32768));
+		assert_eq!(parse_dmx_value("32768/2", 1), Some(128));
+		assert_eq!(parse_dmx_value("16777215/3", 2), Some(65535));
+		assert_eq!(parse_dmx_value("70000/2", 2), Some(65535));
+		assert_eq!(parse_dmx_value("1/0", 1), None);
+		assert_eq!(parse_dmx_value("1/5", 1), None);
+		assert_eq!(parse_dmx_value("full", 1), None);
+	}
+
+	#[test]
+	fn non_adjacent_fine_channel_and_gaps() {
+		let (fixture_info, dmx_data, warnings) = convert_channels(&[
+			dmx_channel("1", "Dimmer"),
+			dmx_channel("2,4", "Pan"),
+			dmx_channel("6", "Tilt"),
+		]);
+		assert!(validate_fixture_info(&fixture_info).is_empty());
+		assert!(dmx_data.validate(&fixture_info).is_empty());
+		assert!(!warnings.is_empty());
+
+		let personality = &dmx_data.personalities["Standard"];
+		assert_eq!(
+			personality.dmx_channel_order,
+			vec!["Body_Dimmer", RESERVED_CHANNEL, RESERVED_CHANNEL, "Body_Tilt"],
+		);
+		let pan = &personality.channel_mappings["Body_Pan"];
+		assert_eq!((pan.coarse, pan.fine, pan.ultra), (1, Some(3), None));
+
+		let slots: Vec<(String, Vec<u16>)> = personality
+			.layout(&fixture_info)
+			.unwrap()
+			.into_iter()
+			.map(|channel| (channel.channel_id, channel.slots))
+			.collect();
+		for (channel_id, expected_slots) in [("Body_Dimmer", vec![0]), ("Body_Pan", vec![1, 3]), ("Body_Tilt", vec![5])] {
+			assert!(slots.contains(&(channel_id.to_owned(), expected_slots)));
+		}
+	}
+
+	#[test]
+	fn three_byte_channel() {
+		let (fixture_info, dmx_data, _) = convert_channels(&[dmx_channel("1,2,3", "Dimmer")]);
+		assert!(dmx_data.validate(&fixture_info).is_empty());
+		let dimmer = &dmx_data.personalities["Standard"].channel_mappings["Body_Dimmer"];
+		assert_eq!((dimmer.coarse, dimmer.fine, dimmer.ultra), (0, Some(1), Some(2)));
+		assert!(matches!(fixture_info.channels["Body_Dimmer"].size, ChannelSize::U16));
+	}
+
+	#[test]
+	fn offsets_outside_universe() {
+		for offset in ["0", "513", "1,65536"] {
+			let result = convert(&archive(&[dmx_channel(offset, "Dimmer")]));
+			assert!(matches!(result, Err(GdtfImportError::InvalidOffset(..))), "offset {offset} was accepted");
+		}
+
+		let (fixture_info, dmx_data, _) = convert_channels(&[dmx_channel("512", "Dimmer")]);
+		assert!(dmx_data.validate(&fixture_info).is_empty());
+	}
+}
@@ -0,0 +1,4 @@
+//! Converters from third-party fixture definition formats to fixture bundles for the DMX driver
+
+pub mod gdtf;
+pub mod ofl;
@@ -0,0 +1,239 @@
+//! Lays out Open Fixture Library (OFL) modes as DMX personalities.
+//!
+//! The driver-independent parts of a definition, such as channels and their capabilities, are
+//! mapped by the patcher's OFL importer. This module places each mode's channels in DMX slots,
+//! resolving fine channels into 16-bit channels and explicit slot mappings.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::{
+	plugins::{
+		output_dmx::{
+			fixture_types::{DMXByteOrder, DMXChannelMapping, DMXFixtureData, DMXPersonalityData},
+			DMX_DRIVER_ID,
+		},
+		patcher::{
+			driver_plugin_api::{ChannelSize, FixtureBundle},
+			importers::{
+				ofl::{self, OflDefinition, OflImportError, OflMode},
+				ConvertedFixture, ImportWarning,
+			},
+		},
+	},
+	utilities::serialized_data::SerializedData,
+};
+
+/// A DMX slot in a mode, before channel IDs are assigned
+enum ModeSlot {
+	Channel(String, ChannelSize),
+
+	/// A 16-bit channel whose bytes are placed explicitly, since its fine channels don't directly follow it
+	Mapped(String, DMXChannelMapping),
+	Reserved,
+}
+
+/// Converts an OFL fixture definition into a fixture bundle for the DMX output driver.
+///
+/// OFL stores the manufacturer outside of the fixture definition, so it can be provided separately.
+pub fn convert(definition: &Value, manufacturer: Option<String>) -> Result<ConvertedFixture, OflImportError> {
+	let definition = OflDefinition::parse(definition)?;
+	let mut warnings = Vec::new();
+
+	// Fine channel alias --> (Coarse channel, Byte index starting at 1)
+	let mut fine_aliases: HashMap<&str, (&str, usize)> = HashMap::new();
+	for (channel_key, channel) in definition.channels() {
+		let aliases = channel.get("fineChannelAliases").and_then(Value::as_array);
+		for (index, alias) in aliases.into_iter().flatten().filter_map(Value::as_str).enumerate() {
+			fine_aliases.insert(alias, (channel_key.as_str(), index + 1));
+		}
+	}
+
+	// Lay out each mode's DMX slots
+	let mut mode_layouts: Vec<(String, Vec<ModeSlot>)> = Vec::new();
+	for (mode_index, mode) in definition.modes.iter().enumerate() {
+		let mode_name = mode
+			.get("name")
+			.and_then(Value::as_str)
+			.map_or_else(|| format!("Mode {}", mode_index + 1), String::from);
+		let mode_channels = mode.get("channels").and_then(Value::as_array).cloned().unwrap_or_default();
+
+		if mode_channels.iter().any(Value::is_object) {
+			warnings.push(ImportWarning::new(&mode_name, "Matrix channel inserts are not supported. The mode was skipped."));
+			continue;
+		}
+		let mode_channels: Vec<Option<&str>> = mode_channels.iter().map(Value::as_str).collect();
+
+		// Channels with fine channels elsewhere in the mode, or with 24-bit resolution, are placed with an explicit
+		// slot mapping. Ordered channels fill the remaining slots.
+		let mut mappings: HashMap<usize, DMXChannelMapping> = HashMap::new();
+		let mut mapped_positions: Vec<usize> = Vec::new();
+		for (position, channel_key) in mode_channels.iter().enumerate() {
+			let channel_key = match channel_key {
+				Some(channel_key) if definition.channel(channel_key).is_some() => *channel_key,
+				_ => continue,
+			};
+			let find_fine = |byte: usize| {
+				mode_channels
+					.iter()
+					.position(|alias| alias.and_then(|alias| fine_aliases.get(alias)) == Some(&(channel_key, byte)))
+			};
+			if let (Some(fine), ultra) = (find_fine(1), find_fine(2)) {
+				if fine != position + 1 || ultra.is_some() {
+					mappings.insert(position, DMXChannelMapping {
+						coarse: position as u16,
+						fine: Some(fine as u16),
+						ultra: ultra.map(|ultra| ultra as u16),
+					});
+					mapped_positions.push(fine);
+					mapped_positions.extend(ultra);
+				}
+			}
+		}
+
+		let mut slots = Vec::new();
+		let mut position = 0;
+		while position < mode_channels.len() {
+			let location = format!("{} / slot {}", mode_name, position + 1);
+			if mapped_positions.contains(&position) {
+				position += 1;
+				continue;
+			}
+			let channel_key = match mode_channels[position] {
+				Some(channel_key) if definition.channel(channel_key).is_some() => channel_key,
+				Some(channel_key) if fine_aliases.contains_key(channel_key) => {
+					warnings.push(ImportWarning::new(&location, format!("Fine channel {:?} has no coarse channel in this mode. It will output 0.", channel_key)));
+					slots.push(ModeSlot::Reserved);
+					position += 1;
+					continue;
+				}
+				Some(channel_key) => {
+					warnings.push(ImportWarning::new(&location, format!("Channel {:?} is not supported. Switching channels and template channels can't be imported. It will output 0.", channel_key)));
+					slots.push(ModeSlot::Reserved);
+					position += 1;
+					continue;
+				}
+				None => {
+					slots.push(ModeSlot::Reserved);
+					position += 1;
+					continue;
+				}
+			};
+
+			if let Some(mapping) = mappings.remove(&position) {
+				slots.push(ModeSlot::Mapped(channel_key.to_owned(), mapping));
+				position += 1;
+				continue;
+			}
+
+			// Fine channels that directly follow their coarse channel are output in order
+			let is_fine_of = |slot: usize, byte: usize| {
+				slot < mode_channels.len()
+					&& mode_channels[slot].and_then(|alias| fine_aliases.get(alias)) == Some(&(channel_key, byte))
+			};
+			if is_fine_of(position + 1, 1) {
+				slots.push(ModeSlot::Channel(channel_key.to_owned(), ChannelSize::U16));
+				position += 2;
+			} else {
+				slots.push(ModeSlot::Channel(channel_key.to_owned(), ChannelSize::U8));
+				position += 1;
+			}
+		}
+
+		if slots.iter().all(|slot| matches!(slot, ModeSlot::Reserved)) {
+			warnings.push(ImportWarning::new(&mode_name, "The mode has no channels that can be imported. It was skipped."));
+			continue;
+		}
+		mode_layouts.push((mode_name, slots));
+	}
+
+	let modes: Vec<OflMode> = mode_layouts
+		.iter()
+		.map(|(mode_name, slots)| OflMode {
+			name: mode_name.clone(),
+			channels: slots
+				.iter()
+				.map(|slot| match slot {
+					ModeSlot::Channel(channel_key, size) => Some((channel_key.clone(), size.clone())),
+					ModeSlot::Mapped(channel_key, _) => Some((channel_key.clone(), ChannelSize::U16)),
+					ModeSlot::Reserved => None,
+				})
+				.collect(),
+		})
+		.collect();
+	let (fixture_info, mode_channel_ids) =
+		ofl::convert_fixture_info(&definition, manufacturer, DMX_DRIVER_ID, &modes, &mut warnings)?;
+
+	let mut dmx_personalities = HashMap::new();
+	for ((mode_name, slots), channel_ids) in mode_layouts.into_iter().zip(mode_channel_ids) {
+		let mut dmx_channel_order = Vec::new();
+		let mut channel_mappings = HashMap::new();
+		for (slot, channel_id) in slots.into_iter().zip(channel_ids) {
+			match slot {
+				ModeSlot::Mapped(_, mapping) => {
+					channel_mappings.insert(channel_id, mapping);
+				}
+				_ => dmx_channel_order.push(channel_id),
+			}
+		}
+		dmx_personalities.insert(
+			mode_name,
+			DMXPersonalityData {
+				dmx_channel_order,
+				channel_mappings,
+				byte_order: DMXByteOrder::MsbFirst,
+			},
+		);
+	}
+
+	let output_info = serde_json::to_value(DMXFixtureData {
+		personalities: dmx_personalities,
+	})
+	.map_err(|error| OflImportError::ImportFailed(error.to_string()))?;
+
+	return Ok(ConvertedFixture {
+		bundle: FixtureBundle {
+			fixture_info,
+			output_info: SerializedData::JSON(output_info),
+		},
+		warnings,
+	});
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::plugins::patcher::driver_plugin_api::validate_fixture_info;
+	use serde_json::json;
+
+	#[test]
+	fn mode_with_three_byte_channel() {
+		let definition = json!({
+			"name": "Spot",
+			"availableChannels": {
+				"Dimmer": { "fineChannelAliases": ["Dimmer fine", "Dimmer fine^2"], "capability": { "type": "Intensity" } },
+				"Pan": { "fineChannelAliases": ["Pan fine"], "capability": { "type": "Pan" } },
+				"Tilt": { "fineChannelAliases": ["Tilt fine"], "capability": { "type": "Tilt" } },
+			},
+			"modes": [
+				{ "name": "Extended", "channels": ["Dimmer", "Dimmer fine", "Dimmer fine^2", "Pan", "Tilt", "Pan fine", "Tilt fine", null] },
+			],
+		});
+		let converted = convert(&definition, Some("Acme".into())).ok().unwrap();
+		let fixture_info = converted.bundle.fixture_info;
+		let dmx_data: DMXFixtureData = converted.bundle.output_info.deserialize().ok().unwrap();
+		assert!(validate_fixture_info(&fixture_info).is_empty());
+		assert!(dmx_data.validate(&fixture_info).is_empty());
+		assert!(matches!(fixture_info.channels["Dimmer"].size, ChannelSize::U16));
+
+		let personality = &dmx_data.personalities["Extended"];
+		let dimmer = &personality.channel_mappings["Dimmer"];
+		assert_eq!((dimmer.coarse, dimmer.fine, dimmer.ultra), (0, Some(1), Some(2)));
+		let pan = &personality.channel_mappings["Pan"];
+		assert_eq!((pan.coarse, pan.fine, pan.ultra), (3, Some(5), None));
+		let tilt = &personality.channel_mappings["Tilt"];
+		assert_eq!((tilt.coarse, tilt.fine, tilt.ultra), (4, Some(6), None));
+		assert_eq!(personality.dmx_channel_order, vec!["_reserved"]);
+	}
+}
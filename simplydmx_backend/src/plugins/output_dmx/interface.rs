@@ -1,11 +1,18 @@
 use super::{
+	builder::{FixtureBuilderError, FixtureBuilderForm, FixtureDraft, FixtureDraftEdit},
 	driver_types::{DMXDriver, DMXFrame, RegisterUniverseError},
-	fixture_types::DMXFixtureData,
-	state::{DMXFixtureInstance, DMXState, UniverseInstance},
+	fixture_types::{DMXChannelLayout, DMXFixtureData},
+	patch_report::{self, PatchIssue},
+	patch_sheet::{self, PatchImportReport, PatchSheetError},
+	state::{find_free_offset, DMXFixtureInstance, DMXState, UniverseInstance},
+	DMX_DRIVER_ID,
 };
 use crate::{
 	mixer_utils::state::{FixtureMixerOutput, FullMixerOutput},
-	plugins::{patcher::driver_plugin_api::*, saver::Savable},
+	plugins::{
+		patcher::{driver_plugin_api::*, CreateFixtureError, PatcherInterface},
+		saver::Savable,
+	},
 	utilities::{
 		forms::{InteractiveDescription, NumberValidation},
 		serialized_data::SerializedData,
@@ -15,6 +22,7 @@ use anyhow::{anyhow, Context};
 use async_trait::async_trait;
 use simplydmx_plugin_framework::*;
 use std::{collections::HashMap, sync::Arc};
+use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinSet};
 use uuid::Uuid;
 
@@ -31,6 +39,16 @@ impl DMXInterface {
 		);
 	}
 
+	/// Works out the layout of every fixture type in the show. Layouts aren't saved with the show, so this
+	/// needs to run once the show is loaded.
+	pub async fn cache_all_layouts(&self, patcher: &PatcherInterface) {
+		let patcher_state = patcher.get_sharable_state().await;
+		let mut ctx = self.1.write().await;
+		for fixture_type_info in patcher_state.library.values() {
+			ctx.cache_layouts(fixture_type_info);
+		}
+	}
+
 	/// Registers an DMX driver for sending universe frames
 	pub async fn register_dmx_driver<T: DMXDriver>(&self, plugin: T) {
 		let mut ctx = self.1.write().await;
@@ -224,6 +242,422 @@ impl DMXInterface {
 			.collect();
 	}
 
+	/// Gets the number of DMX channels a fixture type occupies in the given personality
+	pub async fn get_footprint_size(
+		&self,
+		patcher: &PatcherInterface,
+		fixture_type: &Uuid,
+		personality: &str,
+	) -> Option<u16> {
+		let patcher_state = patcher.get_sharable_state().await;
+		let ctx = self.1.read().await;
+		let fixture_type_info = patcher_state.library.get(fixture_type)?;
+		return ctx.get_personality_size(fixture_type_info, personality).ok();
+	}
+
+	/// Finds the first offset at or after `start_offset` in a universe where `size` channels are free
+	pub async fn find_free_range(
+		&self,
+		patcher: &PatcherInterface,
+		universe_id: &Uuid,
+		size: u16,
+		start_offset: Option<u16>,
+	) -> Option<u16> {
+		let patcher_state = patcher.get_sharable_state().await;
+		let ctx = self.1.read().await;
+		if !ctx.universes.contains_key(universe_id) {
+			return None;
+		}
+		let used = ctx.get_used_ranges(&patcher_state, universe_id, None);
+		return find_free_offset(&used, size, start_offset.unwrap_or(1));
+	}
+
+	/// Creates several instances of a fixture type, addressing them automatically.
+	///
+	/// If any instance can't be created, the instances created so far are deleted.
+	pub async fn bulk_patch(
+		&self,
+		patcher: &PatcherInterface,
+		options: BulkPatchOptions,
+	) -> Result<Vec<Uuid>, BulkPatchError> {
+		let addresses = self.plan_bulk_patch(patcher, &options).await?;
+
+		let mut created = Vec::new();
+		for (index, (universe, offset)) in addresses.into_iter().enumerate() {
+			let name = options
+				.name
+				.as_ref()
+				.map(|name| format!("{} {}", name, options.start_number as usize + index));
+			let form_data = SerializedData::JSON(serde_json::json!(DMXFixtureInstance {
+				universe: Some(universe),
+				offset: Some(offset),
+			}));
+			match patcher
+				.create_fixture(options.fixture_type, options.personality.clone(), name, None, form_data)
+				.await
+			{
+				Ok(instance_id) => created.push(instance_id),
+				Err(error) => {
+					for instance_id in created.iter() {
+						patcher.delete_fixture(instance_id).await.ok();
+					}
+					return Err(BulkPatchError::CreateFailed(error));
+				}
+			}
+		}
+
+		return Ok(created);
+	}
+
+	/// Works out the universe and offset of each instance in a bulk patch without modifying the show
+	async fn plan_bulk_patch(
+		&self,
+		patcher: &PatcherInterface,
+		options: &BulkPatchOptions,
+	) -> Result<Vec<(Uuid, u16)>, BulkPatchError> {
+		let patcher_state = patcher.get_sharable_state().await;
+		let ctx = self.1.read().await;
+
+		let fixture_type_info = patcher_state
+			.library
+			.get(&options.fixture_type)
+			.ok_or(BulkPatchError::FixtureTypeMissing)?;
+		if !fixture_type_info.personalities.contains_key(&options.personality) {
+			return Err(BulkPatchError::PersonalityMissing);
+		}
+		let size = ctx
+			.get_personality_size(fixture_type_info, &options.personality)
+			.map_err(|_| BulkPatchError::PersonalityMissing)?;
+
+		if let Some(start_offset) = options.start_offset {
+			if !(1..=512).contains(&start_offset) {
+				return Err(BulkPatchError::InvalidOffset);
+			}
+		}
+
+		// Universes to fill, in display order starting at the requested universe
+		let start_index = ctx
+			.universe_display_order
+			.iter()
+			.position(|universe_id| *universe_id == options.universe)
+			.ok_or(BulkPatchError::UniverseMissing)?;
+		let universes: Vec<Uuid> = if options.wrap {
+			ctx.universe_display_order[start_index..].to_vec()
+		} else {
+			vec![options.universe]
+		};
+
+		let mut universe_index = 0;
+		let mut used = ctx.get_used_ranges(&patcher_state, &universes[0], None);
+		let mut next_offset = options.start_offset.unwrap_or(1) as u32;
+		let mut addresses = Vec::with_capacity(options.count as usize);
+		while addresses.len() < options.count as usize {
+			let free_offset = if next_offset <= 512 {
+				find_free_offset(&used, size, next_offset as u16)
+			} else {
+				None
+			};
+			match free_offset {
+				Some(offset) => {
+					let end = offset + size.max(1) - 1;
+					used.push((offset, end));
+					addresses.push((universes[universe_index], offset));
+					next_offset = end as u32 + 1 + options.gap as u32;
+				}
+				None => {
+					universe_index += 1;
+					if universe_index >= universes.len() {
+						return Err(BulkPatchError::OutOfSpace);
+					}
+					used = ctx.get_used_ranges(&patcher_state, &universes[universe_index], None);
+					next_offset = 1;
+				}
+			}
+		}
+
+		return Ok(addresses);
+	}
+
+	/// Checks the show's patch for problems, such as overlapping fixtures or universes without a controller
+	pub async fn get_patch_report(&self, patcher: &PatcherInterface) -> Vec<PatchIssue> {
+		let patcher_state = patcher.get_sharable_state().await;
+		let ctx = self.1.read().await;
+		return patch_report::generate(&patcher_state, &ctx);
+	}
+
+	/// Exports the show's patch as a CSV patch sheet
+	pub async fn export_patch_sheet(&self, patcher: &PatcherInterface) -> Result<String, PatchSheetError> {
+		let patcher_state = patcher.get_sharable_state().await;
+		let ctx = self.1.read().await;
+		return patch_sheet::export(&patcher_state, &ctx);
+	}
+
+	/// Imports a CSV patch sheet, creating or updating fixture instances.
+	///
+	/// Rows that can't be applied are reported as conflicts without affecting the rest of the sheet.
+	pub async fn import_patch_sheet(
+		&self,
+		patcher: &PatcherInterface,
+		sheet: String,
+	) -> Result<PatchImportReport, PatchSheetError> {
+		let sheet = patch_sheet::parse(&sheet)?;
+
+		let mut report = PatchImportReport {
+			created: Vec::new(),
+			updated: Vec::new(),
+			conflicts: sheet.conflicts,
+		};
+
+		// Match rows against the show before applying them, since applying them needs the patcher's write lock
+		let resolved_rows: Vec<_> = {
+			let patcher_state = patcher.get_sharable_state().await;
+			let ctx = self.1.read().await;
+			sheet
+				.rows
+				.into_iter()
+				.filter_map(|(row_number, row)| {
+					match patch_sheet::resolve_row(&patcher_state, &ctx, row_number, row) {
+						Ok(resolved_row) => Some(resolved_row),
+						Err(conflict) => {
+							report.conflicts.push(conflict);
+							None
+						}
+					}
+				})
+				.collect()
+		};
+
+		for resolved_row in resolved_rows {
+			let form_data = SerializedData::JSON(serde_json::json!(DMXFixtureInstance {
+				universe: resolved_row.universe,
+				offset: resolved_row.offset,
+			}));
+			let result = match resolved_row.existing_instance {
+				Some(instance_id) => {
+					let previous_number = patcher
+						.get_sharable_state()
+						.await
+						.fixtures
+						.get(&instance_id)
+						.and_then(|instance| instance.number);
+					let renumbered = match resolved_row.number {
+						Some(number) => patcher
+							.renumber_fixture(&instance_id, Some(number))
+							.await
+							.map_err(|error| error.to_string()),
+						None => Ok(()),
+					};
+					match renumbered {
+						Ok(()) => match patcher
+							.edit_fixture(
+								&instance_id,
+								resolved_row.personality,
+								resolved_row.name,
+								resolved_row.comments,
+								form_data,
+							)
+							.await
+						{
+							Ok(_) => Ok(instance_id),
+							Err(error) => {
+								// A row that fails shouldn't change anything, so the fixture gets its old number back
+								if resolved_row.number.is_some() {
+									patcher.renumber_fixture(&instance_id, previous_number).await.ok();
+								}
+								Err(error.to_string())
+							}
+						},
+						Err(message) => Err(message),
+					}
+				}
+				None => match patcher
+					.create_fixture(
+						resolved_row.fixture_type,
+						resolved_row.personality,
+						resolved_row.name,
+						resolved_row.comments,
+						form_data,
+					)
+					.await
+				{
+					Ok(instance_id) => match resolved_row.number {
+						Some(number) => match patcher.renumber_fixture(&instance_id, Some(number)).await {
+							Ok(()) => Ok(instance_id),
+							Err(error) => {
+								// Don't leave behind a fixture the user didn't ask for
+								patcher.delete_fixture(&instance_id).await.ok();
+								Err(error.to_string())
+							}
+						},
+						None => Ok(instance_id),
+					},
+					Err(error) => Err(error.to_string()),
+				},
+			};
+			match result {
+				Ok(instance_id) => {
+					if resolved_row.existing_instance.is_some() {
+						report.updated.push(instance_id);
+					} else {
+						report.created.push(instance_id);
+					}
+					patcher
+						.edit_visualization_info(&instance_id, |visualization_info| {
+							if let Some((x, y)) = resolved_row.placement {
+								visualization_info.x = x;
+								visualization_info.y = y;
+							}
+							if resolved_row.position.is_some() {
+								visualization_info.position = resolved_row.position;
+							}
+							if resolved_row.rotation.is_some() {
+								visualization_info.rotation = resolved_row.rotation;
+							}
+							if resolved_row.hang_position.is_some() {
+								visualization_info.hang_position = resolved_row.hang_position;
+							}
+							if resolved_row.layer.is_some() {
+								visualization_info.layer = resolved_row.layer;
+							}
+						})
+						.await;
+				}
+				Err(message) => report.conflicts.push(patch_sheet::PatchImportConflict {
+					row: resolved_row.row,
+					message,
+				}),
+			}
+		}
+
+		return Ok(report);
+	}
+
+	/// Starts a draft of a new fixture type from the details form
+	pub async fn create_fixture_draft(&self, form_data: SerializedData) -> Result<Uuid, FixtureBuilderError> {
+		let draft = FixtureDraft::new(form_data.deserialize()?)?;
+		let mut ctx = self.1.write().await;
+		let draft_id = Uuid::new_v4();
+		ctx.fixture_drafts.insert(draft_id, draft);
+		return Ok(draft_id);
+	}
+
+	/// Starts a draft that edits a fixture type already in the show
+	pub async fn create_fixture_draft_from_type(
+		&self,
+		patcher: &PatcherInterface,
+		fixture_type: &Uuid,
+	) -> Result<Uuid, FixtureBuilderError> {
+		let patcher_state = patcher.get_sharable_state().await;
+		let mut ctx = self.1.write().await;
+		let info = patcher_state
+			.library
+			.get(fixture_type)
+			.ok_or(FixtureBuilderError::FixtureTypeMissing)?;
+		let dmx = ctx.library.get(fixture_type).ok_or(FixtureBuilderError::FixtureTypeMissing)?;
+		let draft = FixtureDraft {
+			info: info.clone(),
+			dmx: dmx.clone(),
+		};
+		let draft_id = Uuid::new_v4();
+		ctx.fixture_drafts.insert(draft_id, draft);
+		return Ok(draft_id);
+	}
+
+	/// Lists the IDs and names of every fixture draft
+	pub async fn list_fixture_drafts(&self) -> Vec<(Uuid, String)> {
+		let ctx = self.1.read().await;
+		let mut drafts: Vec<(Uuid, String)> = ctx
+			.fixture_drafts
+			.iter()
+			.map(|(draft_id, draft)| (*draft_id, draft.info.name.clone()))
+			.collect();
+		drafts.sort_by(|a, b| a.1.cmp(&b.1));
+		return drafts;
+	}
+
+	pub async fn get_fixture_draft(&self, draft_id: &Uuid) -> Option<FixtureDraft> {
+		let ctx = self.1.read().await;
+		return ctx.fixture_drafts.get(draft_id).cloned();
+	}
+
+	/// Gets one of the fixture builder's forms. Without a draft, the details form creates a new draft.
+	pub async fn get_fixture_builder_form(
+		&self,
+		draft_id: Option<&Uuid>,
+		form: &FixtureBuilderForm,
+	) -> Result<FormDescriptor, FixtureBuilderError> {
+		let ctx = self.1.read().await;
+		let draft = match draft_id {
+			Some(draft_id) => Some(ctx.fixture_drafts.get(draft_id).ok_or(FixtureBuilderError::DraftMissing)?),
+			None => None,
+		};
+		return Ok(FixtureDraft::get_form(draft, form));
+	}
+
+	/// Applies a step to a fixture draft, using data submitted from one of the builder's forms
+	pub async fn submit_fixture_builder_form(
+		&self,
+		draft_id: &Uuid,
+		form: &FixtureBuilderForm,
+		form_data: SerializedData,
+	) -> Result<(), FixtureBuilderError> {
+		return self.edit_fixture_draft(draft_id, FixtureDraft::parse_form(form, form_data)?).await;
+	}
+
+	/// Applies a step to a fixture draft. The draft is left unchanged if the step would make it less valid.
+	pub async fn edit_fixture_draft(&self, draft_id: &Uuid, edit: FixtureDraftEdit) -> Result<(), FixtureBuilderError> {
+		let mut ctx = self.1.write().await;
+		let draft = ctx.fixture_drafts.get_mut(draft_id).ok_or(FixtureBuilderError::DraftMissing)?;
+		return draft.apply(edit);
+	}
+
+	/// Lists every problem that would prevent a fixture draft from being imported
+	pub async fn validate_fixture_draft(&self, draft_id: &Uuid) -> Result<Vec<FixtureValidationError>, FixtureBuilderError> {
+		let ctx = self.1.read().await;
+		let draft = ctx.fixture_drafts.get(draft_id).ok_or(FixtureBuilderError::DraftMissing)?;
+		return Ok(draft.validate());
+	}
+
+	/// Imports a finished fixture draft into the show, returning the fixture type's ID.
+	///
+	/// If the draft edits a fixture type already in the show, the existing type is replaced and its version
+	/// is incremented. This fails if any instance of the type can't use the new definition.
+	pub async fn finish_fixture_draft(&self, patcher: &PatcherInterface, draft_id: &Uuid) -> Result<Uuid, FixtureBuilderError> {
+		let mut draft = self
+			.1
+			.read()
+			.await
+			.fixture_drafts
+			.get(draft_id)
+			.cloned()
+			.ok_or(FixtureBuilderError::DraftMissing)?;
+
+		if let Some(existing_type) = patcher.get_sharable_state().await.library.get(&draft.info.id) {
+			draft.info.version = existing_type.version + 1;
+		}
+
+		// The DMX plugin's lock can't be held here, since the patcher calls back into it to import the DMX data and
+		// check the type's instances against it
+		let fixture_type = draft.info.id;
+		patcher
+			.import_fixture(FixtureBundle {
+				fixture_info: draft.info,
+				output_info: SerializedData::JSON(serde_json::json!(draft.dmx)),
+			})
+			.await
+			.map_err(FixtureBuilderError::ImportFailed)?;
+
+		self.1.write().await.fixture_drafts.remove(draft_id);
+		return Ok(fixture_type);
+	}
+
+	/// Deletes a fixture draft without importing it
+	pub async fn discard_fixture_draft(&self, draft_id: &Uuid) -> Result<(), FixtureBuilderError> {
+		let mut ctx = self.1.write().await;
+		ctx.fixture_drafts.remove(draft_id).ok_or(FixtureBuilderError::DraftMissing)?;
+		return Ok(());
+	}
+
 	pub async fn list_drivers(&self) -> Vec<DMXDriverDescription> {
 		let ctx = self.1.write().await;
 		return ctx
@@ -252,6 +686,10 @@ pub struct DMXShowSave {
 	pub library: HashMap<Uuid, DMXFixtureData>,
 	pub fixtures: HashMap<Uuid, DMXFixtureInstance>,
 	pub universes: HashMap<Uuid, UniverseInstance>,
+	#[serde(default)]
+	pub universe_display_order: Vec<Uuid>,
+	#[serde(default)]
+	pub fixture_drafts: HashMap<Uuid, FixtureDraft>,
 }
 
 #[async_trait]
@@ -263,6 +701,8 @@ impl Savable for DMXInterface {
 				library: ctx.library.clone(), // TODO: Minify this first
 				fixtures: ctx.fixtures.clone(),
 				universes: ctx.universes.clone(),
+				universe_display_order: ctx.universe_display_order.clone(),
+				fixture_drafts: ctx.fixture_drafts.clone(),
 			}
 			.serialize_cbor()?,
 		));
@@ -274,7 +714,7 @@ impl Savable for DMXInterface {
 impl OutputDriver for DMXInterface {
 	// Metadata
 	fn get_id(&self) -> String {
-		return "DMX".into();
+		return DMX_DRIVER_ID.into();
 	}
 	fn get_name(&self) -> String {
 		return "DMX".into();
@@ -285,10 +725,24 @@ impl OutputDriver for DMXInterface {
 
 	// Fixture library imports/exports
 
-	/// Takes a `Uuid` and a `DMXFixtureData` instance serialized as `SerializedData`
-	async fn import_fixture(&self, id: &Uuid, data: SerializedData) -> Result<(), ImportError> {
+	/// Takes a fixture definition and a `DMXFixtureData` instance serialized as `SerializedData`
+	async fn import_fixture(
+		&self,
+		patcher_state: &SharablePatcherState,
+		fixture_info: &FixtureInfo,
+		data: SerializedData,
+	) -> Result<(), ImportError> {
+		let fixture_data: DMXFixtureData = data.deserialize()?;
+		let validation_errors = fixture_data.validate(fixture_info);
+		if !validation_errors.is_empty() {
+			return Err(ImportError::InvalidDefinition(validation_errors));
+		}
+
 		let mut ctx = self.1.write().await;
-		ctx.library.insert(id.clone(), data.deserialize()?);
+		ctx.check_replacement(patcher_state, fixture_info, &fixture_data)
+			.map_err(|(instance_id, error)| ImportError::InstanceConflict(instance_id, error.to_string()))?;
+		ctx.library.insert(fixture_info.id.clone(), fixture_data);
+		ctx.cache_layouts(fixture_info);
 		return Ok(());
 	}
 
@@ -355,6 +809,7 @@ impl OutputDriver for DMXInterface {
 		let normalized_fixture = ctx
 			.normalize_fixture(
 				patcher,
+				id,
 				fixture_type_info,
 				personality_id,
 				form.deserialize()?,
@@ -406,14 +861,41 @@ impl OutputDriver for DMXInterface {
 
 	async fn edit_fixture_instance(
 		&self,
-		_patcher_state: &SharablePatcherState,
+		patcher_state: &SharablePatcherState,
 		instance_id: &Uuid,
-		_fixture_type_info: &FixtureInfo,
-		_personality_id: &str,
+		fixture_type_info: &FixtureInfo,
+		personality_id: &str,
 		form: SerializedData,
 	) -> Result<(), EditInstanceError> {
 		let mut ctx = self.1.write().await;
-		ctx.fixtures.insert(instance_id.clone(), form.deserialize()?);
+		let normalized_fixture = ctx
+			.normalize_fixture(
+				patcher_state,
+				instance_id,
+				fixture_type_info,
+				personality_id,
+				form.deserialize()?,
+			)
+			.await?;
+		ctx.fixtures.insert(instance_id.clone(), normalized_fixture);
+		return Ok(());
+	}
+
+	async fn change_personality(
+		&self,
+		patcher_state: &SharablePatcherState,
+		instance_id: &Uuid,
+		fixture_type_info: &FixtureInfo,
+		personality_id: &str,
+	) -> Result<(), EditInstanceError> {
+		let ctx = self.1.read().await;
+		let fixture = ctx
+			.fixtures
+			.get(instance_id)
+			.with_context(|| format!("Could not find fixture {instance_id}"))?
+			.clone();
+		ctx.normalize_fixture(patcher_state, instance_id, fixture_type_info, personality_id, fixture)
+			.await?;
 		return Ok(());
 	}
 
@@ -446,18 +928,16 @@ impl OutputDriver for DMXInterface {
 				fixture_instance_data.universe,
 				fixture_instance_data.offset,
 			) {
-				if let (Some(mut universe_frame), Some(patcher_fixture_type), Some(fixture_type)) = (
+				if let (Some(mut universe_frame), Some(patcher_fixture_type), Some(layout)) = (
 					universes.get_mut(universe_id),
 					patcher_data
 						.library
 						.get(&patcher_fixture_instance.fixture_id),
-					ctx.library.get(&patcher_fixture_instance.fixture_id),
+					ctx.get_layout(&patcher_fixture_instance.fixture_id, &patcher_fixture_instance.personality),
 				) {
 					insert_fixture_data(
-						patcher_fixture_instance,
 						patcher_fixture_type,
-						// fixture_instance_data,
-						fixture_type,
+						layout,
 						&fixture_mixer_data,
 						offset,
 						&mut universe_frame,
@@ -512,44 +992,89 @@ impl OutputDriver for DMXInterface {
 	}
 }
 
-/// Renders a fixture's data into the universe. If fixtures are configured improperly, this function will incur a race condition.
+/// Renders a fixture's data into the universe using its personality's cached layout. If fixtures are configured
+/// improperly, this function will incur a race condition.
 ///
 /// Overlaps should be caught before this point
 fn insert_fixture_data(
-	patcher_fixture_instance: &FixtureInstance,
 	patcher_fixture_type: &FixtureInfo,
-	// fixture_data: &DMXFixtureInstance,
-	fixture_type: &DMXFixtureData,
+	layout: &[DMXChannelLayout],
 	data: &FixtureMixerOutput,
-	mut offset: u16,
+	offset: u16,
 	universe_frame: &mut DMXFrame,
 ) {
-	if let Some(dmx_personality) = fixture_type
-		.personalities
-		.get(&patcher_fixture_instance.personality)
-	{
-		for channel_name in dmx_personality.dmx_channel_order.iter() {
-			if let (Some(channel_info), Some(channel_value)) = (
-				patcher_fixture_type.channels.get(channel_name),
-				data.get(channel_name),
-			) {
-				match channel_info.size {
-					ChannelSize::U8 => {
-						universe_frame[(offset - 1) as usize] = *channel_value as u8;
-						offset += 1;
-					}
-					ChannelSize::U16 => {
-						let current_value_bytes = channel_value.to_be_bytes();
-						universe_frame[(offset - 1) as usize] = current_value_bytes[0];
-						universe_frame[offset as usize] = current_value_bytes[1];
-						offset += 2;
-					}
+	let first_address = match offset.checked_sub(1) {
+		Some(first_address) => first_address,
+		None => return,
+	};
+	for channel_layout in layout {
+		if let (Some(channel_info), Some(channel_value)) = (
+			patcher_fixture_type.channels.get(&channel_layout.channel_id),
+			data.get(&channel_layout.channel_id),
+		) {
+			let bytes = to_dmx_bytes(*channel_value, &channel_info.size, channel_layout.slots.len());
+			for (slot, byte) in channel_layout.slots.iter().zip(bytes) {
+				let address = first_address.checked_add(*slot).and_then(|address| universe_frame.get_mut(address as usize));
+				if let Some(address) = address {
+					*address = byte;
 				}
 			}
 		}
 	}
 }
 
+/// Scales a channel's value to the resolution of `byte_count` DMX slots, returning the most significant byte first
+fn to_dmx_bytes(value: u16, size: &ChannelSize, byte_count: usize) -> impl Iterator<Item = u8> {
+	let max_value = size.max_value() as u64;
+	let max_output = (1u64 << (8 * byte_count)) - 1;
+	let scaled = (value as u64).min(max_value) * max_output / max_value;
+	return (0..byte_count).rev().map(move |byte| (scaled >> (8 * byte)) as u8);
+}
+
+#[portable]
+/// Describes a set of fixture instances to create with [`DMXInterface::bulk_patch`]
+pub struct BulkPatchOptions {
+	pub fixture_type: Uuid,
+	pub personality: String,
+	pub count: u16,
+
+	/// Base name for the instances. Each instance's name is suffixed with its number.
+	pub name: Option<String>,
+
+	/// The number appended to the first instance's name
+	pub start_number: u32,
+
+	/// The universe to start patching in
+	pub universe: Uuid,
+
+	/// The first offset to try. Defaults to the first free address in the universe.
+	pub start_offset: Option<u16>,
+
+	/// The number of empty channels to leave after each instance
+	pub gap: u16,
+
+	/// Whether to continue into the next universes (in display order) when a universe runs out of space
+	pub wrap: bool,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while bulk patching fixtures
+pub enum BulkPatchError {
+	#[error("The requested fixture type does not exist in the library")]
+	FixtureTypeMissing,
+	#[error("The requested personality does not exist")]
+	PersonalityMissing,
+	#[error("The requested universe does not exist")]
+	UniverseMissing,
+	#[error("Offset must be between 1 and 512")]
+	InvalidOffset,
+	#[error("There is not enough free space to patch all of the requested fixtures")]
+	OutOfSpace,
+	#[error("Could not create a fixture instance:\n{0}")]
+	CreateFailed(CreateFixtureError),
+}
+
 #[portable]
 /// An error that could occur while linking a DMX universe to a universe controller
 pub enum LinkUniverseError {
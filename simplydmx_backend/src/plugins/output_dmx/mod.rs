@@ -1,6 +1,10 @@
+pub mod builder;
 pub mod driver_types;
 pub mod fixture_types;
+pub mod importers;
 pub mod interface;
+pub mod patch_report;
+pub mod patch_sheet;
 pub mod services;
 pub mod state;
 
@@ -9,6 +13,10 @@ use super::{mixer::MixerInterface, patcher::PatcherInterface, saver::SaverInterf
 use async_trait::async_trait;
 use simplydmx_plugin_framework::*;
 
+/// The ID the DMX plugin registers itself under as an output driver. Fixture types must use this
+/// driver to be addressed in DMX.
+pub const DMX_DRIVER_ID: &str = "DMX";
+
 pub async fn initialize(
 	plugin_context: PluginContext,
 	saver: SaverInterface,
@@ -29,6 +37,7 @@ pub async fn initialize(
 	patcher_interface
 		.register_output_driver(output_context.clone())
 		.await;
+	output_context.cache_all_layouts(&patcher_interface).await;
 
 	plugin_context
 		.declare_event::<Vec<u8>>(
@@ -111,6 +120,78 @@ pub async fn initialize(
 		.register_service(true, services::RenameUniverse::new(output_context.clone()))
 		.await
 		.unwrap();
+	plugin_context
+		.register_service(true, services::ImportGdtf::new(patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::ImportOflFixture::new(patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::BulkPatchFixtures::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::FindFreeDMXRange::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::GetDMXFootprint::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::ExportPatchCsv::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::ImportPatchCsv::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::GetPatchReport::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::CreateFixtureDraft::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::CreateFixtureDraftFromType::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::ListFixtureDrafts::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::GetFixtureDraft::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::GetFixtureBuilderForm::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::SubmitFixtureBuilderForm::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::EditFixtureDraft::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::ValidateFixtureDraft::new(output_context.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::FinishFixtureDraft::new(output_context.clone(), patcher_interface.clone()))
+		.await
+		.unwrap();
+	plugin_context
+		.register_service(true, services::DiscardFixtureDraft::new(output_context.clone()))
+		.await
+		.unwrap();
 
 	plugin_context
 		.register_service_type_specifier(
@@ -0,0 +1,192 @@
+//! Patch reports: every problem with the show's patch, found in a single pass.
+
+use simplydmx_plugin_framework::*;
+use uuid::Uuid;
+
+use crate::plugins::patcher::driver_plugin_api::SharablePatcherState;
+
+use super::{state::DMXState, DMX_DRIVER_ID};
+
+#[portable]
+/// A problem found in the show's patch
+pub enum PatchIssue {
+	/// Two fixtures occupy some of the same addresses in a universe
+	AddressOverlap {
+		universe: Uuid,
+		fixtures: (Uuid, Uuid),
+
+		/// The first and last overlapping addresses
+		addresses: (u16, u16),
+	},
+
+	/// A fixture's footprint extends past the end of its universe
+	AddressOutOfRange { fixture: Uuid, universe: Uuid, offset: u16 },
+
+	/// A fixture isn't assigned to a universe, so it won't output anything
+	Unpatched { fixture: Uuid },
+
+	/// A fixture is assigned to a universe that doesn't exist
+	UniverseMissing { fixture: Uuid, universe: Uuid },
+
+	/// A universe isn't linked to a controller, so its data isn't sent anywhere
+	UniverseUnlinked { universe: Uuid },
+
+	/// A universe is linked to a controller that isn't loaded
+	ControllerMissing { universe: Uuid, controller: String },
+
+	/// A fixture's type is missing from the show
+	FixtureTypeMissing { fixture: Uuid, fixture_type: Uuid },
+
+	/// A fixture's type is in the show, but the DMX plugin has no data for it
+	DMXFixtureTypeMissing { fixture: Uuid, fixture_type: Uuid },
+
+	/// A fixture's personality is missing from its type
+	PersonalityMissing { fixture: Uuid, personality: String },
+
+	/// A fixture's personality is missing from the DMX plugin's data for its type
+	DMXPersonalityMissing { fixture: Uuid, personality: String },
+
+	/// A fixture's personality doesn't have a valid DMX layout
+	InvalidLayout { fixture: Uuid, message: String },
+
+	/// A DMX fixture has no DMX addressing data
+	DMXInstanceMissing { fixture: Uuid },
+
+	/// The DMX plugin has addressing data for a fixture that doesn't exist
+	OrphanedInstance { fixture: Uuid },
+
+	/// The DMX plugin has data for a fixture type that isn't in the show
+	OrphanedFixtureType { fixture_type: Uuid },
+}
+
+/// Checks the show's patch, listing fixtures in patch order and universes in display order
+pub fn generate(patcher: &SharablePatcherState, dmx: &DMXState) -> Vec<PatchIssue> {
+	let mut issues = Vec::new();
+
+	// Check each fixture's type, personality, and addressing
+	let mut footprints: Vec<(Uuid, Uuid, (u16, u16))> = Vec::new();
+	for fixture_id in patcher.fixture_order.iter() {
+		let fixture = match patcher.fixtures.get(fixture_id) {
+			Some(fixture) => fixture,
+			None => continue,
+		};
+		let dmx_fixture = dmx.fixtures.get(fixture_id);
+
+		let fixture_type_info = match patcher.library.get(&fixture.fixture_id) {
+			Some(fixture_type_info) => fixture_type_info,
+			None => {
+				issues.push(PatchIssue::FixtureTypeMissing { fixture: *fixture_id, fixture_type: fixture.fixture_id });
+				continue;
+			}
+		};
+		if fixture_type_info.output_driver != DMX_DRIVER_ID {
+			continue;
+		}
+		let mut personality_valid = true;
+		if !fixture_type_info.personalities.contains_key(&fixture.personality) {
+			issues.push(PatchIssue::PersonalityMissing { fixture: *fixture_id, personality: fixture.personality.clone() });
+			personality_valid = false;
+		}
+		match dmx.library.get(&fixture.fixture_id) {
+			Some(dmx_fixture_type) => {
+				if !dmx_fixture_type.personalities.contains_key(&fixture.personality) {
+					issues.push(PatchIssue::DMXPersonalityMissing { fixture: *fixture_id, personality: fixture.personality.clone() });
+					personality_valid = false;
+				}
+			}
+			None => {
+				issues.push(PatchIssue::DMXFixtureTypeMissing { fixture: *fixture_id, fixture_type: fixture.fixture_id });
+				personality_valid = false;
+			}
+		}
+
+		let dmx_fixture = match dmx_fixture {
+			Some(dmx_fixture) => dmx_fixture,
+			None => {
+				issues.push(PatchIssue::DMXInstanceMissing { fixture: *fixture_id });
+				continue;
+			}
+		};
+		let (universe, offset) = match (dmx_fixture.universe, dmx_fixture.offset) {
+			(Some(universe), Some(offset)) => (universe, offset),
+			_ => {
+				issues.push(PatchIssue::Unpatched { fixture: *fixture_id });
+				continue;
+			}
+		};
+		if !dmx.universes.contains_key(&universe) {
+			issues.push(PatchIssue::UniverseMissing { fixture: *fixture_id, universe });
+			continue;
+		}
+		if personality_valid {
+			match dmx.get_personality_size(fixture_type_info, &fixture.personality) {
+				Ok(size) => {
+					let end = offset as u32 + size.max(1) as u32 - 1;
+					if offset < 1 || end > 512 {
+						issues.push(PatchIssue::AddressOutOfRange { fixture: *fixture_id, universe, offset });
+					}
+					footprints.push((universe, *fixture_id, (offset, end.min(u16::MAX as u32) as u16)));
+				}
+				Err(error) => issues.push(PatchIssue::InvalidLayout { fixture: *fixture_id, message: error.to_string() }),
+			}
+		}
+	}
+
+	// Check for overlapping fixtures within each universe
+	for universe_id in dmx.universe_display_order.iter() {
+		let mut universe_footprints: Vec<&(Uuid, Uuid, (u16, u16))> = footprints
+			.iter()
+			.filter(|(universe, _, _)| universe == universe_id)
+			.collect();
+		universe_footprints.sort_by_key(|(_, _, range)| *range);
+		for (index, (_, first, first_range)) in universe_footprints.iter().enumerate() {
+			for (_, second, second_range) in universe_footprints[index + 1..].iter() {
+				if second_range.0 > first_range.1 {
+					break;
+				}
+				issues.push(PatchIssue::AddressOverlap {
+					universe: *universe_id,
+					fixtures: (*first, *second),
+					addresses: (second_range.0, first_range.1.min(second_range.1)),
+				});
+			}
+		}
+	}
+
+	// Check each universe's controller
+	for universe_id in dmx.universe_display_order.iter() {
+		let universe = match dmx.universes.get(universe_id) {
+			Some(universe) => universe,
+			None => continue,
+		};
+		match universe.controller {
+			Some(ref controller) => {
+				if !dmx.drivers.contains_key(controller) {
+					issues.push(PatchIssue::ControllerMissing { universe: *universe_id, controller: controller.clone() });
+				}
+			}
+			None => issues.push(PatchIssue::UniverseUnlinked { universe: *universe_id }),
+		}
+	}
+
+	// Check for DMX data that no longer belongs to anything
+	let mut orphaned_instances: Vec<Uuid> = dmx
+		.fixtures
+		.keys()
+		.filter(|fixture_id| !patcher.fixtures.contains_key(fixture_id))
+		.copied()
+		.collect();
+	orphaned_instances.sort();
+	issues.extend(orphaned_instances.into_iter().map(|fixture| PatchIssue::OrphanedInstance { fixture }));
+
+	let mut orphaned_types: Vec<Uuid> = dmx
+		.library
+		.keys()
+		.filter(|fixture_type| !patcher.library.contains_key(fixture_type))
+		.copied()
+		.collect();
+	orphaned_types.sort();
+	issues.extend(orphaned_types.into_iter().map(|fixture_type| PatchIssue::OrphanedFixtureType { fixture_type }));
+
+	return issues;
+}
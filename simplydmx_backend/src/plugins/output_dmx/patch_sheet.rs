@@ -0,0 +1,310 @@
+//! Patch sheets: the show's patch as a CSV file that can be exchanged with other software.
+//!
+//! Each row describes one fixture instance. Universes are identified by name, and fixture types by name
+//! and manufacturer. Rows with an `ID` column matching an existing instance update that instance instead
+//! of creating a new one.
+//!
+//! Name, comment, and placement columns that are left empty keep the fixture's existing values.
+
+use serde::{Deserialize, Serialize};
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::plugins::patcher::{
+	driver_plugin_api::{FixtureInfo, Position, SharablePatcherState},
+	FixtureNumber, StageRotation,
+};
+
+use super::{state::DMXState, DMX_DRIVER_ID};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+/// A single row of a patch sheet
+pub struct PatchSheetRow {
+	#[serde(rename = "Number", default)]
+	pub number: Option<String>,
+	#[serde(rename = "ID", default)]
+	pub id: Option<Uuid>,
+	#[serde(rename = "Name", default)]
+	pub name: Option<String>,
+	#[serde(rename = "Manufacturer", default)]
+	pub manufacturer: Option<String>,
+	#[serde(rename = "Type")]
+	pub fixture_type: String,
+	#[serde(rename = "Personality")]
+	pub personality: String,
+	#[serde(rename = "Universe", default)]
+	pub universe: Option<String>,
+	#[serde(rename = "Address", default)]
+	pub address: Option<u16>,
+	#[serde(rename = "Comments", default)]
+	pub comments: Option<String>,
+	#[serde(rename = "X", default)]
+	pub x: Option<u16>,
+	#[serde(rename = "Y", default)]
+	pub y: Option<u16>,
+	#[serde(rename = "Position X", default)]
+	pub position_x: Option<f32>,
+	#[serde(rename = "Position Y", default)]
+	pub position_y: Option<f32>,
+	#[serde(rename = "Position Z", default)]
+	pub position_z: Option<f32>,
+	#[serde(rename = "Rotation X", default)]
+	pub rotation_x: Option<f32>,
+	#[serde(rename = "Rotation Y", default)]
+	pub rotation_y: Option<f32>,
+	#[serde(rename = "Rotation Z", default)]
+	pub rotation_z: Option<f32>,
+	#[serde(rename = "Hang Position", default)]
+	pub hang_position: Option<String>,
+	#[serde(rename = "Layer", default)]
+	pub layer: Option<String>,
+}
+
+/// The rows of a patch sheet, before they are matched against the show
+pub struct ParsedSheet {
+	/// The rows that could be read, with their row numbers
+	pub rows: Vec<(usize, PatchSheetRow)>,
+
+	/// Rows that could not be read
+	pub conflicts: Vec<PatchImportConflict>,
+}
+
+/// A patch sheet row that has been matched against the show, ready to be applied
+pub struct ResolvedRow {
+	/// The row's number in the sheet, counting the header as row 1
+	pub row: usize,
+
+	/// The existing instance to update, or `None` to create a new instance
+	pub existing_instance: Option<Uuid>,
+	pub number: Option<FixtureNumber>,
+	pub fixture_type: Uuid,
+	pub personality: String,
+	pub name: Option<String>,
+	pub comments: Option<String>,
+	pub universe: Option<Uuid>,
+	pub offset: Option<u16>,
+	pub placement: Option<(u16, u16)>,
+	pub position: Option<Position>,
+	pub rotation: Option<StageRotation>,
+	pub hang_position: Option<String>,
+	pub layer: Option<String>,
+}
+
+/// Writes the show's patch as CSV, sorted by universe (in display order) and address.
+///
+/// Unaddressed fixtures are listed last, sorted by number.
+pub fn export(patcher: &SharablePatcherState, dmx: &DMXState) -> Result<String, PatchSheetError> {
+	let mut instances: Vec<_> = patcher.fixtures.values().collect();
+	instances.sort_by_cached_key(|instance| {
+		let address = dmx.fixtures.get(&instance.id).and_then(|dmx_instance| {
+			let universe_index = dmx
+				.universe_display_order
+				.iter()
+				.position(|universe_id| Some(*universe_id) == dmx_instance.universe)?;
+			return Some((universe_index, dmx_instance.offset.unwrap_or(0)));
+		});
+		return (address.is_none(), address, instance.number);
+	});
+
+	let mut writer = csv::Writer::from_writer(Vec::new());
+	for instance in instances {
+		let fixture_type_info = patcher.library.get(&instance.fixture_id);
+		let dmx_instance = dmx.fixtures.get(&instance.id);
+		let universe = dmx_instance
+			.and_then(|dmx_instance| dmx_instance.universe)
+			.and_then(|universe_id| dmx.universes.get(&universe_id));
+		let visualization_info = &instance.visualization_info;
+		writer
+			.serialize(PatchSheetRow {
+				number: instance.number.map(|number| number.to_string()),
+				id: Some(instance.id),
+				name: instance.name.clone(),
+				manufacturer: fixture_type_info.and_then(|info| info.manufacturer.clone()),
+				fixture_type: fixture_type_info.map(|info| info.name.clone()).unwrap_or_default(),
+				personality: instance.personality.clone(),
+				universe: universe.map(|universe| universe.name.clone()),
+				address: universe.and(dmx_instance.and_then(|dmx_instance| dmx_instance.offset)),
+				comments: instance.comments.clone(),
+				x: Some(visualization_info.x),
+				y: Some(visualization_info.y),
+				position_x: visualization_info.position.map(|position| position.x),
+				position_y: visualization_info.position.map(|position| position.y),
+				position_z: visualization_info.position.map(|position| position.z),
+				rotation_x: visualization_info.rotation.map(|rotation| rotation.x),
+				rotation_y: visualization_info.rotation.map(|rotation| rotation.y),
+				rotation_z: visualization_info.rotation.map(|rotation| rotation.z),
+				hang_position: visualization_info.hang_position.clone(),
+				layer: visualization_info.layer.clone(),
+			})
+			.map_err(|error| PatchSheetError::ExportFailed(error.to_string()))?;
+	}
+
+	let data = writer
+		.into_inner()
+		.map_err(|error| PatchSheetError::ExportFailed(error.to_string()))?;
+	return String::from_utf8(data).map_err(|error| PatchSheetError::ExportFailed(error.to_string()));
+}
+
+/// Parses a patch sheet. Columns may appear in any order, and optional columns may be omitted.
+///
+/// Rows that can't be read are reported as conflicts. Only an unreadable header rejects the whole sheet.
+pub fn parse(sheet: &str) -> Result<ParsedSheet, PatchSheetError> {
+	let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::All).from_reader(sheet.as_bytes());
+	reader
+		.headers()
+		.map_err(|error| PatchSheetError::InvalidSheet(error.to_string()))?;
+
+	let mut rows = Vec::new();
+	let mut conflicts = Vec::new();
+	for (index, row) in reader.deserialize::<PatchSheetRow>().enumerate() {
+		// The header is row 1
+		let row_number = index + 2;
+		match row {
+			Ok(row) => rows.push((row_number, row)),
+			Err(error) => conflicts.push(PatchImportConflict {
+				row: row_number,
+				message: format!("The row could not be read: {}", error),
+			}),
+		}
+	}
+	return Ok(ParsedSheet { rows, conflicts });
+}
+
+/// Matches a row's fixture type, universe, and existing instance against the show
+pub fn resolve_row(
+	patcher: &SharablePatcherState,
+	dmx: &DMXState,
+	row_number: usize,
+	row: PatchSheetRow,
+) -> Result<ResolvedRow, PatchImportConflict> {
+	let conflict = |message: String| PatchImportConflict { row: row_number, message };
+
+	let existing_instance = row.id.and_then(|id| patcher.fixtures.get(&id));
+
+	// Find the fixture type
+	let fixture_type = match existing_instance {
+		Some(instance) if patcher.library.get(&instance.fixture_id).is_some_and(|info| type_matches(info, &row)) => {
+			instance.fixture_id
+		}
+		Some(_) => {
+			return Err(conflict(format!(
+				"Fixture type {:?} does not match the existing fixture. Replace the fixture type instead.",
+				row.fixture_type
+			)));
+		}
+		None => {
+			let mut candidates = patcher.library.values().filter(|info| type_matches(info, &row));
+			match (candidates.next(), candidates.next()) {
+				(Some(info), None) => info.id,
+				(None, _) => return Err(conflict(format!("Fixture type {:?} is not in the show", row.fixture_type))),
+				(Some(_), Some(_)) => {
+					return Err(conflict(format!(
+						"Fixture type {:?} is ambiguous. Specify a manufacturer.",
+						row.fixture_type
+					)));
+				}
+			}
+		}
+	};
+	let fixture_type_info = &patcher.library[&fixture_type];
+	if fixture_type_info.output_driver != DMX_DRIVER_ID {
+		return Err(conflict(format!("Fixture type {:?} is not a DMX fixture", row.fixture_type)));
+	}
+	if !fixture_type_info.personalities.contains_key(&row.personality) {
+		return Err(conflict(format!("Personality {:?} does not exist", row.personality)));
+	}
+
+	// Check the fixture number
+	let number = match row.number {
+		Some(ref number) => {
+			let number: FixtureNumber = number
+				.parse()
+				.map_err(|_| conflict(format!("{:?} is not a valid fixture number", number)))?;
+			if let Some(numbered_fixture) = patcher.find_fixture_by_number(&number) {
+				if Some(numbered_fixture) != existing_instance.map(|instance| instance.id) {
+					return Err(conflict(format!("Fixture number {} is already in use", number)));
+				}
+			}
+			Some(number)
+		}
+		None => None,
+	};
+
+	// Find the universe
+	let universe = match row.universe {
+		Some(ref universe_name) => {
+			let universe = dmx
+				.universes
+				.values()
+				.find(|universe| &universe.name == universe_name || universe.id.to_string() == *universe_name)
+				.ok_or_else(|| conflict(format!("Universe {:?} does not exist", universe_name)))?;
+			if row.address.is_none() {
+				return Err(conflict(String::from("Fixtures assigned to a universe need an address")));
+			}
+			Some(universe.id)
+		}
+		None => None,
+	};
+
+	return Ok(ResolvedRow {
+		row: row_number,
+		existing_instance: existing_instance.map(|instance| instance.id),
+		number,
+		fixture_type,
+		personality: row.personality,
+		name: row.name.or_else(|| existing_instance.and_then(|instance| instance.name.clone())),
+		comments: row.comments.or_else(|| existing_instance.and_then(|instance| instance.comments.clone())),
+		universe,
+		offset: universe.and(row.address),
+		placement: row.x.zip(row.y),
+		position: match (row.position_x, row.position_y, row.position_z) {
+			(Some(x), Some(y), Some(z)) => Some(Position { x, y, z }),
+			_ => None,
+		},
+		rotation: match (row.rotation_x, row.rotation_y, row.rotation_z) {
+			(Some(x), Some(y), Some(z)) => Some(StageRotation { x, y, z }),
+			_ => None,
+		},
+		hang_position: row.hang_position,
+		layer: row.layer,
+	});
+}
+
+fn type_matches(fixture_type_info: &FixtureInfo, row: &PatchSheetRow) -> bool {
+	let name_matches = fixture_type_info.name == row.fixture_type
+		|| fixture_type_info.short_name.as_ref() == Some(&row.fixture_type);
+	let manufacturer_matches = row.manufacturer.is_none() || fixture_type_info.manufacturer == row.manufacturer;
+	return name_matches && manufacturer_matches;
+}
+
+#[portable]
+/// Describes the outcome of a patch sheet import
+pub struct PatchImportReport {
+	/// IDs of the fixture instances created by the import
+	pub created: Vec<Uuid>,
+
+	/// IDs of the existing fixture instances updated by the import
+	pub updated: Vec<Uuid>,
+
+	/// Rows that could not be applied. The rest of the sheet is still imported.
+	pub conflicts: Vec<PatchImportConflict>,
+}
+
+#[portable]
+/// A patch sheet row that could not be applied
+pub struct PatchImportConflict {
+	/// The row's number in the sheet, counting the header as row 1
+	pub row: usize,
+	pub message: String,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while importing or exporting a patch sheet
+pub enum PatchSheetError {
+	#[error("The patch sheet could not be read:\n{0}")]
+	InvalidSheet(String),
+	#[error("The patch sheet could not be written:\n{0}")]
+	ExportFailed(String),
+}
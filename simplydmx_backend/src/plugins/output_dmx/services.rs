@@ -3,8 +3,23 @@ use uuid::Uuid;
 
 use crate::utilities::{forms::FormDescriptor, serialized_data::SerializedData};
 
-use super::interface::{
-	DMXDriverDescription, DMXInterface, GetLinkUniverseFormError, LinkUniverseError,
+use super::{
+	builder::{FixtureBuilderError, FixtureBuilderForm, FixtureDraft, FixtureDraftEdit},
+	importers::{
+		gdtf::{self, GdtfImportError},
+		ofl,
+	},
+	interface::{
+		BulkPatchError, BulkPatchOptions, DMXDriverDescription, DMXInterface, GetLinkUniverseFormError,
+		LinkUniverseError,
+	},
+	patch_report::PatchIssue,
+	patch_sheet::{PatchImportReport, PatchSheetError},
+};
+use crate::plugins::patcher::{
+	driver_plugin_api::FixtureValidationError,
+	importers::{ofl::OflImportError, ImportReport},
+	PatcherInterface,
 };
 
 #[interpolate_service(
@@ -205,3 +220,380 @@ impl RenameUniverse {
 			.await;
 	}
 }
+
+#[interpolate_service(
+	"import_gdtf",
+	"Import GDTF Fixture",
+	"Imports a fixture type from a GDTF (.gdtf) file, reporting any features that could not be imported. Re-importing a fixture type replaces the show's copy, as long as the fixtures using it are still valid."
+)]
+impl ImportGdtf {
+	#![inner_raw(PatcherInterface)]
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		Self(patcher_interface)
+	}
+
+	#[service_main(
+		("The contents of the .gdtf file"),
+		("A report describing the imported fixture and any unsupported features"),
+	)]
+	async fn main(self, data: Vec::<u8>) -> Result<ImportReport, GdtfImportError> {
+		let (bundle, report) = gdtf::convert(&data)?.into_report();
+		self.0
+			.import_fixture(bundle)
+			.await
+			.map_err(|error| GdtfImportError::ImportFailed(error.to_string()))?;
+		return Ok(report);
+	}
+}
+
+#[interpolate_service(
+	"import_ofl_fixture",
+	"Import Open Fixture Library Fixture",
+	"Imports a fixture type from an Open Fixture Library JSON definition, reporting any features that could not be imported. Re-importing a fixture type replaces the show's copy, as long as the fixtures using it are still valid."
+)]
+impl ImportOflFixture {
+	#![inner_raw(PatcherInterface)]
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		Self(patcher_interface)
+	}
+
+	#[service_main(
+		("The contents of the OFL fixture's .json file"),
+		("The fixture's manufacturer, which OFL stores outside of the fixture definition"),
+		("A report describing the imported fixture and any unsupported features"),
+	)]
+	async fn main(self, definition: String, manufacturer: Option::<String>) -> Result<ImportReport, OflImportError> {
+		let definition: serde_json::Value = serde_json::from_str(&definition)
+			.map_err(|error| OflImportError::InvalidDefinition(error.to_string()))?;
+		let (bundle, report) = ofl::convert(&definition, manufacturer)?.into_report();
+		self.0
+			.import_fixture(bundle)
+			.await
+			.map_err(|error| OflImportError::ImportFailed(error.to_string()))?;
+		return Ok(report);
+	}
+}
+
+#[interpolate_service(
+	"bulk_patch_fixtures",
+	"Bulk Patch Fixtures",
+	"Creates several instances of a fixture type, numbering their names and assigning DMX addresses automatically"
+)]
+impl BulkPatchFixtures {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The fixture type, personality, count, naming, and addressing to use"),
+		("The IDs of the new fixture instances, in patch order"),
+	)]
+	async fn main(self, options: BulkPatchOptions) -> Result<Vec<Uuid>, BulkPatchError> {
+		return self.0.bulk_patch(&self.1, options).await;
+	}
+}
+
+#[interpolate_service(
+	"find_free_dmx_range",
+	"Find Free DMX Range",
+	"Finds the first offset in a universe where a range of channels is unoccupied"
+)]
+impl FindFreeDMXRange {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The universe to search", "universes"),
+		("The number of consecutive channels needed"),
+		("The first offset to consider. Defaults to 1."),
+		("The first offset of the free range, if one exists"),
+	)]
+	async fn main(self, universe_id: Uuid, size: u16, start_offset: Option::<u16>) -> Option<u16> {
+		return self.0.find_free_range(&self.1, &universe_id, size, start_offset).await;
+	}
+}
+
+#[interpolate_service(
+	"get_dmx_footprint",
+	"Get DMX Footprint",
+	"Gets the number of DMX channels a fixture type occupies in a personality"
+)]
+impl GetDMXFootprint {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The fixture type to measure", "fixture-type-uuid"),
+		("The personality to measure"),
+		("The number of channels, if the fixture type and personality exist"),
+	)]
+	async fn main(self, fixture_type: Uuid, personality: String) -> Option<u16> {
+		return self.0.get_footprint_size(&self.1, &fixture_type, &personality).await;
+	}
+}
+
+#[interpolate_service(
+	"export_patch_csv",
+	"Export Patch Sheet",
+	"Exports the show's patch as a CSV patch sheet"
+)]
+impl ExportPatchCsv {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The contents of the patch sheet"),
+	)]
+	async fn main(self) -> Result<String, PatchSheetError> {
+		return self.0.export_patch_sheet(&self.1).await;
+	}
+}
+
+#[interpolate_service(
+	"import_patch_csv",
+	"Import Patch Sheet",
+	"Creates or updates fixture instances from a CSV patch sheet, reporting any rows that could not be applied"
+)]
+impl ImportPatchCsv {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The contents of the patch sheet"),
+		("The instances that were created or updated, and any rows that could not be applied"),
+	)]
+	async fn main(self, sheet: String) -> Result<PatchImportReport, PatchSheetError> {
+		return self.0.import_patch_sheet(&self.1, sheet).await;
+	}
+}
+
+#[interpolate_service(
+	"get_patch_report",
+	"Get Patch Report",
+	"Checks the show's patch for overlapping addresses, unpatched fixtures, unlinked universes, and missing or orphaned data"
+)]
+impl GetPatchReport {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("Every problem found in the patch"),
+	)]
+	async fn main(self) -> Vec::<PatchIssue> {
+		return self.0.get_patch_report(&self.1).await;
+	}
+}
+
+#[interpolate_service(
+	"create_fixture_draft",
+	"Create Fixture Draft",
+	"Starts building a new fixture type from the fixture builder's details form"
+)]
+impl CreateFixtureDraft {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("Data from the fixture builder's details form"),
+		("The ID of the new draft"),
+	)]
+	async fn main(self, form_data: SerializedData) -> Result<Uuid, FixtureBuilderError> {
+		return self.0.create_fixture_draft(form_data).await;
+	}
+}
+
+#[interpolate_service(
+	"create_fixture_draft_from_type",
+	"Edit Fixture Type",
+	"Starts a fixture builder draft that edits a fixture type already in the show"
+)]
+impl CreateFixtureDraftFromType {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The fixture type to edit", "fixture-type-uuid"),
+		("The ID of the new draft"),
+	)]
+	async fn main(self, fixture_type: Uuid) -> Result<Uuid, FixtureBuilderError> {
+		return self.0.create_fixture_draft_from_type(&self.1, &fixture_type).await;
+	}
+}
+
+#[interpolate_service(
+	"list_fixture_drafts",
+	"List Fixture Drafts",
+	"Lists the fixture types being built or edited"
+)]
+impl ListFixtureDrafts {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID and name of each draft"),
+	)]
+	async fn main(self) -> Vec<(Uuid, String)> {
+		return self.0.list_fixture_drafts().await;
+	}
+}
+
+#[interpolate_service(
+	"get_fixture_draft",
+	"Get Fixture Draft",
+	"Gets the current state of a fixture builder draft"
+)]
+impl GetFixtureDraft {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID of the draft"),
+		("The draft, or `None` if it does not exist"),
+	)]
+	async fn main(self, draft_id: Uuid) -> Option<FixtureDraft> {
+		return self.0.get_fixture_draft(&draft_id).await;
+	}
+}
+
+#[interpolate_service(
+	"get_fixture_builder_form",
+	"Get Fixture Builder Form",
+	"Gets one of the fixture builder's forms"
+)]
+impl GetFixtureBuilderForm {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID of the draft the form is for, or `None` to get the form for creating a draft"),
+		("The form to get"),
+		("The form"),
+	)]
+	async fn main(self, draft_id: Option::<Uuid>, form: FixtureBuilderForm) -> Result<FormDescriptor, FixtureBuilderError> {
+		return self.0.get_fixture_builder_form(draft_id.as_ref(), &form).await;
+	}
+}
+
+#[interpolate_service(
+	"submit_fixture_builder_form",
+	"Submit Fixture Builder Form",
+	"Applies data from one of the fixture builder's forms to a draft"
+)]
+impl SubmitFixtureBuilderForm {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID of the draft"),
+		("The form the data was entered in"),
+		("The data entered in the form"),
+		("Whether or not the step was applied"),
+	)]
+	async fn main(self, draft_id: Uuid, form: FixtureBuilderForm, form_data: SerializedData) -> Result<(), FixtureBuilderError> {
+		return self.0.submit_fixture_builder_form(&draft_id, &form, form_data).await;
+	}
+}
+
+#[interpolate_service(
+	"edit_fixture_draft",
+	"Edit Fixture Draft",
+	"Applies a step to a fixture builder draft, rejecting steps that would make the draft less valid"
+)]
+impl EditFixtureDraft {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID of the draft"),
+		("The step to apply"),
+		("Whether or not the step was applied"),
+	)]
+	async fn main(self, draft_id: Uuid, edit: FixtureDraftEdit) -> Result<(), FixtureBuilderError> {
+		return self.0.edit_fixture_draft(&draft_id, edit).await;
+	}
+}
+
+#[interpolate_service(
+	"validate_fixture_draft",
+	"Validate Fixture Draft",
+	"Lists every problem that would prevent a fixture builder draft from being imported"
+)]
+impl ValidateFixtureDraft {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID of the draft"),
+		("The problems with the draft"),
+	)]
+	async fn main(self, draft_id: Uuid) -> Result<Vec<FixtureValidationError>, FixtureBuilderError> {
+		return self.0.validate_fixture_draft(&draft_id).await;
+	}
+}
+
+#[interpolate_service(
+	"finish_fixture_draft",
+	"Finish Fixture Draft",
+	"Imports a fixture builder draft into the show, replacing the fixture type it edits"
+)]
+impl FinishFixtureDraft {
+	#![inner_raw(DMXInterface, PatcherInterface)]
+	pub fn new(interface: DMXInterface, patcher_interface: PatcherInterface) -> Self {
+		Self(interface, patcher_interface)
+	}
+
+	#[service_main(
+		("The ID of the draft"),
+		("The ID of the imported fixture type"),
+	)]
+	async fn main(self, draft_id: Uuid) -> Result<Uuid, FixtureBuilderError> {
+		return self.0.finish_fixture_draft(&self.1, &draft_id).await;
+	}
+}
+
+#[interpolate_service(
+	"discard_fixture_draft",
+	"Discard Fixture Draft",
+	"Deletes a fixture builder draft without importing it"
+)]
+impl DiscardFixtureDraft {
+	#![inner_raw(DMXInterface)]
+	pub fn new(interface: DMXInterface) -> Self {
+		Self(interface)
+	}
+
+	#[service_main(
+		("The ID of the draft"),
+		("Whether or not the draft was deleted"),
+	)]
+	async fn main(self, draft_id: Uuid) -> Result<(), FixtureBuilderError> {
+		return self.0.discard_fixture_draft(&draft_id).await;
+	}
+}
@@ -5,31 +5,22 @@ use uuid::Uuid;
 
 use simplydmx_plugin_framework::*;
 
-use crate::plugins::patcher::driver_plugin_api::{ChannelSize, FixtureInfo, SharablePatcherState};
+use crate::plugins::patcher::driver_plugin_api::{FixtureInfo, SharablePatcherState};
 
 use super::{
+	builder::FixtureDraft,
 	driver_types::DMXDriver,
-	fixture_types::{DMXFixtureData, DMXPersonalityData},
+	fixture_types::{DMXChannelLayout, DMXFixtureData},
 	interface::DMXShowSave,
 };
 
-fn get_size(
-	personality: &DMXPersonalityData,
-	fixture_type_info: &FixtureInfo,
-) -> anyhow::Result<u16> {
+fn get_size(layout: &[DMXChannelLayout]) -> anyhow::Result<u16> {
 	// Calculate the fixture & personality's size in the DMX universe
-	let mut size = 0;
-	for channel_id in personality.dmx_channel_order.iter() {
-		let channel = fixture_type_info
-			.channels
-			.get(channel_id)
-			.context("Fixture definition referenced a channel that was not defined.")?;
-		size += match channel.size {
-			ChannelSize::U8 => 1,
-			ChannelSize::U16 => 2,
-		};
-	}
-	return Ok(size);
+	let last_slot = layout.iter().flat_map(|channel| channel.slots.iter()).max();
+	return match last_slot {
+		Some(slot) => slot.checked_add(1).context("Fixture definition uses a slot past the last possible address"),
+		None => Ok(0),
+	};
 }
 
 fn check_overlap(range1: (u16, u16), range2: (u16, u16)) -> bool {
@@ -42,6 +33,27 @@ fn check_overlap(range1: (u16, u16), range2: (u16, u16)) -> bool {
 	}
 }
 
+/// Finds the first offset at or after `start` where `size` channels fit without overlapping `used`
+pub fn find_free_offset(used: &[(u16, u16)], size: u16, start: u16) -> Option<u16> {
+	let size = size.max(1) as u32;
+	let mut candidate = start.max(1) as u32;
+	loop {
+		let end = candidate + size - 1;
+		if end > 512 {
+			return None;
+		}
+		let blocking_end = used
+			.iter()
+			.filter(|used_range| check_overlap((candidate as u16, end as u16), **used_range))
+			.map(|used_range| used_range.1)
+			.max();
+		match blocking_end {
+			Some(blocking_end) => candidate = blocking_end as u32 + 1,
+			None => return Some(candidate as u16),
+		}
+	}
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct DMXState {
 	#[serde(skip)]
@@ -50,6 +62,15 @@ pub struct DMXState {
 	pub fixtures: HashMap<Uuid, DMXFixtureInstance>,
 	pub universe_display_order: Vec<Uuid>,
 	pub universes: HashMap<Uuid, UniverseInstance>,
+
+	/// Fixture types being built or edited, keyed by draft ID
+	pub fixture_drafts: HashMap<Uuid, FixtureDraft>,
+
+	/// The layout of each fixture type's personalities, keyed by fixture type ID, then personality ID.
+	///
+	/// Layouts are worked out when a fixture type is imported so they don't need to be rebuilt every frame.
+	#[serde(skip)]
+	pub layouts: HashMap<Uuid, HashMap<String, Vec<DMXChannelLayout>>>,
 }
 impl DMXState {
 	pub fn new() -> Self {
@@ -59,20 +80,111 @@ impl DMXState {
 			fixtures: HashMap::new(),
 			universe_display_order: Vec::new(),
 			universes: HashMap::new(),
+			fixture_drafts: HashMap::new(),
+			layouts: HashMap::new(),
 		};
 	}
 	pub fn from_file(file: DMXShowSave) -> Self {
+		let mut universe_display_order: Vec<Uuid> = file
+			.universe_display_order
+			.into_iter()
+			.filter(|universe_id| file.universes.contains_key(universe_id))
+			.collect();
+
+		// Older show files don't store the display order
+		let mut missing: Vec<&UniverseInstance> = file
+			.universes
+			.values()
+			.filter(|universe| !universe_display_order.contains(&universe.id))
+			.collect();
+		missing.sort_by(|a, b| a.name.cmp(&b.name));
+		universe_display_order.extend(missing.into_iter().map(|universe| universe.id));
+
 		return DMXState {
 			drivers: HashMap::new(),
 			library: file.library,
 			fixtures: file.fixtures,
-			universe_display_order: Vec::new(),
+			universe_display_order,
 			universes: file.universes,
+			fixture_drafts: file.fixture_drafts,
+			layouts: HashMap::new(),
+		};
+	}
+
+	/// Works out the layout of each of a fixture type's personalities, replacing any cached layouts.
+	///
+	/// Personalities that don't have a valid layout are left out, so their fixtures aren't output.
+	pub fn cache_layouts(&mut self, fixture_type_info: &FixtureInfo) {
+		let fixture_data = match self.library.get(&fixture_type_info.id) {
+			Some(fixture_data) => fixture_data,
+			None => {
+				self.layouts.remove(&fixture_type_info.id);
+				return;
+			}
 		};
+		let mut layouts = HashMap::new();
+		for (personality_id, personality) in fixture_data.personalities.iter() {
+			if let Ok(layout) = personality.layout(fixture_type_info) {
+				layouts.insert(personality_id.clone(), layout);
+			}
+		}
+		self.layouts.insert(fixture_type_info.id, layouts);
 	}
+
+	/// Gets the cached layout of a fixture type's personality
+	pub fn get_layout(&self, fixture_type_id: &Uuid, personality_id: &str) -> Option<&[DMXChannelLayout]> {
+		return self
+			.layouts
+			.get(fixture_type_id)?
+			.get(personality_id)
+			.map(Vec::as_slice);
+	}
+
+	/// Gets the number of DMX channels a fixture type occupies in the given personality
+	pub fn get_personality_size(&self, fixture_type_info: &FixtureInfo, personality_id: &str) -> anyhow::Result<u16> {
+		let fixture_info = self
+			.library
+			.get(&fixture_type_info.id)
+			.context("Cannot find fixture definition")?;
+		let personality = fixture_info
+			.personalities
+			.get(personality_id)
+			.context("Could not find the requested personality")?;
+		return get_size(&personality.layout(fixture_type_info)?);
+	}
+
+	/// Gets the universe and inclusive address range occupied by a fixture instance, if it is addressed
+	pub fn get_footprint(&self, patcher: &SharablePatcherState, instance_id: &Uuid) -> Option<(Uuid, (u16, u16))> {
+		let fixture = self.fixtures.get(instance_id)?;
+		let (universe, offset) = (fixture.universe?, fixture.offset?);
+		let patcher_instance = patcher.fixtures.get(instance_id)?;
+		let patcher_definition = patcher.library.get(&patcher_instance.fixture_id)?;
+		let size = self.get_personality_size(patcher_definition, &patcher_instance.personality).ok()?;
+		return Some((universe, (offset, offset.checked_add(size.max(1) - 1)?)));
+	}
+
+	/// Gets the inclusive address ranges occupied in a universe, optionally excluding one fixture instance
+	pub fn get_used_ranges(
+		&self,
+		patcher: &SharablePatcherState,
+		universe_id: &Uuid,
+		exclude: Option<&Uuid>,
+	) -> Vec<(u16, u16)> {
+		return self
+			.fixtures
+			.keys()
+			.filter(|fixture_id| Some(*fixture_id) != exclude)
+			.filter_map(|fixture_id| self.get_footprint(patcher, fixture_id))
+			.filter(|(universe, _)| universe == universe_id)
+			.map(|(_, range)| range)
+			.collect();
+	}
+	/// Validates a fixture instance's addressing. `instance_id` is excluded from conflict checks so existing
+	/// fixtures can be re-validated against a new personality.
 	pub async fn normalize_fixture(
 		&self,
 		patcher: &SharablePatcherState,
+		instance_id: &Uuid,
 		fixture_type_info: &FixtureInfo,
 		personality_id: &str,
 		mut fixture: DMXFixtureInstance,
@@ -85,52 +197,19 @@ impl DMXState {
 							return Err(anyhow!("Offset must be between 1 and 512"));
 						}
 
-						// Get information about the fixture
-						let ctx = self;
-						let fixture_info = ctx
-							.library
-							.get(&fixture_type_info.id)
-							.context("Cannot find fixture definition")?;
-						let personality = fixture_info
-							.personalities
-							.get(personality_id)
-							.context("Could not find the requested personality")?;
-
-						let size = get_size(personality, fixture_type_info)?;
+						let size = self.get_personality_size(fixture_type_info, personality_id)?;
 
 						// Get the range of values that this fixture would cover (inclusive)
 						let covered_range = (offset, offset + size - 1);
+						if covered_range.1 > 512 {
+							return Err(anyhow!("The fixture does not fit in the universe at this offset"));
+						}
 
 						// Check for conflicts
-						let overlap = self.fixtures.iter().any(|(fixture_id, fixture)| {
-							if fixture.universe != Some(universe) {
-								return false;
-							}
-							if let (Some(offset), Some(patcher_instance)) =
-								(fixture.offset, patcher.fixtures.get(fixture_id))
-							{
-								if let (Some(patcher_definition), Some(dmx_info)) = (
-									patcher.library.get(&patcher_instance.fixture_id),
-									ctx.library.get(&fixture_type_info.id),
-								) {
-									if let (Some(dmx_personality),) =
-										(dmx_info.personalities.get(&patcher_instance.personality),)
-									{
-										if let (Ok(size),) =
-											(get_size(dmx_personality, patcher_definition),)
-										{
-											// Now we have offset and size. Check if there's a conflict
-											let this_covered_range = (offset, offset + size - 1);
-											return check_overlap(
-												covered_range,
-												this_covered_range,
-											);
-										}
-									}
-								}
-							}
-							return false; // If we had the information, we would have checked already.
-						});
+						let overlap = self
+							.get_used_ranges(patcher, &universe, Some(instance_id))
+							.into_iter()
+							.any(|used_range| check_overlap(covered_range, used_range));
 
 						if overlap {
 							return Err(anyhow!(
@@ -153,6 +232,58 @@ impl DMXState {
 			}
 		}
 	}
+
+	/// Checks that every addressed instance of a fixture type still fits in its universe if the type is replaced
+	/// with `fixture_info` and `fixture_data`, without overlapping any other fixture.
+	///
+	/// Returns the first instance that doesn't fit, in patch order, along with the reason.
+	pub fn check_replacement(
+		&self,
+		patcher: &SharablePatcherState,
+		fixture_info: &FixtureInfo,
+		fixture_data: &DMXFixtureData,
+	) -> Result<(), (Uuid, anyhow::Error)> {
+		// Instances of the type being replaced are checked against each other using their new footprints
+		let mut used: Vec<(Uuid, (u16, u16))> = self
+			.fixtures
+			.keys()
+			.filter(|instance_id| patcher.fixtures.get(instance_id).map(|instance| instance.fixture_id) != Some(fixture_info.id))
+			.filter_map(|instance_id| self.get_footprint(patcher, instance_id))
+			.collect();
+
+		for instance_id in patcher.fixture_order.iter() {
+			let instance = match patcher.fixtures.get(instance_id) {
+				Some(instance) if instance.fixture_id == fixture_info.id => instance,
+				_ => continue,
+			};
+			let (universe, offset) = match self.fixtures.get(instance_id) {
+				Some(DMXFixtureInstance { universe: Some(universe), offset: Some(offset) }) => (*universe, *offset),
+				_ => continue,
+			};
+
+			let footprint = fixture_data
+				.personalities
+				.get(&instance.personality)
+				.context("Could not find the requested personality")
+				.and_then(|personality| get_size(&personality.layout(fixture_info)?))
+				.and_then(|size| {
+					offset
+						.checked_add(size.max(1) - 1)
+						.filter(|end| *end <= 512)
+						.map(|end| (offset, end))
+						.context("The fixture does not fit in the universe at this offset")
+				})
+				.map_err(|error| (*instance_id, error))?;
+			let overlap = used
+				.iter()
+				.any(|(used_universe, used_range)| *used_universe == universe && check_overlap(footprint, *used_range));
+			if overlap {
+				return Err((*instance_id, anyhow!("Offset conflicts with an existing fixture definition")));
+			}
+			used.push((universe, footprint));
+		}
+		return Ok(());
+	}
 }
 
 #[portable]
@@ -0,0 +1,119 @@
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// A named dimmer curve stored in the show, which maps the mixer's output to the level sent to a fixture
+#[portable]
+pub struct DimmerCurve {
+	pub id: Uuid,
+	pub name: String,
+	pub shape: CurveShape,
+}
+
+/// The shape of a dimmer curve
+#[portable]
+#[derive(Default)]
+pub enum CurveShape {
+	/// Output matches the mixer's output
+	#[default]
+	Linear,
+
+	/// Output is the square of the input, giving finer control over low levels
+	SquareLaw,
+
+	/// Output is the square root of the input, the inverse of [`CurveShape::SquareLaw`]
+	InverseSquareLaw,
+
+	/// Output eases in at the bottom of the range and eases out at the top
+	SCurve,
+
+	/// Output is interpolated linearly between points of a lookup table, sorted by input
+	Custom(Vec<CurvePoint>),
+}
+
+/// A point in a custom dimmer curve. Both values range from 0 to 1.
+#[portable]
+#[derive(Copy)]
+pub struct CurvePoint {
+	pub input: f32,
+	pub output: f32,
+}
+
+impl CurveShape {
+	/// Checks that a custom curve's lookup table can be evaluated, sorting its points by input
+	pub fn prepare(&mut self) -> Result<(), DimmerCurveError> {
+		if let CurveShape::Custom(points) = self {
+			if points.len() < 2 {
+				return Err(DimmerCurveError::InvalidCurve("Custom curves need at least two points".into()));
+			}
+			for point in points.iter() {
+				if !(0.0..=1.0).contains(&point.input) || !(0.0..=1.0).contains(&point.output) {
+					return Err(DimmerCurveError::InvalidCurve("Curve points must range from 0 to 1".into()));
+				}
+			}
+			points.sort_by(|a, b| a.input.total_cmp(&b.input));
+		}
+		return Ok(());
+	}
+
+	/// Applies the curve to a fraction of the channel's full range. Custom curves must be prepared first.
+	pub fn evaluate(&self, input: f32) -> f32 {
+		let input = input.clamp(0.0, 1.0);
+		return match self {
+			CurveShape::Linear => input,
+			CurveShape::SquareLaw => input * input,
+			CurveShape::InverseSquareLaw => input.sqrt(),
+			CurveShape::SCurve => input * input * (3.0 - 2.0 * input),
+			CurveShape::Custom(points) => match points.iter().position(|point| point.input >= input) {
+				None => points.last().map_or(input, |point| point.output),
+				Some(0) => points[0].output,
+				Some(index) => {
+					let (low, high) = (points[index - 1], points[index]);
+					let progress = (input - low.input) / (high.input - low.input);
+					low.output + (high.output - low.output) * progress
+				}
+			},
+		};
+	}
+
+	/// Finds the input that produces an output, assuming the curve never decreases. Custom curves must be
+	/// prepared first.
+	pub fn invert(&self, output: f32) -> f32 {
+		let output = output.clamp(0.0, 1.0);
+		let (mut low, mut high) = (0.0_f32, 1.0_f32);
+		for _ in 0..24 {
+			let middle = (low + high) / 2.0;
+			if self.evaluate(middle) < output {
+				low = middle;
+			} else {
+				high = middle;
+			}
+		}
+		return (low + high) / 2.0;
+	}
+
+	/// Applies the curve to a channel value
+	pub fn apply(&self, value: u16, max_value: u16) -> u16 {
+		if max_value == 0 {
+			return value;
+		}
+		let output = self.evaluate(value as f32 / max_value as f32);
+		return (output * max_value as f32).round() as u16;
+	}
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while editing or assigning dimmer curves
+pub enum DimmerCurveError {
+	#[error("The requested dimmer curve does not exist")]
+	CurveMissing,
+	#[error("This fixture does not exist")]
+	FixtureMissing,
+	#[error("The requested fixture type does not exist in the show")]
+	FixtureTypeMissing,
+	#[error("Channel {0:?} does not exist in the fixture's definition")]
+	UnknownChannel(String),
+	#[error("The curve is invalid: {0}")]
+	InvalidCurve(String),
+}
@@ -3,6 +3,7 @@ pub use super::PatcherInterface;
 pub use super::{
 	fixture_types::*,
 	state::{FixtureInstance, SharablePatcherState},
+	validation::{format_validation_errors, validate_fixture_info, FixtureValidationError},
 };
 pub use crate::{impl_anyhow, utilities::forms::FormDescriptor};
 use crate::{
@@ -42,7 +43,21 @@ pub trait OutputDriver: Send + Sync + 'static {
 	fn get_description(&self) -> String;
 
 	/// Imports a fixture description with the given SerializedData instance, as packaged in the bundle
-	async fn import_fixture(&self, id: &Uuid, data: SerializedData) -> Result<(), ImportError>;
+	///
+	/// The generic portion of the definition has already been validated. The driver should check that its
+	/// own data is consistent with `fixture_info`, returning `ImportError::InvalidDefinition` if it is not.
+	///
+	/// If `fixture_info` replaces a fixture type that is already in `patcher_state`, the patcher has checked that
+	/// every instance's personality still exists. The driver must check that each instance's driver-specific
+	/// settings, such as its address, are still valid with the new definition, returning
+	/// `ImportError::InstanceConflict` if they are not. The patcher holds its lock throughout, so instances can't
+	/// change between the check and the import.
+	async fn import_fixture(
+		&self,
+		patcher_state: &SharablePatcherState,
+		fixture_info: &FixtureInfo,
+		data: SerializedData,
+	) -> Result<(), ImportError>;
 
 	/// Exports driver-specific information about the fixture for saving in a JSON format
 	async fn export_fixture_json(&self, id: &Uuid) -> anyhow::Result<serde_json::Value>;
@@ -81,6 +96,19 @@ pub trait OutputDriver: Send + Sync + 'static {
 		form: SerializedData,
 	) -> Result<(), EditInstanceError>;
 
+	/// Checks that an existing instance of a fixture can switch to a different personality while keeping
+	/// its current driver-specific settings, such as its address. The patcher updates the personality
+	/// itself once this succeeds.
+	///
+	/// `fixture_type_info` may differ from the instance's current type when its fixture type is being replaced.
+	async fn change_personality(
+		&self,
+		patcher_state: &SharablePatcherState,
+		instance_id: &Uuid,
+		fixture_type_info: &FixtureInfo,
+		personality_id: &str,
+	) -> Result<(), EditInstanceError>;
+
 	/// Sends updates to the output.
 	///
 	/// This function's implementation must be fast and infallible. To resolve speed, try to keep information you would
@@ -105,6 +133,10 @@ pub trait OutputDriver: Send + Sync + 'static {
 pub enum ImportError {
 	#[error("Could not parse form data:\n{0}")]
 	InvalidData(String),
+	#[error("The driver's data does not match the fixture definition:\n{}", format_validation_errors(.0))]
+	InvalidDefinition(Vec<FixtureValidationError>),
+	#[error("Fixture {0} can't use the new definition: {1}")]
+	InstanceConflict(Uuid, String),
 	#[error("An error occurred while importing the fixture:\n{0}")]
 	Other(String),
 }
@@ -5,6 +5,8 @@ use uuid::Uuid;
 
 use crate::mixer_utils::state::BlendingScheme;
 
+use super::curves::CurveShape;
+
 #[portable]
 /// Importable fixture descriptor bundle that includes output driver-specific information
 pub struct FixtureBundle {
@@ -24,6 +26,11 @@ pub struct FixtureInfo {
 	/// Instances of this fixture will contain this UUID as a reference to the source data.
 	pub id: Uuid,
 
+	/// The revision of this definition. This should be incremented whenever a non-breaking change is made, so
+	/// shows can detect that their embedded copy of the fixture is older than the one in the fixture library.
+	#[serde(default)]
+	pub version: u32,
+
 	/// The human-readable name of the fixture
 	pub name: String,
 
@@ -52,6 +59,49 @@ pub struct FixtureInfo {
 	///
 	/// These get filtered by what channels are available in the selected personality
 	pub control_groups: Vec<ControlGroup>,
+
+	/// Cells, or heads, of a fixture that repeat the same attributes, such as the segments of an LED bar.
+	///
+	/// Each cell is controlled as its own sub-fixture. Channels used by a cell are not controlled through the
+	/// parent fixture.
+	#[serde(default)]
+	pub cells: Vec<FixtureCell>,
+}
+
+impl FixtureInfo {
+	/// Checks whether a channel is controlled through one of the fixture's cells
+	pub fn is_cell_channel(&self, channel_id: &str) -> bool {
+		return self.cells.iter().any(|cell| cell.attributes.values().any(|cell_channel| cell_channel == channel_id));
+	}
+}
+
+/// A cell, or head, of a fixture, which is controlled as its own sub-fixture
+#[portable]
+pub struct FixtureCell {
+	/// Identifies the cell within the fixture definition
+	pub id: String,
+
+	/// The human-readable name of the cell
+	pub name: String,
+
+	/// Maps the cell's attribute IDs to channel IDs within the fixture.
+	///
+	/// Cells should share attribute IDs so they can be controlled interchangeably.
+	pub attributes: FxHashMap<String, String>,
+
+	/// The cell's position relative to the fixture's origin, in meters
+	#[serde(default)]
+	pub position: Position,
+}
+
+/// A position in meters. Cells are positioned relative to their fixture's origin, and fixtures are
+/// positioned relative to the stage.
+#[portable]
+#[derive(Default, Copy)]
+pub struct Position {
+	pub x: f32,
+	pub y: f32,
+	pub z: f32,
 }
 
 /// Contains data about a group of channels that can be controlled using a special controller
@@ -67,6 +117,22 @@ pub struct ControlGroup {
 	channels: ControlGroupData,
 }
 
+impl ControlGroup {
+	pub fn new(name: Option<String>, channels: ControlGroupData) -> Self {
+		return ControlGroup { name, channels };
+	}
+
+	/// Gets the name of a non-standard control
+	pub fn get_name(&self) -> Option<&String> {
+		return self.name.as_ref();
+	}
+
+	/// Gets the type of control and the channels it uses
+	pub fn get_channels(&self) -> &ControlGroupData {
+		return &self.channels;
+	}
+}
+
 /// Specifies the type of ControlGroup in use and associated channels
 #[portable]
 pub enum ControlGroupData {
@@ -80,6 +146,76 @@ pub enum ControlGroupData {
 	GenericInput(String),
 }
 
+impl ControlGroupData {
+	/// Gets the kind of capability the control provides
+	pub fn capability(&self) -> Capability {
+		return match self {
+			ControlGroupData::Intensity(_) => Capability::Intensity,
+			ControlGroupData::RGBGroup { .. } => Capability::RGBGroup,
+			ControlGroupData::CMYKGroup { .. } => Capability::CMYKGroup,
+			ControlGroupData::PanTilt { .. } => Capability::PanTilt,
+			ControlGroupData::Gobo(_) => Capability::Gobo,
+			ControlGroupData::ColorWheel(_) => Capability::ColorWheel,
+			ControlGroupData::Zoom(_) => Capability::Zoom,
+			ControlGroupData::GenericInput(_) => Capability::GenericInput,
+		};
+	}
+
+	/// Creates a copy of the control with each channel ID replaced, or `None` if any channel can't be replaced
+	pub fn map_channels(&self, map: impl Fn(&String) -> Option<String>) -> Option<ControlGroupData> {
+		return Some(match self {
+			ControlGroupData::Intensity(channel) => ControlGroupData::Intensity(map(channel)?),
+			ControlGroupData::RGBGroup { red, green, blue } => ControlGroupData::RGBGroup {
+				red: map(red)?,
+				green: map(green)?,
+				blue: map(blue)?,
+			},
+			ControlGroupData::CMYKGroup { cyan, magenta, yellow, black } => ControlGroupData::CMYKGroup {
+				cyan: map(cyan)?,
+				magenta: map(magenta)?,
+				yellow: map(yellow)?,
+				black: map(black)?,
+			},
+			ControlGroupData::PanTilt { pan, tilt } => ControlGroupData::PanTilt {
+				pan: map(pan)?,
+				tilt: map(tilt)?,
+			},
+			ControlGroupData::Gobo(channel) => ControlGroupData::Gobo(map(channel)?),
+			ControlGroupData::ColorWheel(channel) => ControlGroupData::ColorWheel(map(channel)?),
+			ControlGroupData::Zoom(channel) => ControlGroupData::Zoom(map(channel)?),
+			ControlGroupData::GenericInput(channel) => ControlGroupData::GenericInput(map(channel)?),
+		});
+	}
+
+	/// Lists the IDs of every channel used by the control
+	pub fn channel_ids(&self) -> Vec<&String> {
+		return match self {
+			ControlGroupData::Intensity(channel)
+			| ControlGroupData::Gobo(channel)
+			| ControlGroupData::ColorWheel(channel)
+			| ControlGroupData::Zoom(channel)
+			| ControlGroupData::GenericInput(channel) => vec![channel],
+			ControlGroupData::RGBGroup { red, green, blue } => vec![red, green, blue],
+			ControlGroupData::CMYKGroup { cyan, magenta, yellow, black } => vec![cyan, magenta, yellow, black],
+			ControlGroupData::PanTilt { pan, tilt } => vec![pan, tilt],
+		};
+	}
+}
+
+/// The kinds of control a fixture can offer, matching the variants of [`ControlGroupData`]
+#[portable]
+#[derive(Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+	Intensity,
+	RGBGroup,
+	CMYKGroup,
+	PanTilt,
+	Gobo,
+	ColorWheel,
+	Zoom,
+	GenericInput,
+}
+
 /// Metadata about the fixture, used for display in the UI
 #[portable]
 pub struct FixtureMeta {
@@ -111,6 +247,61 @@ pub struct Channel {
 
 	/// Dictates how the channel should be blended/controlled
 	pub ch_type: ChannelType,
+
+	/// The physical quantity the channel controls, such as an angle or color temperature, so values can be
+	/// entered in real-world units
+	#[serde(default)]
+	pub physical: Option<PhysicalRange>,
+}
+
+impl Channel {
+	/// Gets the segments of a segmented channel
+	pub fn get_segments(&self) -> Option<&Vec<Segment>> {
+		return match self.ch_type {
+			ChannelType::Segmented { ref segments, .. } => Some(segments),
+			ChannelType::Linear { .. } => None,
+		};
+	}
+
+	/// Finds a segment by name, ignoring case
+	pub fn find_segment(&self, name: &str) -> Option<&Segment> {
+		return self.get_segments()?.iter().find(|segment| segment.name.eq_ignore_ascii_case(name));
+	}
+
+	/// Finds the segment a value falls into
+	pub fn segment_at(&self, value: u16) -> Option<&Segment> {
+		return self.get_segments()?.iter().find(|segment| (segment.start..=segment.end).contains(&value));
+	}
+
+	/// Converts a raw value to the channel's physical unit, or `None` if the channel has no physical range
+	pub fn to_physical(&self, value: u16) -> Option<PhysicalValue> {
+		let physical = self.physical.as_ref()?;
+		let (raw_start, raw_end) = physical.raw_range.unwrap_or((0, self.size.max_value()));
+		let fraction = if raw_start == raw_end {
+			0.0
+		} else {
+			(value as f32 - raw_start as f32) / (raw_end as f32 - raw_start as f32)
+		};
+		return Some(PhysicalValue {
+			value: physical.start + (physical.end - physical.start) * physical.curve.evaluate(fraction),
+			unit: physical.unit,
+		});
+	}
+
+	/// Converts a physical value to the closest raw value, clamping it to the channel's physical range.
+	///
+	/// Returns `None` if the channel has no physical range or uses a different unit.
+	pub fn from_physical(&self, value: &PhysicalValue) -> Option<u16> {
+		let physical = self.physical.as_ref().filter(|physical| physical.unit == value.unit)?;
+		let (raw_start, raw_end) = physical.raw_range.unwrap_or((0, self.size.max_value()));
+		let fraction = if physical.start == physical.end {
+			0.0
+		} else {
+			(value.value - physical.start) / (physical.end - physical.start)
+		};
+		let input = physical.curve.invert(fraction);
+		return Some((raw_start as f32 + (raw_end as f32 - raw_start as f32) * input).round() as u16);
+	}
 }
 
 /// Dictates the size of the output. Values will be stored as the largest of these options, but bounds
@@ -121,6 +312,56 @@ pub enum ChannelSize {
 	U16,
 }
 
+impl ChannelSize {
+	/// Gets the largest value that fits in the channel
+	pub fn max_value(&self) -> u16 {
+		return match self {
+			ChannelSize::U8 => u8::MAX as u16,
+			ChannelSize::U16 => u16::MAX,
+		};
+	}
+}
+
+/// A unit of physical quantity a channel can control
+#[portable]
+#[derive(Copy, PartialEq, Eq)]
+pub enum PhysicalUnit {
+	Degrees,
+	Kelvin,
+	Hertz,
+	Percent,
+}
+
+/// Maps a channel's raw values onto a physical quantity
+#[portable]
+pub struct PhysicalRange {
+	pub unit: PhysicalUnit,
+
+	/// The physical value at the start of the raw range
+	pub start: f32,
+
+	/// The physical value at the end of the raw range
+	pub end: f32,
+
+	/// The raw values the physical range covers, such as the strobe range of a shutter channel. Defaults to the
+	/// channel's full range.
+	#[serde(default)]
+	pub raw_range: Option<(u16, u16)>,
+
+	/// Maps a fraction of the raw range to a fraction of the physical range. The curve should never decrease so
+	/// physical values can be converted back.
+	#[serde(default)]
+	pub curve: CurveShape,
+}
+
+/// A value in a physical unit
+#[portable]
+#[derive(Copy)]
+pub struct PhysicalValue {
+	pub value: f32,
+	pub unit: PhysicalUnit,
+}
+
 /// Describes information used for controlling and blending the channel
 #[portable]
 pub enum ChannelType {
@@ -139,6 +380,7 @@ pub enum ChannelType {
 pub enum AssetDescriptor {
 	BuiltIn(String),
 	SVGInline(String),
+	PNGInline(Vec<u8>),
 }
 
 /// Describes how a segment should be displayed to the user in the UI
@@ -167,6 +409,14 @@ pub struct Segment {
 	pub display: SegmentDisplay,
 }
 
+impl Segment {
+	/// Gets the value used to select this segment. The center of the segment is used so the selection isn't
+	/// thrown off by fixtures that round differently at the segment's edges.
+	pub fn center(&self) -> u16 {
+		return self.start + self.end.saturating_sub(self.start) / 2;
+	}
+}
+
 /// Identifies non-implementation-specific features of a personality.
 ///
 /// Implementation-specific features of a personality such as channel order should
@@ -0,0 +1,78 @@
+//! Shared pieces for converting third-party fixture definition formats to SimplyDMX's `FixtureBundle`.
+//!
+//! Driver-independent mappings live here, while output drivers handle the parts of each format
+//! that describe their own protocol.
+
+pub mod ofl;
+
+use simplydmx_plugin_framework::*;
+use uuid::Uuid;
+
+use crate::{
+	mixer_utils::state::BlendingScheme,
+	plugins::patcher::driver_plugin_api::{Channel, ChannelSize, ChannelType, FixtureBundle},
+};
+
+/// The channel used to fill DMX slots that a mode leaves unused
+pub const RESERVED_CHANNEL: &str = "_reserved";
+
+/// Creates the channel used to fill unused DMX slots. It always outputs 0.
+pub fn reserved_channel() -> Channel {
+	return Channel {
+		intensity_emulation: None,
+		size: ChannelSize::U8,
+		default: 0,
+		ch_type: ChannelType::Linear { priority: BlendingScheme::LTP },
+		physical: None,
+	};
+}
+
+/// A fixture definition converted from another format, ready to be imported into the patcher
+pub struct ConvertedFixture {
+	pub bundle: FixtureBundle,
+	pub warnings: Vec<ImportWarning>,
+}
+
+impl ConvertedFixture {
+	/// Splits the converted fixture into the bundle to import and a report for the user
+	pub fn into_report(self) -> (FixtureBundle, ImportReport) {
+		let report = ImportReport {
+			fixture_id: self.bundle.fixture_info.id,
+			name: self.bundle.fixture_info.name.clone(),
+			warnings: self.warnings,
+		};
+		return (self.bundle, report);
+	}
+}
+
+#[portable]
+/// Describes a feature of an imported fixture definition that could not be fully represented
+pub struct ImportWarning {
+	/// Where the feature was found, such as a mode or channel name
+	pub location: String,
+
+	/// A description of the feature and how it was handled
+	pub message: String,
+}
+
+impl ImportWarning {
+	pub fn new(location: impl Into<String>, message: impl Into<String>) -> Self {
+		return ImportWarning {
+			location: location.into(),
+			message: message.into(),
+		};
+	}
+}
+
+#[portable]
+/// The result of importing a fixture definition from another format
+pub struct ImportReport {
+	/// The ID of the imported fixture type
+	pub fixture_id: Uuid,
+
+	/// The name of the imported fixture type
+	pub name: String,
+
+	/// Features of the definition that could not be fully represented
+	pub warnings: Vec<ImportWarning>,
+}
@@ -0,0 +1,612 @@
+//! Maps Open Fixture Library (OFL) definitions onto SimplyDMX fixture types.
+//!
+//! OFL definitions are read as raw JSON rather than deserialized into typed structs, since the
+//! format is large and only part of it can be represented in SimplyDMX. Anything that can't be
+//! represented is reported back to the user as an `ImportWarning`.
+//!
+//! This module only handles the parts of a definition that apply to every output driver, such as
+//! channels, capabilities, and control groups. Output drivers lay out each mode's channels and
+//! pass them in as `OflMode`s.
+
+use std::collections::{BTreeSet, HashMap};
+
+use rustc_hash::FxHashMap;
+use serde_json::{Map, Value};
+use sha1::{Digest, Sha1};
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::{Builder, Uuid};
+
+use super::{reserved_channel, ImportWarning, RESERVED_CHANNEL};
+use crate::{
+	mixer_utils::state::BlendingScheme,
+	plugins::patcher::{
+		driver_plugin_api::{
+			AssetDescriptor, Channel, ChannelSize, ChannelType, ControlGroup, ControlGroupData, FixtureInfo,
+			FixtureMeta, Personality, PhysicalRange, PhysicalUnit, Segment, SegmentDisplay,
+		},
+		CurveShape,
+	},
+};
+
+/// The namespace used to derive stable IDs for fixture types imported from OFL
+const OFL_NAMESPACE: Uuid = Uuid::from_u128(0x6f666c00_5d4c_4b1e_9a0e_2f3c8d7b1e55);
+
+/// The most bytes a channel's DMX values can use. OFL supports up to 24-bit resolution.
+const MAX_VALUE_BYTES: u32 = 3;
+
+#[portable]
+#[derive(Error)]
+/// An error that prevented an OFL definition from being imported
+pub enum OflImportError {
+	#[error("The definition is not a valid OFL fixture: {0}")]
+	InvalidDefinition(String),
+	#[error("The fixture does not contain any modes that can be imported")]
+	NoModes,
+	#[error("Channel {0:?} has more fine channels than the supported 24-bit resolution")]
+	UnsupportedResolution(String),
+	#[error("The converted fixture could not be imported: {0}")]
+	ImportFailed(String),
+}
+
+/// An OFL fixture definition that has been checked for the sections required to import it
+pub struct OflDefinition<'a> {
+	definition: &'a Map<String, Value>,
+	name: String,
+
+	/// The definition's modes, in the order they are defined
+	pub modes: &'a [Value],
+}
+
+impl<'a> OflDefinition<'a> {
+	/// Reads an OFL definition, returning an error if required sections are missing
+	pub fn parse(definition: &'a Value) -> Result<OflDefinition<'a>, OflImportError> {
+		let definition = definition
+			.as_object()
+			.ok_or_else(|| OflImportError::InvalidDefinition("Expected a JSON object".into()))?;
+		let name = definition
+			.get("name")
+			.and_then(Value::as_str)
+			.ok_or_else(|| OflImportError::InvalidDefinition("The fixture has no name".into()))?
+			.to_owned();
+		let modes = definition
+			.get("modes")
+			.and_then(Value::as_array)
+			.ok_or_else(|| OflImportError::InvalidDefinition("The fixture has no modes".into()))?;
+		return Ok(OflDefinition {
+			definition,
+			name,
+			modes,
+		});
+	}
+
+	/// Gets a channel from the definition's `availableChannels`
+	pub fn channel(&self, channel_key: &str) -> Option<&'a Value> {
+		return self.section("availableChannels")?.get(channel_key);
+	}
+
+	/// Iterates over the definition's `availableChannels`, by key
+	pub fn channels(&self) -> impl Iterator<Item = (&'a String, &'a Value)> {
+		return self.section("availableChannels").into_iter().flatten();
+	}
+
+	fn wheels(&self) -> Option<&'a Map<String, Value>> {
+		return self.section("wheels");
+	}
+
+	fn section(&self, key: &str) -> Option<&'a Map<String, Value>> {
+		return self.definition.get(key).and_then(Value::as_object);
+	}
+}
+
+/// A mode's channels, as laid out by an output driver
+pub struct OflMode {
+	pub name: String,
+
+	/// The channels used by the mode, by key and size. `None` is a slot filled with the reserved channel.
+	pub channels: Vec<Option<(String, ChannelSize)>>,
+}
+
+/// The role of a channel, used to build control groups
+enum ChannelRole {
+	Intensity,
+	Red,
+	Green,
+	Blue,
+	Pan,
+	Tilt,
+	Gobo,
+	ColorWheel,
+	Zoom,
+	Generic,
+}
+
+/// Converts the driver-independent parts of an OFL definition into a fixture type.
+///
+/// Returns the fixture type, along with the channel ID used for each entry of each mode's
+/// `channels`, in the same order as `modes`.
+pub fn convert_fixture_info(
+	definition: &OflDefinition,
+	manufacturer: Option<String>,
+	output_driver: &str,
+	modes: &[OflMode],
+	warnings: &mut Vec<ImportWarning>,
+) -> Result<(FixtureInfo, Vec<Vec<String>>), OflImportError> {
+	if modes.is_empty() {
+		return Err(OflImportError::NoModes);
+	}
+
+	// Channels used at both resolutions need a separate ID for each
+	let mut used_sizes: HashMap<&str, BTreeSet<u8>> = HashMap::new();
+	for (channel_key, size) in modes.iter().flat_map(|mode| mode.channels.iter().flatten()) {
+		used_sizes
+			.entry(channel_key.as_str())
+			.or_default()
+			.insert(size_bytes(size) as u8);
+	}
+	let channel_id = |channel_key: &str, size: &ChannelSize| -> String {
+		if let (ChannelSize::U16, Some(sizes)) = (size, used_sizes.get(channel_key)) {
+			if sizes.len() > 1 {
+				return format!("{} (16-bit)", channel_key);
+			}
+		}
+		return channel_key.to_owned();
+	};
+
+	let mut channels: FxHashMap<String, Channel> = FxHashMap::default();
+	let mut channel_roles: Vec<(String, ChannelRole)> = Vec::new();
+	let mut personalities = FxHashMap::default();
+	let mut mode_channel_ids = Vec::new();
+	for mode in modes.iter() {
+		let mut channel_ids = Vec::new();
+		let mut personality_channels = Vec::new();
+		for mode_channel in mode.channels.iter() {
+			let (channel_key, size) = match mode_channel {
+				Some((channel_key, size)) => (channel_key, size),
+				None => {
+					channels.entry(RESERVED_CHANNEL.to_owned()).or_insert_with(reserved_channel);
+					if !personality_channels.iter().any(|id| id == RESERVED_CHANNEL) {
+						personality_channels.push(RESERVED_CHANNEL.to_owned());
+					}
+					channel_ids.push(RESERVED_CHANNEL.to_owned());
+					continue;
+				}
+			};
+
+			let id = channel_id(channel_key, size);
+			if !channels.contains_key(&id) {
+				let channel_definition = definition
+					.channel(channel_key)
+					.ok_or_else(|| OflImportError::InvalidDefinition(format!("Channel {:?} does not exist", channel_key)))?;
+				channels.insert(id.clone(), convert_channel(channel_key, channel_definition, size, definition.wheels(), warnings)?);
+				channel_roles.push((id.clone(), channel_role(channel_key, channel_definition, definition.wheels())));
+			}
+			if !personality_channels.contains(&id) {
+				personality_channels.push(id.clone());
+			}
+			channel_ids.push(id);
+		}
+		personalities.insert(mode.name.clone(), Personality { available_channels: personality_channels });
+		mode_channel_ids.push(channel_ids);
+	}
+
+	let control_groups = build_control_groups(&channel_roles, &personalities, warnings);
+	let fixture_info = FixtureInfo {
+		id: stable_id(manufacturer.as_deref(), &definition.name),
+		version: 0,
+		short_name: definition.definition.get("shortName").and_then(Value::as_str).map(String::from),
+		name: definition.name.clone(),
+		manufacturer: manufacturer.clone(),
+		family: string_array(definition.definition.get("categories")).first().cloned(),
+		metadata: FixtureMeta {
+			manufacturer,
+			manual_link: definition
+				.definition
+				.get("links")
+				.and_then(|links| links.get("manual"))
+				.and_then(|manual| string_array(Some(manual)).first().cloned()),
+		},
+		channels,
+		personalities,
+		output_driver: output_driver.to_owned(),
+		control_groups,
+		cells: Vec::new(),
+	};
+
+	return Ok((fixture_info, mode_channel_ids));
+}
+
+/// Derives a fixture type's ID from its manufacturer and name, so importing a newer revision of
+/// the same definition replaces the existing fixture type instead of adding a duplicate. The patcher
+/// checks that the show's fixtures can still use the new revision before replacing it.
+fn stable_id(manufacturer: Option<&str>, name: &str) -> Uuid {
+	let mut hasher = Sha1::new();
+	hasher.update(OFL_NAMESPACE.as_bytes());
+	hasher.update(format!("{}/{}", manufacturer.unwrap_or(""), name).as_bytes());
+	let mut bytes = [0u8; 16];
+	bytes.copy_from_slice(&hasher.finalize()[..16]);
+	return Builder::from_sha1_bytes(bytes).into_uuid();
+}
+
+/// Reads an optional JSON array of strings
+fn string_array(value: Option<&Value>) -> Vec<String> {
+	return value
+		.and_then(Value::as_array)
+		.map(|values| values.iter().filter_map(Value::as_str).map(String::from).collect())
+		.unwrap_or_default();
+}
+
+fn size_bytes(size: &ChannelSize) -> u32 {
+	return match size {
+		ChannelSize::U8 => 1,
+		ChannelSize::U16 => 2,
+	};
+}
+
+/// Scales a DMX value from the definition's resolution to the channel's size.
+///
+/// The end of a range is padded with `0xFF` bytes so ranges stay contiguous at higher resolutions.
+fn scale_value(value: u64, from_bytes: u32, to_bytes: u32, is_end: bool) -> u16 {
+	let scaled = if from_bytes > to_bytes {
+		value.checked_shr(8 * (from_bytes - to_bytes)).unwrap_or(0)
+	} else {
+		let shift = 8 * (to_bytes - from_bytes);
+		value.saturating_mul(1 << shift) | if is_end { (1 << shift) - 1 } else { 0 }
+	};
+	return scaled.min((1 << (8 * to_bytes)) - 1) as u16;
+}
+
+/// Gets the capabilities of a channel, which can be given as one `capability` or a list of `capabilities`
+fn capabilities(channel: &Value) -> Vec<&Value> {
+	if let Some(capabilities) = channel.get("capabilities").and_then(Value::as_array) {
+		return capabilities.iter().collect();
+	}
+	return channel.get("capability").into_iter().collect();
+}
+
+/// Finds the wheel slot referenced by a `WheelSlot` capability
+fn wheel_slot<'a>(capability: &Value, channel_key: &str, wheels: Option<&'a Map<String, Value>>) -> Option<&'a Value> {
+	let wheel_name = capability.get("wheel").and_then(Value::as_str).unwrap_or(channel_key);
+	let slot_number = capability.get("slotNumber").and_then(Value::as_f64)?;
+	if slot_number.fract() != 0.0 || slot_number < 1.0 {
+		// Split slots sit between two wheel slots
+		return None;
+	}
+	return wheels?
+		.get(wheel_name)?
+		.get("slots")?
+		.as_array()?
+		.get(slot_number as usize - 1);
+}
+
+/// Reads the physical range of a capability, such as the angles of a pan channel or the speeds of a strobe
+fn physical_range(capability: &Value, raw_range: Option<(u16, u16)>) -> Option<PhysicalRange> {
+	let (start_key, end_key) = match capability.get("type").and_then(Value::as_str)? {
+		"Pan" | "Tilt" | "Zoom" => ("angleStart", "angleEnd"),
+		"ColorTemperature" => ("colorTemperatureStart", "colorTemperatureEnd"),
+		"ShutterStrobe" | "StrobeSpeed" => ("speedStart", "speedEnd"),
+		_ => return None,
+	};
+	let (start, start_unit) = physical_entity(capability.get(start_key)?)?;
+	let (end, end_unit) = physical_entity(capability.get(end_key)?)?;
+	if start_unit != end_unit {
+		return None;
+	}
+	return Some(PhysicalRange {
+		unit: start_unit,
+		start,
+		end,
+		raw_range,
+		curve: CurveShape::Linear,
+	});
+}
+
+/// Parses an OFL entity that has a physical unit, such as `540deg` or `3200K`
+fn physical_entity(value: &Value) -> Option<(f32, PhysicalUnit)> {
+	let value = value.as_str()?;
+	let units = [
+		("deg", PhysicalUnit::Degrees),
+		("K", PhysicalUnit::Kelvin),
+		("Hz", PhysicalUnit::Hertz),
+		("%", PhysicalUnit::Percent),
+	];
+	for (suffix, unit) in units {
+		if let Some(number) = value.strip_suffix(suffix) {
+			return number.parse::<f32>().ok().map(|number| (number, unit));
+		}
+	}
+	return None;
+}
+
+/// Parses a CSS-style hex color
+fn parse_color(color: &str) -> Option<(u8, u8, u8)> {
+	let hex = color.strip_prefix('#')?;
+	if hex.len() != 6 {
+		return None;
+	}
+	let component = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+	return Some((component(0)?, component(2)?, component(4)?));
+}
+
+/// Converts an OFL channel into a SimplyDMX channel of the given size
+fn convert_channel(
+	channel_key: &str,
+	channel: &Value,
+	size: &ChannelSize,
+	wheels: Option<&Map<String, Value>>,
+	warnings: &mut Vec<ImportWarning>,
+) -> Result<Channel, OflImportError> {
+	let to_bytes = size_bytes(size);
+	let from_bytes = match channel.get("dmxValueResolution").and_then(Value::as_str) {
+		Some("8bit") => 1,
+		Some("16bit") => 2,
+		Some("24bit") => 3,
+		_ => match string_array(channel.get("fineChannelAliases")).len() {
+			fine_channels if fine_channels < MAX_VALUE_BYTES as usize => 1 + fine_channels as u32,
+			_ => return Err(OflImportError::UnsupportedResolution(channel_key.to_owned())),
+		},
+	};
+	let max_value = ((1u32 << (8 * to_bytes)) - 1) as u16;
+
+	let default = match channel.get("defaultValue") {
+		Some(Value::Number(value)) => scale_value(value.as_u64().unwrap_or(0), from_bytes, to_bytes, false),
+		Some(Value::String(value)) => value
+			.trim_end_matches('%')
+			.parse::<f64>()
+			.map_or(0, |percent| (percent.clamp(0.0, 100.0) / 100.0 * max_value as f64).round() as u16),
+		_ => 0,
+	};
+
+	let capabilities = capabilities(channel);
+	let priority = match channel.get("precedence").and_then(Value::as_str) {
+		Some("HTP") => BlendingScheme::HTP,
+		Some(_) => BlendingScheme::LTP,
+		None => {
+			if capabilities.iter().any(|capability| capability.get("type").and_then(Value::as_str) == Some("Intensity")) {
+				BlendingScheme::HTP
+			} else {
+				BlendingScheme::LTP
+			}
+		}
+	};
+
+	if capabilities.len() <= 1 {
+		return Ok(Channel {
+			intensity_emulation: None,
+			size: size.clone(),
+			default,
+			ch_type: ChannelType::Linear { priority },
+			physical: capabilities.first().and_then(|capability| physical_range(capability, None)),
+		});
+	}
+
+	let mut segments = Vec::new();
+	let mut physical_ranges = Vec::new();
+	for capability in capabilities {
+		let range: Vec<u64> = capability
+			.get("dmxRange")
+			.and_then(Value::as_array)
+			.map(|range| range.iter().filter_map(Value::as_u64).collect())
+			.unwrap_or_default();
+		if range.len() != 2 {
+			warnings.push(ImportWarning::new(channel_key, "A capability has no valid DMX range. It was skipped."));
+			continue;
+		}
+
+		let capability_type = capability.get("type").and_then(Value::as_str).unwrap_or("");
+		let slot = if capability_type == "WheelSlot" { wheel_slot(capability, channel_key, wheels) } else { None };
+		let colors = capability
+			.get("colors")
+			.or_else(|| slot.and_then(|slot| slot.get("colors")));
+		let color = string_array(colors).first().and_then(|color| parse_color(color));
+
+		let display = if let Some((red, green, blue)) = color {
+			SegmentDisplay::Color { red, green, blue }
+		} else if let Some(slot) = slot.filter(|slot| slot.get("type").and_then(Value::as_str) == Some("Gobo")) {
+			match slot.get("resource").and_then(Value::as_str) {
+				Some(resource) => SegmentDisplay::Gobo { asset: AssetDescriptor::BuiltIn(resource.to_owned()) },
+				None => SegmentDisplay::Other,
+			}
+		} else {
+			SegmentDisplay::Other
+		};
+
+		let name = capability
+			.get("comment")
+			.and_then(Value::as_str)
+			.or_else(|| slot.and_then(|slot| slot.get("name").and_then(Value::as_str)))
+			.or_else(|| slot.and_then(|slot| slot.get("type").and_then(Value::as_str)))
+			.or_else(|| capability.get("shutterEffect").and_then(Value::as_str))
+			.or_else(|| capability.get("effectName").and_then(Value::as_str))
+			.unwrap_or(capability_type)
+			.to_owned();
+
+		let start = scale_value(range[0], from_bytes, to_bytes, false);
+		let end = scale_value(range[1], from_bytes, to_bytes, true);
+		physical_ranges.extend(physical_range(capability, Some((start, end))));
+		segments.push(Segment {
+			start,
+			end,
+			name,
+			display,
+		});
+	}
+
+	// A physical range is only kept if it's unambiguous, such as the strobe range of a shutter channel
+	let physical = if physical_ranges.len() == 1 { physical_ranges.pop() } else { None };
+
+	return Ok(Channel {
+		intensity_emulation: None,
+		size: size.clone(),
+		default,
+		ch_type: ChannelType::Segmented {
+			segments,
+			priority,
+			snapping: None,
+		},
+		physical,
+	});
+}
+
+/// Determines what kind of control a channel should use, based on its capabilities
+fn channel_role(channel_key: &str, channel: &Value, wheels: Option<&Map<String, Value>>) -> ChannelRole {
+	let capabilities = capabilities(channel);
+	let types: Vec<&str> = capabilities
+		.iter()
+		.filter_map(|capability| capability.get("type").and_then(Value::as_str))
+		.collect();
+
+	if types.iter().all(|capability_type| *capability_type == "Intensity") && !types.is_empty() {
+		return ChannelRole::Intensity;
+	}
+	if let [capability] = capabilities.as_slice() {
+		match (types.first(), capability.get("color").and_then(Value::as_str)) {
+			(Some(&"ColorIntensity"), Some("Red")) => return ChannelRole::Red,
+			(Some(&"ColorIntensity"), Some("Green")) => return ChannelRole::Green,
+			(Some(&"ColorIntensity"), Some("Blue")) => return ChannelRole::Blue,
+			(Some(&"Pan"), _) => return ChannelRole::Pan,
+			(Some(&"Tilt"), _) => return ChannelRole::Tilt,
+			(Some(&"Zoom"), _) => return ChannelRole::Zoom,
+			_ => {}
+		}
+	}
+
+	// Wheels are identified by the kind of slots they contain
+	let slot_types: Vec<&str> = capabilities
+		.iter()
+		.filter_map(|capability| wheel_slot(capability, channel_key, wheels))
+		.filter_map(|slot| slot.get("type").and_then(Value::as_str))
+		.collect();
+	if slot_types.contains(&"Gobo") {
+		return ChannelRole::Gobo;
+	}
+	if slot_types.contains(&"Color") {
+		return ChannelRole::ColorWheel;
+	}
+	return ChannelRole::Generic;
+}
+
+/// Builds control groups from channel roles. Multi-channel groups are built from the channels
+/// used together in each mode.
+fn build_control_groups(
+	channel_roles: &[(String, ChannelRole)],
+	personalities: &FxHashMap<String, Personality>,
+	warnings: &mut Vec<ImportWarning>,
+) -> Vec<ControlGroup> {
+	let mut control_groups = Vec::new();
+
+	let mut mode_names: Vec<&String> = personalities.keys().collect();
+	mode_names.sort();
+	let mut rgb_groups: Vec<(String, String, String)> = Vec::new();
+	let mut pan_tilt_groups: Vec<(String, String)> = Vec::new();
+	for mode_name in mode_names {
+		let mode_channels = &personalities[mode_name].available_channels;
+		let find = |role: fn(&ChannelRole) -> bool| -> Vec<&String> {
+			return channel_roles
+				.iter()
+				.filter(|(id, channel_role)| role(channel_role) && mode_channels.contains(id))
+				.map(|(id, _)| id)
+				.collect();
+		};
+
+		let (red, green, blue) = (
+			find(|role| matches!(role, ChannelRole::Red)),
+			find(|role| matches!(role, ChannelRole::Green)),
+			find(|role| matches!(role, ChannelRole::Blue)),
+		);
+		match (red.as_slice(), green.as_slice(), blue.as_slice()) {
+			([red], [green], [blue]) => {
+				let group = ((*red).clone(), (*green).clone(), (*blue).clone());
+				if !rgb_groups.contains(&group) {
+					rgb_groups.push(group);
+				}
+			}
+			([], [], []) => {}
+			_ => warnings.push(ImportWarning::new(
+				mode_name.as_str(),
+				"Multiple or incomplete sets of RGB channels were found. They will use generic controls.",
+			)),
+		}
+
+		if let ([pan], [tilt]) = (
+			find(|role| matches!(role, ChannelRole::Pan)).as_slice(),
+			find(|role| matches!(role, ChannelRole::Tilt)).as_slice(),
+		) {
+			let group = ((*pan).clone(), (*tilt).clone());
+			if !pan_tilt_groups.contains(&group) {
+				pan_tilt_groups.push(group);
+			}
+		}
+	}
+
+	let grouped = |id: &String| {
+		rgb_groups.iter().any(|(red, green, blue)| red == id || green == id || blue == id)
+			|| pan_tilt_groups.iter().any(|(pan, tilt)| pan == id || tilt == id)
+	};
+	for (id, role) in channel_roles.iter() {
+		let channels = match role {
+			ChannelRole::Intensity => ControlGroupData::Intensity(id.clone()),
+			ChannelRole::Gobo => ControlGroupData::Gobo(id.clone()),
+			ChannelRole::ColorWheel => ControlGroupData::ColorWheel(id.clone()),
+			ChannelRole::Zoom => ControlGroupData::Zoom(id.clone()),
+			_ if grouped(id) => continue,
+			_ => {
+				control_groups.push(ControlGroup::new(Some(id.clone()), ControlGroupData::GenericInput(id.clone())));
+				continue;
+			}
+		};
+		control_groups.push(ControlGroup::new(None, channels));
+	}
+	for (red, green, blue) in rgb_groups {
+		control_groups.push(ControlGroup::new(None, ControlGroupData::RGBGroup { red, green, blue }));
+	}
+	for (pan, tilt) in pan_tilt_groups {
+		control_groups.push(ControlGroup::new(None, ControlGroupData::PanTilt { pan, tilt }));
+	}
+
+	return control_groups;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn scaled_values() {
+		assert_eq!(scale_value(128, 1, 1, false), 128);
+		assert_eq!(scale_value(128, 1, 2, false), 0x8000);
+		assert_eq!(scale_value(128, 1, 2, true), 0x80FF);
+		assert_eq!(scale_value(0x123456, 3, 2, false), 0x1234);
+		assert_eq!(scale_value(0x123456, 3, 1, false), 0x12);
+		assert_eq!(scale_value(u64::MAX, 1, 2, true), 0xFFFF);
+	}
+
+	#[test]
+	fn channel_resolutions() {
+		let mut warnings = Vec::new();
+		let dimmer = json!({
+			"fineChannelAliases": ["Dimmer fine", "Dimmer fine^2"],
+			"defaultValue": 0x808080,
+			"capability": { "type": "Intensity" },
+		});
+		let channel = convert_channel("Dimmer", &dimmer, &ChannelSize::U16, None, &mut warnings).ok().unwrap();
+		assert_eq!(channel.default, 0x8080);
+		assert!(matches!(channel.ch_type, ChannelType::Linear { priority: BlendingScheme::HTP }));
+
+		let explicit = json!({ "dmxValueResolution": "24bit", "defaultValue": 0xFFFFFF });
+		let channel = convert_channel("Pan", &explicit, &ChannelSize::U8, None, &mut warnings).ok().unwrap();
+		assert_eq!(channel.default, 0xFF);
+
+		let too_fine = json!({ "fineChannelAliases": ["Pan fine", "Pan fine^2", "Pan fine^3"] });
+		let result = convert_channel("Pan", &too_fine, &ChannelSize::U16, None, &mut warnings);
+		assert!(matches!(result, Err(OflImportError::UnsupportedResolution(channel_key)) if channel_key == "Pan"));
+	}
+
+	#[test]
+	fn stable_ids() {
+		assert_eq!(stable_id(Some("Acme"), "Spot"), stable_id(Some("Acme"), "Spot"));
+		assert_ne!(stable_id(Some("Acme"), "Spot"), stable_id(Some("Acme"), "Wash"));
+		assert_ne!(stable_id(Some("Acme"), "Spot"), stable_id(None, "Spot"));
+	}
+}
@@ -1,11 +1,20 @@
 use super::{
+	curves::{CurveShape, DimmerCurve, DimmerCurveError},
 	driver_plugin_api::{self, FixtureBundle, FixtureInstance, OutputDriver, SharableStateWrapper},
-	fixture_types::{ChannelSize, ChannelType, Segment},
-	state::{PatcherContext, VisualizationInfo},
+	fixture_types::{Capability, Channel, ChannelSize, ChannelType, ControlGroupData, FixtureInfo, PhysicalValue, Position, Segment},
+	library::{read_bundle, write_bundle, FixtureLibraryError, LibraryEntry, OutdatedFixture},
+	state::{
+		CapabilitySummary, ChannelLimits, FixtureControls, FixtureNumber, FixtureTransforms, PatcherContext, SoftwareStrobe,
+		StageRotation, SubFixture, VisualizationInfo, STROBE_DUTY_ATTRIBUTE, STROBE_RATE_ATTRIBUTE,
+	},
+	validation::{format_validation_errors, validate_fixture_info, FixtureValidationError},
 };
 use crate::{
 	impl_anyhow,
-	mixer_utils::state::{BlendingData, FullMixerBlendingData, FullMixerOutput, SnapData},
+	mixer_utils::{
+		data_sources::FrameInfo,
+		state::{BlendingData, BlendingScheme, FullMixerBlendingData, FullMixerOutput, SnapData},
+	},
 	plugins::saver::Savable,
 	utilities::{forms::FormDescriptor, serialized_data::SerializedData},
 };
@@ -52,45 +61,14 @@ impl PatcherInterface {
 
 					// Iterate through channels, populating the fixture containers
 					for channel_id in fixture_personality.available_channels.iter() {
+						// Channels belonging to cells are controlled through the cells' sub-fixtures
+						if fixture_info.is_cell_channel(channel_id) {
+							continue;
+						}
 						if let Some(channel_info) = fixture_info.channels.get(channel_id) {
-							match &channel_info.ch_type {
-								ChannelType::Linear { priority } => {
-									// Insert default value
-									fixture_defaults
-										.insert(channel_id.clone(), channel_info.default);
-									// Insert blending instructions
-									fixture_blending_data.insert(
-										channel_id.clone(),
-										BlendingData {
-											scheme: priority.clone(),
-											snap: SnapData::NoSnap,
-											allow_wrap: false,
-											max_value: get_max_value(&channel_info.size),
-											min_value: 0,
-										},
-									);
-								}
-								ChannelType::Segmented {
-									segments,
-									priority,
-									snapping,
-								} => {
-									// Insert default value
-									fixture_defaults
-										.insert(channel_id.clone(), channel_info.default);
-									// Insert blending instructions
-									fixture_blending_data.insert(
-										channel_id.clone(),
-										BlendingData {
-											scheme: priority.clone(),
-											snap: snapping.clone().unwrap_or(SnapData::NoSnap),
-											allow_wrap: false,
-											max_value: get_max_value_segments(&segments),
-											min_value: get_min_value_segments(&segments),
-										},
-									);
-								}
-							}
+							let (default, channel_blending_data) = get_channel_base(channel_info);
+							fixture_defaults.insert(channel_id.clone(), default);
+							fixture_blending_data.insert(channel_id.clone(), channel_blending_data);
 						} else {
 							log_error!(
 								self.0,
@@ -101,6 +79,23 @@ impl PatcherInterface {
 						}
 					}
 
+					// Fixtures with a software strobe get virtual attributes to control it
+					if fixture_data.software_strobe.is_some() {
+						for (attribute_id, default) in [(STROBE_RATE_ATTRIBUTE, 0), (STROBE_DUTY_ATTRIBUTE, u16::MAX / 2)] {
+							fixture_defaults.insert(attribute_id.to_owned(), default);
+							fixture_blending_data.insert(
+								attribute_id.to_owned(),
+								BlendingData {
+									scheme: BlendingScheme::LTP,
+									snap: SnapData::NoSnap,
+									allow_wrap: false,
+									max_value: u16::MAX,
+									min_value: 0,
+								},
+							);
+						}
+					}
+
 					// Insert fixture values into show containers
 					default_values.insert(fixture_id.clone(), fixture_defaults);
 					blending_data.insert(fixture_id.clone(), fixture_blending_data);
@@ -117,6 +112,29 @@ impl PatcherInterface {
 			}
 		}
 
+		// Cells are exposed to the mixer as their own sub-fixtures, using the cells' attribute IDs
+		for cell in ctx.sharable.cells.values() {
+			let parent = unwrap_continue!(ctx.sharable.fixtures.get(&cell.parent));
+			let fixture_info = unwrap_continue!(ctx.sharable.library.get(&parent.fixture_id));
+			let fixture_personality = unwrap_continue!(fixture_info.personalities.get(&parent.personality));
+			let (_, cell_info) = unwrap_continue!(ctx.sharable.get_cell_definition(cell));
+
+			let mut cell_defaults = FxHashMap::default();
+			let mut cell_blending_data = FxHashMap::default();
+			for (attribute_id, channel_id) in cell_info.attributes.iter() {
+				if !fixture_personality.available_channels.contains(channel_id) {
+					continue;
+				}
+				let channel_info = unwrap_continue!(fixture_info.channels.get(channel_id));
+				let (default, channel_blending_data) = get_channel_base(channel_info);
+				cell_defaults.insert(attribute_id.clone(), default);
+				cell_blending_data.insert(attribute_id.clone(), channel_blending_data);
+			}
+
+			default_values.insert(cell.id, cell_defaults);
+			blending_data.insert(cell.id, cell_blending_data);
+		}
+
 		return (default_values, blending_data);
 	}
 
@@ -127,31 +145,67 @@ impl PatcherInterface {
 			.insert(plugin.get_id(), Arc::new(Box::new(plugin)));
 	}
 
-	/// Import a fixture bundle
+	/// Import a fixture bundle.
+	///
+	/// If the show already has a fixture type with the same ID, it is replaced. Every instance of the type must
+	/// still be valid with the new definition, and `patcher.patch_updated` is emitted so the mixer picks up the
+	/// new channels.
 	pub async fn import_fixture(
 		&self,
 		fixture_bundle: FixtureBundle,
 	) -> Result<(), ImportFixtureError> {
+		let validation_errors = validate_fixture_info(&fixture_bundle.fixture_info);
+		if !validation_errors.is_empty() {
+			return Err(ImportFixtureError::InvalidDefinition(validation_errors));
+		}
+
 		let mut ctx = self.1.write().await;
+		let replacing = ctx.sharable.library.contains_key(&fixture_bundle.fixture_info.id);
+		if replacing {
+			for fixture_id in ctx.sharable.fixture_order.iter() {
+				let instance = unwrap_continue!(ctx.sharable.fixtures.get(fixture_id));
+				if instance.fixture_id == fixture_bundle.fixture_info.id
+					&& !fixture_bundle.fixture_info.personalities.contains_key(&instance.personality)
+				{
+					return Err(ImportFixtureError::PersonalityInUse(*fixture_id, instance.personality.clone()));
+				}
+			}
+		}
+
 		if let Some(output_driver) = ctx
 			.output_drivers
 			.get(&fixture_bundle.fixture_info.output_driver)
 		{
-			if let Err(controller_error) = output_driver
-				.import_fixture(&fixture_bundle.fixture_info.id, fixture_bundle.output_info)
+			match output_driver
+				.import_fixture(&ctx.sharable, &fixture_bundle.fixture_info, fixture_bundle.output_info)
 				.await
 			{
-				return Err(ImportFixtureError::ErrorFromController(controller_error));
-			} else {
-				// Controller successfully loaded protocol-specific details
-				ctx.sharable.library.insert(
-					fixture_bundle.fixture_info.id.clone(),
-					fixture_bundle.fixture_info,
-				);
-				self.0
-					.emit("patcher.new_fixture".into(), FilterCriteria::None, ())
-					.await;
-				return Ok(());
+				Err(driver_plugin_api::ImportError::InvalidDefinition(validation_errors)) => {
+					return Err(ImportFixtureError::InvalidDefinition(validation_errors));
+				}
+				Err(controller_error) => {
+					return Err(ImportFixtureError::ErrorFromController(controller_error));
+				}
+				Ok(()) => {
+					// Controller successfully loaded protocol-specific details
+					ctx.sharable.library.insert(
+						fixture_bundle.fixture_info.id.clone(),
+						fixture_bundle.fixture_info,
+					);
+
+					// Existing instances may be using an older copy of this fixture type
+					ctx.sharable.sync_all_cells();
+					drop(ctx);
+					self.0
+						.emit("patcher.new_fixture".into(), FilterCriteria::None, ())
+						.await;
+					if replacing {
+						self.0
+							.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+							.await;
+					}
+					return Ok(());
+				}
 			}
 		} else {
 			return Err(ImportFixtureError::UnknownController);
@@ -222,6 +276,7 @@ impl PatcherInterface {
 					return Err(CreateFixtureError::ErrorFromController(controller_error));
 				} else {
 					// Controller successfully loaded protocol-specific details
+					let number = ctx.sharable.next_fixture_number();
 					ctx.sharable.fixture_order.push(instance_uuid.clone());
 					ctx.sharable.fixtures.insert(
 						instance_uuid.clone(),
@@ -232,8 +287,13 @@ impl PatcherInterface {
 							name,
 							comments,
 							visualization_info: Default::default(),
+							number: Some(number),
+							transforms: Default::default(),
+							dimmer_curve: None,
+							software_strobe: None,
 						},
 					);
+					ctx.sharable.sync_cells(&instance_uuid);
 					self.0
 						.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
 						.await;
@@ -274,6 +334,7 @@ impl PatcherInterface {
 			return Err(DeleteFixtureError::ErrorFromController(controller_err.to_string()));
 		} else {
 			ctx.sharable.fixture_order.retain(|fixture| fixture != fixture_id);
+			ctx.sharable.sync_cells(fixture_id);
 			self.0
 				.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
 				.await;
@@ -303,6 +364,10 @@ impl PatcherInterface {
 				return Err(EditFixtureError::FixtureTypeMissing);
 			},
 		};
+		if !fixture_type_info.personalities.contains_key(&personality) {
+			ctx.sharable.fixtures.insert(instance_id, fixture);
+			return Err(EditFixtureError::PersonalityMissing);
+		}
 		let controller = match ctx.output_drivers.get(&fixture_type_info.output_driver) {
 			Some(controller) => controller,
 			None => {
@@ -335,8 +400,13 @@ impl PatcherInterface {
 					name,
 					comments,
 					visualization_info: fixture.visualization_info,
+					number: fixture.number,
+					transforms: fixture.transforms,
+					dimmer_curve: fixture.dimmer_curve,
+					software_strobe: fixture.software_strobe,
 				},
 			);
+			ctx.sharable.sync_cells(&instance_id);
 			self.0
 				.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
 				.await;
@@ -344,22 +414,224 @@ impl PatcherInterface {
 		}
 	}
 
+	/// Switches a fixture to a different personality, keeping its driver-specific settings.
+	///
+	/// Mixer data is stored by attribute ID, so attributes shared between the personalities keep their values.
+	/// Attributes that are no longer available are removed when the mixer cleans up after `patcher.patch_updated`.
+	pub async fn change_personality(&self, instance_id: &Uuid, personality: String) -> Result<(), EditFixtureError> {
+		let mut ctx = self.1.write().await;
+		let fixture = ctx.sharable.fixtures.get(instance_id).ok_or(EditFixtureError::FixtureMissing)?;
+		let fixture_type_info = ctx
+			.sharable
+			.library
+			.get(&fixture.fixture_id)
+			.ok_or(EditFixtureError::FixtureTypeMissing)?;
+		if !fixture_type_info.personalities.contains_key(&personality) {
+			return Err(EditFixtureError::PersonalityMissing);
+		}
+		let controller = ctx
+			.output_drivers
+			.get(&fixture_type_info.output_driver)
+			.ok_or(EditFixtureError::ControllerMissing)?;
+
+		controller
+			.change_personality(&ctx.sharable, instance_id, fixture_type_info, &personality)
+			.await
+			.map_err(EditFixtureError::ErrorFromController)?;
+
+		if let Some(fixture) = ctx.sharable.fixtures.get_mut(instance_id) {
+			fixture.personality = personality;
+		}
+		ctx.sharable.sync_cells(instance_id);
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Replaces the fixture type of several fixtures, keeping their driver-specific settings.
+	///
+	/// Attributes are matched by ID unless overridden in `attribute_map`, which maps old attribute IDs to
+	/// new ones. Returns the resolved mapping for each fixture so the mixer can migrate its layer data.
+	///
+	/// Transforms and channel dimmer curves follow the same mapping. Anything that refers to a channel
+	/// without a match in the new type is dropped.
+	pub async fn replace_fixture_type(
+		&self,
+		instance_ids: &[Uuid],
+		fixture_type: &Uuid,
+		personality: String,
+		attribute_map: &FxHashMap<String, String>,
+	) -> Result<FxHashMap<Uuid, FxHashMap<String, String>>, ReplaceFixtureTypeError> {
+		let mut ctx = self.1.write().await;
+		let new_type = ctx
+			.sharable
+			.library
+			.get(fixture_type)
+			.ok_or(ReplaceFixtureTypeError::FixtureTypeMissing)?
+			.clone();
+		let new_channels = &new_type
+			.personalities
+			.get(&personality)
+			.ok_or(ReplaceFixtureTypeError::PersonalityMissing)?
+			.available_channels;
+		let controller = ctx
+			.output_drivers
+			.get(&new_type.output_driver)
+			.ok_or(ReplaceFixtureTypeError::ControllerMissing)?
+			.clone();
+		if let Some(attribute_id) = attribute_map.values().find(|attribute_id| !new_channels.contains(attribute_id)) {
+			return Err(ReplaceFixtureTypeError::UnknownAttribute(attribute_id.clone()));
+		}
+
+		// Resolve the attribute mapping for each fixture from its current personality
+		let mut attribute_maps = FxHashMap::default();
+		for instance_id in instance_ids.iter() {
+			let instance = ctx
+				.sharable
+				.fixtures
+				.get(instance_id)
+				.ok_or(ReplaceFixtureTypeError::FixtureMissing(*instance_id))?;
+			let old_type = ctx
+				.sharable
+				.library
+				.get(&instance.fixture_id)
+				.ok_or(ReplaceFixtureTypeError::FixtureTypeMissing)?;
+			if old_type.output_driver != new_type.output_driver {
+				return Err(ReplaceFixtureTypeError::DriverMismatch);
+			}
+			let instance_map: FxHashMap<String, String> = old_type
+				.personalities
+				.get(&instance.personality)
+				.map_or(&[][..], |old_personality| &old_personality.available_channels)
+				.iter()
+				.filter_map(|old_attribute| {
+					let new_attribute = attribute_map.get(old_attribute).unwrap_or(old_attribute);
+					if new_channels.contains(new_attribute) {
+						return Some((old_attribute.clone(), new_attribute.clone()));
+					}
+					return None;
+				})
+				.collect();
+			attribute_maps.insert(*instance_id, instance_map);
+		}
+
+		// Fixtures are switched one at a time so each is checked against the new size of the ones before it
+		let mut previous = Vec::new();
+		for instance_id in instance_ids.iter() {
+			if let Err(controller_err) = controller
+				.change_personality(&ctx.sharable, instance_id, &new_type, &personality)
+				.await
+			{
+				for (instance_id, fixture_id, personality) in previous {
+					if let Some(instance) = ctx.sharable.fixtures.get_mut(&instance_id) {
+						instance.fixture_id = fixture_id;
+						instance.personality = personality;
+					}
+				}
+				return Err(ReplaceFixtureTypeError::ErrorFromController(*instance_id, controller_err));
+			}
+			if let Some(instance) = ctx.sharable.fixtures.get_mut(instance_id) {
+				previous.push((
+					*instance_id,
+					std::mem::replace(&mut instance.fixture_id, *fixture_type),
+					std::mem::replace(&mut instance.personality, personality.clone()),
+				));
+			}
+		}
+
+		// Carry settings that refer to channels over to the new type
+		let has_intensity = new_type.control_groups.iter().any(|control_group| {
+			matches!(control_group.get_channels(), ControlGroupData::Intensity(channel_id) if new_channels.contains(channel_id))
+		});
+		let sharable = &mut ctx.sharable;
+		for (instance_id, old_fixture_id, _) in previous.iter() {
+			let instance_map = unwrap_continue!(attribute_maps.get(instance_id));
+			let old_type = unwrap_continue!(sharable.library.get(old_fixture_id));
+			if let Some(instance) = sharable.fixtures.get_mut(instance_id) {
+				instance.transforms = migrate_transforms(&instance.transforms, instance_map, old_type, &new_type);
+				if !has_intensity {
+					instance.dimmer_curve = None;
+				}
+			}
+
+			// Curves already assigned to the new type's channels take precedence
+			let old_curves: Vec<(String, Uuid)> = sharable.channel_curves.get(old_fixture_id).map_or_else(Vec::new, |channel_curves| {
+				instance_map
+					.iter()
+					.filter_map(|(old_attribute, new_attribute)| Some((new_attribute.clone(), *channel_curves.get(old_attribute)?)))
+					.collect()
+			});
+			if !old_curves.is_empty() {
+				let new_curves = sharable.channel_curves.entry(*fixture_type).or_default();
+				for (channel_id, curve_id) in old_curves {
+					new_curves.entry(channel_id).or_insert(curve_id);
+				}
+			}
+		}
+
+		for instance_id in instance_ids.iter() {
+			ctx.sharable.sync_cells(instance_id);
+		}
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(attribute_maps);
+	}
+
 	/// Edits the position of a fixture within the visualizer
 	pub async fn edit_fixture_placement(&self, instance_id: &Uuid, x: u16, y: u16) {
+		self.edit_visualization_info(instance_id, |visualization_info| {
+			visualization_info.x = x;
+			visualization_info.y = y;
+		}).await;
+	}
+
+	/// Edits the position and orientation of a fixture on stage. Either may be `None` if it is unknown.
+	pub async fn edit_fixture_stage_placement(&self, instance_id: &Uuid, position: Option<Position>, rotation: Option<StageRotation>) {
+		self.edit_visualization_info(instance_id, |visualization_info| {
+			visualization_info.position = position;
+			visualization_info.rotation = rotation;
+		}).await;
+	}
+
+	/// Edits the hang position and layer a fixture is organized under
+	pub async fn edit_fixture_hang(&self, instance_id: &Uuid, hang_position: Option<String>, layer: Option<String>) {
+		self.edit_visualization_info(instance_id, |visualization_info| {
+			visualization_info.hang_position = hang_position;
+			visualization_info.layer = layer;
+		}).await;
+	}
+
+	/// Applies a change to a fixture's visualization info and notifies the visualizer
+	pub async fn edit_visualization_info(&self, instance_id: &Uuid, edit: impl FnOnce(&mut VisualizationInfo) + Send) {
 		let mut ctx = self.1.write().await;
 
 		if let Some(fixture) = ctx.sharable.fixtures.get_mut(instance_id) {
-			fixture.visualization_info.x = x;
-			fixture.visualization_info.y = y;
+			edit(&mut fixture.visualization_info);
+			self.0.emit("patcher.visualization_updated".into(), FilterCriteria::None, (
+				*instance_id,
+				fixture.visualization_info.clone(),
+			)).await;
 		}
+	}
 
-		self.0.emit("patcher.visualization_updated".into(), FilterCriteria::None, (
-			instance_id.clone(),
-			VisualizationInfo {
-				x,
-				y,
-			},
-		)).await;
+	/// Gets the visualization info of every fixture in the show
+	pub async fn get_visualization_data(&self) -> FxHashMap<Uuid, VisualizationInfo> {
+		let ctx = self.1.read().await;
+		return ctx
+			.sharable
+			.fixtures
+			.iter()
+			.map(|(fixture_id, fixture)| (*fixture_id, fixture.visualization_info.clone()))
+			.collect();
+	}
+
+	/// Gets the fixture instance each cell belongs to, keyed by cell ID
+	pub async fn get_cell_parents(&self) -> FxHashMap<Uuid, Uuid> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.cells.values().map(|cell| (cell.id, cell.parent)).collect();
 	}
 
 	/// Gets the position of a fixture within the visualizer
@@ -373,6 +645,654 @@ impl PatcherInterface {
 		}
 	}
 
+	/// Gets the transforms applied to a fixture's output
+	pub async fn get_fixture_transforms(&self, instance_id: &Uuid) -> Option<FixtureTransforms> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.fixtures.get(instance_id).map(|fixture| fixture.transforms.clone());
+	}
+
+	/// Replaces the transforms applied to a fixture's output
+	pub async fn set_fixture_transforms(&self, instance_id: &Uuid, transforms: FixtureTransforms) -> Result<(), FixtureTransformsError> {
+		let mut ctx = self.1.write().await;
+		let ctx = &mut *ctx;
+
+		let fixture = ctx.sharable.fixtures.get_mut(instance_id).ok_or(FixtureTransformsError::FixtureMissing)?;
+		let fixture_type = ctx.sharable.library.get(&fixture.fixture_id).ok_or(FixtureTransformsError::FixtureTypeMissing)?;
+
+		for channel_id in transforms.inverted.iter().chain(transforms.limits.keys()) {
+			if !fixture_type.channels.contains_key(channel_id) {
+				return Err(FixtureTransformsError::UnknownChannel(channel_id.clone()));
+			}
+		}
+		for (channel_id, limits) in transforms.limits.iter() {
+			if limits.min > limits.max {
+				return Err(FixtureTransformsError::InvalidLimits(channel_id.clone()));
+			}
+		}
+
+		fixture.transforms = transforms;
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Gets a fixture's software strobe settings, or `None` if the fixture doesn't exist or has no software strobe
+	pub async fn get_software_strobe(&self, instance_id: &Uuid) -> Option<SoftwareStrobe> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.fixtures.get(instance_id)?.software_strobe;
+	}
+
+	/// Enables, changes, or disables the software strobe of several fixtures.
+	///
+	/// Enabling the strobe adds the fixtures' virtual strobe attributes, and disabling it removes them. No fixtures are
+	/// changed if any of them can't use a software strobe.
+	pub async fn set_software_strobe(&self, instance_ids: &[Uuid], strobe: Option<SoftwareStrobe>) -> Result<(), SoftwareStrobeError> {
+		let mut ctx = self.1.write().await;
+
+		if let Some(ref strobe) = strobe {
+			if !strobe.max_rate.is_finite() || strobe.max_rate <= 0.0 {
+				return Err(SoftwareStrobeError::InvalidRate);
+			}
+		}
+		for instance_id in instance_ids {
+			let fixture = ctx.sharable.fixtures.get(instance_id).ok_or(SoftwareStrobeError::FixtureMissing(*instance_id))?;
+			let fixture_type = ctx.sharable.library.get(&fixture.fixture_id).ok_or(SoftwareStrobeError::FixtureTypeMissing)?;
+			let has_intensity = fixture_type
+				.control_groups
+				.iter()
+				.any(|control_group| matches!(control_group.get_channels(), ControlGroupData::Intensity(_)));
+			if strobe.is_some() && !has_intensity {
+				return Err(SoftwareStrobeError::NoIntensity(*instance_id));
+			}
+		}
+
+		for instance_id in instance_ids {
+			if let Some(fixture) = ctx.sharable.fixtures.get_mut(instance_id) {
+				fixture.software_strobe = strobe;
+			}
+		}
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Lists the dimmer curves available to the show, sorted by name
+	pub async fn list_dimmer_curves(&self) -> Vec<DimmerCurve> {
+		let ctx = self.1.read().await;
+		let mut curves: Vec<DimmerCurve> = ctx.sharable.curves.values().cloned().collect();
+		curves.sort_by(|a, b| a.name.cmp(&b.name));
+		return curves;
+	}
+
+	/// Adds a dimmer curve to the show, returning its ID
+	pub async fn create_dimmer_curve(&self, name: String, mut shape: CurveShape) -> Result<Uuid, DimmerCurveError> {
+		shape.prepare()?;
+		let mut ctx = self.1.write().await;
+		let id = Uuid::new_v4();
+		ctx.sharable.curves.insert(id, DimmerCurve { id, name, shape });
+		return Ok(id);
+	}
+
+	/// Changes the name and shape of a dimmer curve
+	pub async fn edit_dimmer_curve(&self, curve_id: &Uuid, name: String, mut shape: CurveShape) -> Result<(), DimmerCurveError> {
+		shape.prepare()?;
+		let mut ctx = self.1.write().await;
+		let curve = ctx.sharable.curves.get_mut(curve_id).ok_or(DimmerCurveError::CurveMissing)?;
+		curve.name = name;
+		curve.shape = shape;
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Removes a dimmer curve from the show, along with every assignment of it
+	pub async fn delete_dimmer_curve(&self, curve_id: &Uuid) -> Result<(), DimmerCurveError> {
+		let mut ctx = self.1.write().await;
+		ctx.sharable.curves.remove(curve_id).ok_or(DimmerCurveError::CurveMissing)?;
+		for fixture in ctx.sharable.fixtures.values_mut() {
+			if fixture.dimmer_curve == Some(*curve_id) {
+				fixture.dimmer_curve = None;
+			}
+		}
+		for channel_curves in ctx.sharable.channel_curves.values_mut() {
+			channel_curves.retain(|_, assigned_curve| assigned_curve != curve_id);
+		}
+		ctx.sharable.channel_curves.retain(|_, channel_curves| !channel_curves.is_empty());
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Assigns a dimmer curve to a fixture's intensity channels, or removes its curve if `curve_id` is `None`
+	pub async fn set_fixture_dimmer_curve(&self, instance_id: &Uuid, curve_id: Option<Uuid>) -> Result<(), DimmerCurveError> {
+		let mut ctx = self.1.write().await;
+		if curve_id.is_some_and(|curve_id| !ctx.sharable.curves.contains_key(&curve_id)) {
+			return Err(DimmerCurveError::CurveMissing);
+		}
+		let fixture = ctx.sharable.fixtures.get_mut(instance_id).ok_or(DimmerCurveError::FixtureMissing)?;
+		fixture.dimmer_curve = curve_id;
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Assigns a dimmer curve to a channel of every instance of a fixture type, or removes the channel's curve
+	/// if `curve_id` is `None`
+	pub async fn set_channel_dimmer_curve(&self, fixture_type: &Uuid, channel_id: String, curve_id: Option<Uuid>) -> Result<(), DimmerCurveError> {
+		let mut ctx = self.1.write().await;
+		if curve_id.is_some_and(|curve_id| !ctx.sharable.curves.contains_key(&curve_id)) {
+			return Err(DimmerCurveError::CurveMissing);
+		}
+		let fixture_type_info = ctx.sharable.library.get(fixture_type).ok_or(DimmerCurveError::FixtureTypeMissing)?;
+		if !fixture_type_info.channels.contains_key(&channel_id) {
+			return Err(DimmerCurveError::UnknownChannel(channel_id));
+		}
+
+		match curve_id {
+			Some(curve_id) => {
+				ctx.sharable.channel_curves.entry(*fixture_type).or_default().insert(channel_id, curve_id);
+			}
+			None => {
+				if let Some(channel_curves) = ctx.sharable.channel_curves.get_mut(fixture_type) {
+					channel_curves.remove(&channel_id);
+					if channel_curves.is_empty() {
+						ctx.sharable.channel_curves.remove(fixture_type);
+					}
+				}
+			}
+		}
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Changes a fixture's number, or removes its number if `number` is `None`
+	pub async fn renumber_fixture(&self, instance_id: &Uuid, number: Option<FixtureNumber>) -> Result<(), RenumberFixtureError> {
+		return self.renumber_fixtures(&[(*instance_id, number)]).await;
+	}
+
+	/// Changes the numbers of several fixtures at once.
+	///
+	/// Fixtures may swap numbers with each other, but no fixture may take a number that is in use by a fixture
+	/// outside of `numbers`. If any number conflicts, no fixtures are renumbered.
+	pub async fn renumber_fixtures(&self, numbers: &[(Uuid, Option<FixtureNumber>)]) -> Result<(), RenumberFixtureError> {
+		let mut ctx = self.1.write().await;
+
+		let mut new_numbers: FxHashMap<Uuid, Option<FixtureNumber>> = FxHashMap::default();
+		for (instance_id, number) in numbers.iter() {
+			if !ctx.sharable.fixtures.contains_key(instance_id) {
+				return Err(RenumberFixtureError::FixtureMissing(*instance_id));
+			}
+			new_numbers.insert(*instance_id, *number);
+		}
+
+		// Check the resulting numbers for duplicates, including the numbers of cells
+		let mut used_numbers: FxHashMap<FixtureNumber, Uuid> = FxHashMap::default();
+		for (number, fixture_id) in ctx.sharable.numbered_fixtures(&new_numbers) {
+			if used_numbers.insert(number, fixture_id).is_some() {
+				return Err(RenumberFixtureError::DuplicateNumber(number));
+			}
+		}
+
+		for (instance_id, number) in new_numbers {
+			if let Some(fixture) = ctx.sharable.fixtures.get_mut(&instance_id) {
+				fixture.number = number;
+			}
+		}
+
+		self.0
+			.emit("patcher.patch_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Assigns sequential numbers to several fixtures in the order given, starting at `start`
+	pub async fn renumber_sequence(&self, instance_ids: &[Uuid], start: FixtureNumber, increment: u32) -> Result<(), RenumberFixtureError> {
+		let mut numbers = Vec::with_capacity(instance_ids.len());
+		for (index, instance_id) in instance_ids.iter().enumerate() {
+			let number = (index as u32)
+				.checked_mul(increment)
+				.and_then(|offset| start.number.checked_add(offset))
+				.ok_or(RenumberFixtureError::NumberOutOfRange)?;
+			numbers.push((*instance_id, Some(FixtureNumber { number, part: start.part })));
+		}
+		return self.renumber_fixtures(&numbers).await;
+	}
+
+	/// Finds the fixture or cell with the given number
+	pub async fn find_fixture_by_number(&self, number: &FixtureNumber) -> Option<Uuid> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.find_fixture_by_number(number);
+	}
+
+	/// Finds the fixtures and cells with numbers in an inclusive range, sorted by number.
+	///
+	/// If `end` is a whole number, the parts of that fixture are included as well.
+	pub async fn find_fixtures_by_range(&self, start: &FixtureNumber, end: &FixtureNumber) -> Vec<Uuid> {
+		let ctx = self.1.read().await;
+		let mut fixtures: Vec<(FixtureNumber, Uuid)> = ctx
+			.sharable
+			.numbered_fixtures(&FxHashMap::default())
+			.into_iter()
+			.filter(|(number, _)| number.in_range(start, end))
+			.collect();
+		fixtures.sort();
+		return fixtures.into_iter().map(|(_, fixture_id)| fixture_id).collect();
+	}
+
+	/// Lists the cells of a fixture, which are controlled as sub-fixtures, in the order they are defined
+	pub async fn get_fixture_cells(&self, instance_id: &Uuid) -> Vec<SubFixture> {
+		let ctx = self.1.read().await;
+		let number = ctx.sharable.fixtures.get(instance_id).and_then(|fixture| fixture.number);
+		let mut cells: Vec<(usize, SubFixture)> = ctx
+			.sharable
+			.cells
+			.values()
+			.filter(|cell| cell.parent == *instance_id)
+			.filter_map(|cell| {
+				let (index, cell_info) = ctx.sharable.get_cell_definition(cell)?;
+				return Some((
+					index,
+					SubFixture {
+						id: cell.id,
+						cell: cell_info.id.clone(),
+						name: cell_info.name.clone(),
+						number: number.and_then(|number| number.with_cell(index)),
+						position: cell_info.position,
+					},
+				));
+			})
+			.collect();
+		cells.sort_by_key(|(index, _)| *index);
+		return cells.into_iter().map(|(_, cell)| cell).collect();
+	}
+
+	/// Gets the controls offered by each fixture or cell in a selection, in selection order.
+	///
+	/// IDs that don't belong to a fixture or cell are skipped.
+	pub async fn get_fixture_controls(&self, fixture_ids: &[Uuid]) -> Vec<FixtureControls> {
+		let ctx = self.1.read().await;
+		return fixture_ids
+			.iter()
+			.filter_map(|fixture_id| {
+				return ctx.sharable.get_controls(fixture_id).map(|controls| FixtureControls {
+					fixture: *fixture_id,
+					controls,
+				});
+			})
+			.collect();
+	}
+
+	/// Finds the fixtures and cells in a selection that offer a capability, in selection order
+	pub async fn filter_fixtures_by_capability(&self, fixture_ids: &[Uuid], capability: Capability) -> Vec<Uuid> {
+		let ctx = self.1.read().await;
+		return fixture_ids
+			.iter()
+			.filter(|fixture_id| {
+				ctx.sharable
+					.get_controls(fixture_id)
+					.is_some_and(|controls| controls.iter().any(|control| control.capability == capability))
+			})
+			.copied()
+			.collect();
+	}
+
+	/// Lists every capability offered by a selection, along with the fixtures and cells that offer it
+	pub async fn get_selection_capabilities(&self, fixture_ids: &[Uuid]) -> Vec<CapabilitySummary> {
+		let ctx = self.1.read().await;
+		let mut capabilities: Vec<CapabilitySummary> = Vec::new();
+		for fixture_id in fixture_ids {
+			let controls = unwrap_continue!(ctx.sharable.get_controls(fixture_id));
+			for control in controls {
+				match capabilities.iter_mut().find(|summary| summary.capability == control.capability) {
+					Some(summary) => {
+						if !summary.fixtures.contains(fixture_id) {
+							summary.fixtures.push(*fixture_id);
+						}
+					}
+					None => capabilities.push(CapabilitySummary {
+						capability: control.capability,
+						fixtures: vec![*fixture_id],
+					}),
+				}
+			}
+		}
+		capabilities.sort_by_key(|summary| summary.capability);
+		return capabilities;
+	}
+
+	/// Lists the segments of a fixture's or cell's attribute, such as the slots of a gobo wheel.
+	///
+	/// Returns `None` if the attribute doesn't exist or isn't segmented.
+	pub async fn get_attribute_segments(&self, fixture_id: &Uuid, attribute_id: &str) -> Option<Vec<Segment>> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.get_attribute(fixture_id, attribute_id)?.get_segments().cloned();
+	}
+
+	/// Finds the value of a named segment for each fixture or cell in a selection, since the same segment can sit
+	/// at a different value on each fixture type.
+	///
+	/// Segment names are matched ignoring case. Fixtures without a matching segment are left out.
+	pub async fn resolve_segment_values(&self, fixture_ids: &[Uuid], attribute_id: &str, segment_name: &str) -> FxHashMap<Uuid, u16> {
+		let ctx = self.1.read().await;
+		return fixture_ids
+			.iter()
+			.filter_map(|fixture_id| {
+				let segment = ctx.sharable.get_attribute(fixture_id, attribute_id)?.find_segment(segment_name)?;
+				return Some((*fixture_id, segment.center()));
+			})
+			.collect();
+	}
+
+	/// Finds the segment an attribute's value falls into, for display
+	pub async fn describe_attribute_value(&self, fixture_id: &Uuid, attribute_id: &str, value: u16) -> Option<Segment> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.get_attribute(fixture_id, attribute_id)?.segment_at(value).cloned();
+	}
+
+	/// Converts an attribute's raw value to its physical unit, for display.
+	///
+	/// Returns `None` if the attribute doesn't exist or has no physical range.
+	pub async fn get_physical_value(&self, fixture_id: &Uuid, attribute_id: &str, value: u16) -> Option<PhysicalValue> {
+		let ctx = self.1.read().await;
+		return ctx.sharable.get_attribute(fixture_id, attribute_id)?.to_physical(value);
+	}
+
+	/// Converts a physical value to the raw value of each fixture or cell in a selection, since each fixture type
+	/// can map the same physical value to a different raw value.
+	///
+	/// Fixtures whose attribute doesn't use the value's unit are left out.
+	pub async fn resolve_physical_values(&self, fixture_ids: &[Uuid], attribute_id: &str, value: &PhysicalValue) -> FxHashMap<Uuid, u16> {
+		let ctx = self.1.read().await;
+		return fixture_ids
+			.iter()
+			.filter_map(|fixture_id| {
+				let raw_value = ctx.sharable.get_attribute(fixture_id, attribute_id)?.from_physical(value)?;
+				return Some((*fixture_id, raw_value));
+			})
+			.collect();
+	}
+
+	/// Searches the user's fixture library
+	pub async fn search_library(&self, query: String, manufacturer: Option<String>, family: Option<String>) -> Vec<LibraryEntry> {
+		let ctx = self.1.read().await;
+		return ctx.fixture_library.search(&query, manufacturer.as_deref(), family.as_deref());
+	}
+
+	/// Copies a fixture type from the show into the user's fixture library
+	pub async fn add_to_library(&self, fixture_id: &Uuid) -> Result<LibraryEntry, FixtureLibraryError> {
+		let ctx = self.1.read().await;
+		let fixture_info = ctx.sharable.library.get(fixture_id).ok_or(FixtureLibraryError::FixtureMissing)?.clone();
+		let controller = ctx
+			.output_drivers
+			.get(&fixture_info.output_driver)
+			.ok_or(FixtureLibraryError::ControllerMissing)?
+			.clone();
+		if let Some(existing) = ctx.fixture_library.get(fixture_id) {
+			if existing.version > fixture_info.version {
+				return Err(FixtureLibraryError::NewerVersionExists(existing.version));
+			}
+		}
+		let path = ctx.fixture_library.bundle_path(fixture_id)?;
+		drop(ctx);
+
+		// Disk access happens without holding the lock so output isn't blocked
+		let output_info = controller
+			.export_fixture_json(fixture_id)
+			.await
+			.map_err(|error| FixtureLibraryError::ExportFailed(error.to_string()))?;
+		let bundle = FixtureBundle {
+			fixture_info,
+			output_info: SerializedData::JSON(output_info),
+		};
+		write_bundle(path, &bundle).await?;
+
+		let entry = LibraryEntry::from(&bundle.fixture_info);
+		self.1.write().await.fixture_library.insert(entry.clone());
+		self.0
+			.emit("patcher.library_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(entry);
+	}
+
+	/// Imports a fixture type from the user's fixture library into the show, replacing any older copy
+	pub async fn load_from_library(&self, fixture_id: &Uuid) -> Result<(), FixtureLibraryError> {
+		let ctx = self.1.read().await;
+		if ctx.fixture_library.get(fixture_id).is_none() {
+			return Err(FixtureLibraryError::FixtureMissing);
+		}
+		let path = ctx.fixture_library.bundle_path(fixture_id)?;
+		drop(ctx);
+
+		let bundle = read_bundle(path).await?;
+
+		let ctx = self.1.read().await;
+		if let Some(existing) = ctx.sharable.library.get(fixture_id) {
+			if existing.version > bundle.fixture_info.version {
+				return Err(FixtureLibraryError::NewerVersionExists(existing.version));
+			}
+		}
+		drop(ctx);
+
+		// Fixtures in the show are checked against the new version as it is imported
+		self.import_fixture(bundle).await.map_err(FixtureLibraryError::ImportFailed)?;
+		return Ok(());
+	}
+
+	/// Deletes a fixture type from the user's fixture library. Shows that use it keep their own copy.
+	pub async fn remove_from_library(&self, fixture_id: &Uuid) -> Result<(), FixtureLibraryError> {
+		let ctx = self.1.read().await;
+		if ctx.fixture_library.get(fixture_id).is_none() {
+			return Err(FixtureLibraryError::FixtureMissing);
+		}
+		let path = ctx.fixture_library.bundle_path(fixture_id)?;
+		drop(ctx);
+
+		tokio::fs::remove_file(path)
+			.await
+			.map_err(|error| FixtureLibraryError::Io(error.to_string()))?;
+		self.1.write().await.fixture_library.remove(fixture_id);
+		self.0
+			.emit("patcher.library_updated".into(), FilterCriteria::None, ())
+			.await;
+		return Ok(());
+	}
+
+	/// Lists fixture types in the show that are older than the copy in the user's fixture library
+	pub async fn get_outdated_fixtures(&self) -> Vec<OutdatedFixture> {
+		let ctx = self.1.read().await;
+		let mut outdated: Vec<OutdatedFixture> = ctx
+			.sharable
+			.library
+			.values()
+			.filter_map(|fixture_info| {
+				let entry = ctx.fixture_library.get(&fixture_info.id)?;
+				if entry.version > fixture_info.version {
+					return Some(OutdatedFixture {
+						fixture_id: fixture_info.id,
+						name: fixture_info.name.clone(),
+						show_version: fixture_info.version,
+						library_version: entry.version,
+					});
+				}
+				return None;
+			})
+			.collect();
+		outdated.sort_by(|a, b| a.name.cmp(&b.name));
+		return outdated;
+	}
+
+	/// Moves the values of each cell into its parent fixture's channels, so output drivers only see whole fixtures
+	fn fold_cells(data: Arc<FullMixerOutput>, ctx: &PatcherContext) -> Arc<FullMixerOutput> {
+		if ctx.sharable.cells.is_empty() {
+			return data;
+		}
+
+		let mut new_data = (*data).clone();
+		for cell in ctx.sharable.cells.values() {
+			let cell_values = unwrap_continue!(new_data.remove(&cell.id));
+			let (_, cell_info) = unwrap_continue!(ctx.sharable.get_cell_definition(cell));
+			let parent_values = new_data.entry(cell.parent).or_default();
+			for (attribute_id, channel_id) in cell_info.attributes.iter() {
+				if let Some(value) = cell_values.get(attribute_id) {
+					parent_values.insert(channel_id.clone(), *value);
+				}
+			}
+		}
+		return Arc::new(new_data);
+	}
+
+	/// Applies the dimmer curves assigned to each fixture instance and fixture type
+	fn apply_dimmer_curves(data: Arc<FullMixerOutput>, ctx: &PatcherContext) -> Arc<FullMixerOutput> {
+		if ctx.sharable.curves.is_empty() {
+			return data;
+		}
+
+		let mut new_data = (*data).clone();
+		for (fixture_id, fixture_values) in new_data.iter_mut() {
+			let fixture_instance = unwrap_continue!(ctx.sharable.fixtures.get(fixture_id));
+			let fixture_type = unwrap_continue!(ctx.sharable.library.get(&fixture_instance.fixture_id));
+			let channel_curves = ctx.sharable.channel_curves.get(&fixture_instance.fixture_id);
+			if fixture_instance.dimmer_curve.is_none() && channel_curves.is_none() {
+				continue;
+			}
+
+			for (channel_id, value) in fixture_values.iter_mut() {
+				let channel = unwrap_continue!(fixture_type.channels.get(channel_id));
+				let is_intensity = fixture_type.control_groups.iter().any(|control_group| {
+					matches!(control_group.get_channels(), ControlGroupData::Intensity(intensity_channel) if intensity_channel == channel_id)
+				});
+				let curve_id = unwrap_continue!(fixture_instance
+					.dimmer_curve
+					.filter(|_| is_intensity)
+					.or_else(|| channel_curves.and_then(|channel_curves| channel_curves.get(channel_id).copied())));
+				let curve = unwrap_continue!(ctx.sharable.curves.get(&curve_id));
+				*value = curve.shape.apply(*value, channel.size.max_value());
+			}
+		}
+		return Arc::new(new_data);
+	}
+
+	/// Applies each fixture instance's transforms, such as inverted or swapped pan and tilt
+	fn apply_transforms(data: Arc<FullMixerOutput>, ctx: &PatcherContext) -> Arc<FullMixerOutput> {
+		if ctx.sharable.fixtures.values().all(|fixture| fixture.transforms.is_empty()) {
+			return data;
+		}
+
+		let mut new_data = (*data).clone();
+		for (fixture_id, fixture_values) in new_data.iter_mut() {
+			let fixture_instance = unwrap_continue!(ctx.sharable.fixtures.get(fixture_id));
+			let transforms = &fixture_instance.transforms;
+			if transforms.is_empty() {
+				continue;
+			}
+			let fixture_type = unwrap_continue!(ctx.sharable.library.get(&fixture_instance.fixture_id));
+			let get_size = |channel_id: &str| fixture_type.channels.get(channel_id).map(|channel| &channel.size);
+
+			if transforms.swap_pan_tilt {
+				for control_group in fixture_type.control_groups.iter() {
+					if let ControlGroupData::PanTilt { pan, tilt } = control_group.get_channels() {
+						let (pan_size, tilt_size) = unwrap_continue!(get_size(pan).zip(get_size(tilt)));
+						let pan_value = fixture_values.get(pan).copied();
+						let tilt_value = fixture_values.get(tilt).copied();
+						if let Some(value) = tilt_value {
+							fixture_values.insert(pan.clone(), rescale(value, tilt_size, pan_size));
+						}
+						if let Some(value) = pan_value {
+							fixture_values.insert(tilt.clone(), rescale(value, pan_size, tilt_size));
+						}
+					}
+				}
+			}
+
+			for channel_id in transforms.inverted.iter() {
+				let size = unwrap_continue!(get_size(channel_id));
+				if let Some(value) = fixture_values.get_mut(channel_id) {
+					*value = size.max_value().saturating_sub(*value);
+				}
+			}
+
+			for (channel_id, limits) in transforms.limits.iter() {
+				if let Some(value) = fixture_values.get_mut(channel_id) {
+					*value = (*value).min(limits.max).max(limits.min);
+				}
+			}
+
+			if let Some(intensity_cap) = transforms.intensity_cap {
+				for control_group in fixture_type.control_groups.iter() {
+					if let ControlGroupData::Intensity(channel_id) = control_group.get_channels() {
+						let size = unwrap_continue!(get_size(channel_id));
+						if let Some(value) = fixture_values.get_mut(channel_id) {
+							let cap = rescale(intensity_cap, &ChannelSize::U16, size);
+							*value = (*value as u32 * cap as u32 / size.max_value() as u32) as u16;
+						}
+					}
+				}
+			}
+		}
+		return Arc::new(new_data);
+	}
+
+	/// Flashes the intensity channels of fixtures with a running software strobe, removing the strobe's virtual
+	/// attributes from the output.
+	///
+	/// Also returns whether any strobe is running, since the output then changes between frames. Strobes that stop
+	/// running lose their phase, so they start lit when they're next started.
+	fn apply_software_strobes(
+		data: Arc<FullMixerOutput>,
+		ctx: &PatcherContext,
+		frame: &FrameInfo,
+		strobe_phases: &mut FxHashMap<Uuid, f64>,
+	) -> (Arc<FullMixerOutput>, bool) {
+		if ctx.sharable.fixtures.values().all(|fixture| fixture.software_strobe.is_none()) {
+			strobe_phases.clear();
+			return (data, false);
+		}
+
+		let mut running = false;
+		let mut new_phases = FxHashMap::default();
+		let mut new_data = (*data).clone();
+		for (fixture_id, fixture_values) in new_data.iter_mut() {
+			let fixture_instance = unwrap_continue!(ctx.sharable.fixtures.get(fixture_id));
+			let strobe = unwrap_continue!(fixture_instance.software_strobe);
+			let rate = fixture_values.remove(STROBE_RATE_ATTRIBUTE).unwrap_or(0);
+			let duty = fixture_values.remove(STROBE_DUTY_ATTRIBUTE).unwrap_or(u16::MAX / 2);
+			if rate == 0 {
+				continue;
+			}
+			running = true;
+			let phase = new_phases
+				.entry(*fixture_id)
+				.or_insert_with(|| strobe_phases.get(fixture_id).copied().unwrap_or(0.0));
+			if strobe.is_lit(rate, duty, frame, phase) {
+				continue;
+			}
+
+			let fixture_type = unwrap_continue!(ctx.sharable.library.get(&fixture_instance.fixture_id));
+			for control_group in fixture_type.control_groups.iter() {
+				if let ControlGroupData::Intensity(channel_id) = control_group.get_channels() {
+					if let Some(value) = fixture_values.get_mut(channel_id) {
+						*value = 0;
+					}
+				}
+			}
+		}
+		*strobe_phases = new_phases;
+		return (Arc::new(new_data), running);
+	}
+
 	/// Applies any virtual intensity channels defined in each fixture's
 	/// type definition
 	fn apply_virtual_intensities(
@@ -428,10 +1348,17 @@ impl PatcherInterface {
 		return Arc::new(new_data);
 	}
 
-	/// Write values to the output plugins
-	pub async fn write_values(&self, data: Arc<FullMixerOutput>) {
+	/// Write values to the output plugins.
+	///
+	/// Returns whether the output changes over time, such as while a software strobe is running, in which case
+	/// frames should keep being written even if the mixer's output doesn't change.
+	pub async fn write_values(&self, data: Arc<FullMixerOutput>, frame: &FrameInfo) -> bool {
 		let ctx = self.1.read().await;
 
+		let data = Self::fold_cells(data, &ctx);
+		let data = Self::apply_dimmer_curves(data, &ctx);
+		let data = Self::apply_transforms(data, &ctx);
+		let (data, animated) = Self::apply_software_strobes(data, &ctx, frame, &mut *ctx.strobe_phases.lock().await);
 		let data = Self::apply_virtual_intensities(&data, &ctx);
 
 		let mut futures = JoinSet::new();
@@ -449,6 +1376,7 @@ impl PatcherInterface {
 			// and the entire system is compromised.
 			result.unwrap();
 		}
+		return animated;
 	}
 
 	pub async fn get_sharable_state<'a>(&'a self) -> SharableStateWrapper<'a> {
@@ -465,10 +1393,77 @@ impl Savable for PatcherInterface {
 	}
 }
 
-fn get_max_value(channel_size: &ChannelSize) -> u16 {
-	return match channel_size {
-		ChannelSize::U8 => 255,
-		ChannelSize::U16 => 65535,
+/// Gets the default value and blending instructions for a channel
+fn get_channel_base(channel_info: &Channel) -> (u16, BlendingData) {
+	return match &channel_info.ch_type {
+		ChannelType::Linear { priority } => (
+			channel_info.default,
+			BlendingData {
+				scheme: priority.clone(),
+				snap: SnapData::NoSnap,
+				allow_wrap: false,
+				max_value: channel_info.size.max_value(),
+				min_value: 0,
+			},
+		),
+		ChannelType::Segmented {
+			segments,
+			priority,
+			snapping,
+		} => (
+			channel_info.default,
+			BlendingData {
+				scheme: priority.clone(),
+				snap: snapping.clone().unwrap_or(SnapData::NoSnap),
+				allow_wrap: false,
+				max_value: get_max_value_segments(segments),
+				min_value: get_min_value_segments(segments),
+			},
+		),
+	};
+}
+
+/// Scales a value between two channel sizes, so the same fraction of the channel's range is used
+fn rescale(value: u16, from: &ChannelSize, to: &ChannelSize) -> u16 {
+	return (value as u32 * to.max_value() as u32 / from.max_value() as u32) as u16;
+}
+
+/// Moves a fixture's transforms to a new fixture type, renaming channels with `attribute_map`.
+///
+/// Channels without a match are dropped, and limits are scaled to the size of the new channel.
+fn migrate_transforms(
+	transforms: &FixtureTransforms,
+	attribute_map: &FxHashMap<String, String>,
+	old_type: &FixtureInfo,
+	new_type: &FixtureInfo,
+) -> FixtureTransforms {
+	let mut inverted: Vec<String> = Vec::new();
+	for channel_id in transforms.inverted.iter().filter_map(|channel_id| attribute_map.get(channel_id)) {
+		if !inverted.contains(channel_id) {
+			inverted.push(channel_id.clone());
+		}
+	}
+	let limits = transforms
+		.limits
+		.iter()
+		.filter_map(|(channel_id, limits)| {
+			let new_channel_id = attribute_map.get(channel_id)?;
+			let from = &old_type.channels.get(channel_id)?.size;
+			let to = &new_type.channels.get(new_channel_id)?.size;
+			return Some((
+				new_channel_id.clone(),
+				ChannelLimits {
+					min: rescale(limits.min, from, to),
+					max: rescale(limits.max, from, to),
+				},
+			));
+		})
+		.collect();
+	return FixtureTransforms {
+		swap_pan_tilt: transforms.swap_pan_tilt,
+		inverted,
+		limits,
+		intensity_cap: transforms.intensity_cap,
 	};
 }
 
@@ -508,8 +1503,52 @@ fn get_min_value_segments(segments: &[Segment]) -> u16 {
 pub enum ImportFixtureError {
 	#[error("Could not find the controller referenced by the fixture definition")]
 	UnknownController,
+	#[error("The fixture definition is invalid:\n{}", format_validation_errors(.0))]
+	InvalidDefinition(Vec<FixtureValidationError>),
 	#[error("An error occured while importing controller-specific details:\n{0:?}")]
 	ErrorFromController(driver_plugin_api::ImportError),
+	#[error("Fixture {0} uses personality {1:?}, which the new definition removes")]
+	PersonalityInUse(Uuid, String),
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while changing the software strobe of fixtures
+pub enum SoftwareStrobeError {
+	#[error("Fixture {0} does not exist")]
+	FixtureMissing(Uuid),
+	#[error("[Internal state error]: The fixture definition is missing for the requested fixture type")]
+	FixtureTypeMissing,
+	#[error("Fixture {0} has no intensity channel for the strobe to flash")]
+	NoIntensity(Uuid),
+	#[error("The strobe's maximum rate must be greater than zero")]
+	InvalidRate,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while changing a fixture's transforms
+pub enum FixtureTransformsError {
+	#[error("This fixture does not exist")]
+	FixtureMissing,
+	#[error("[Internal state error]: The fixture definition is missing for the requested fixture type")]
+	FixtureTypeMissing,
+	#[error("Channel {0:?} does not exist in the fixture's definition")]
+	UnknownChannel(String),
+	#[error("The minimum limit of channel {0:?} is greater than its maximum")]
+	InvalidLimits(String),
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while changing fixture numbers
+pub enum RenumberFixtureError {
+	#[error("Fixture {0} does not exist")]
+	FixtureMissing(Uuid),
+	#[error("Fixture number {0} would be used by more than one fixture")]
+	DuplicateNumber(FixtureNumber),
+	#[error("The requested fixture numbers are too large")]
+	NumberOutOfRange,
 }
 
 #[portable]
@@ -564,10 +1603,32 @@ pub enum EditFixtureError {
 	FixtureTypeMissing,
 	#[error("[Internal state error]: The controller responsible for this fixture is missing")]
 	ControllerMissing,
+	#[error("The requested personality does not exist in the fixture's definition")]
+	PersonalityMissing,
 	#[error("The controller reported an error while creating an instance of the fixture:\n{0:?}")]
 	ErrorFromController(driver_plugin_api::EditInstanceError),
 }
 
+#[portable]
+#[derive(Error)]
+/// An error that could occur when replacing the fixture type of existing fixtures
+pub enum ReplaceFixtureTypeError {
+	#[error("Fixture {0} does not exist")]
+	FixtureMissing(Uuid),
+	#[error("The requested fixture type does not exist in the library")]
+	FixtureTypeMissing,
+	#[error("The requested personality does not exist in the new fixture type")]
+	PersonalityMissing,
+	#[error("The controller responsible for this fixture is missing")]
+	ControllerMissing,
+	#[error("Fixtures can't be moved to a fixture type that uses a different output driver")]
+	DriverMismatch,
+	#[error("Attribute {0:?} does not exist in the new fixture type's personality")]
+	UnknownAttribute(String),
+	#[error("The controller rejected the new fixture type for fixture {0}:\n{1:?}")]
+	ErrorFromController(Uuid, driver_plugin_api::EditInstanceError),
+}
+
 #[portable]
 #[derive(Error)]
 /// An error that could occur when removing a fixture
@@ -0,0 +1,173 @@
+//! The user's fixture library, stored on disk as a directory of fixture bundles.
+//!
+//! Each show embeds its own copy of the fixture types it uses. The library is shared between shows,
+//! so fixture types only need to be imported once. Only an index of the library is kept in memory.
+
+use std::path::PathBuf;
+
+use rustc_hash::FxHashMap;
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::{
+	fixture_types::{FixtureBundle, FixtureInfo},
+	interface::ImportFixtureError,
+};
+
+/// The in-memory index of the fixture library
+pub struct FixtureLibrary {
+	/// The directory the library is stored in, or `None` if no suitable directory could be found
+	directory: Option<PathBuf>,
+	entries: FxHashMap<Uuid, LibraryEntry>,
+}
+
+impl FixtureLibrary {
+	/// Gets the default location of the fixture library in the user's data directory
+	pub fn default_directory() -> Option<PathBuf> {
+		return dirs_next::data_dir().map(|data_dir| data_dir.join("SimplyDMX").join("fixture_library"));
+	}
+
+	/// Builds the library's index from the bundles in `directory`. Files that can't be read are skipped.
+	pub async fn load(directory: Option<PathBuf>) -> FixtureLibrary {
+		let mut entries = FxHashMap::default();
+		if let Some(ref directory) = directory {
+			if let Ok(mut dir_entries) = tokio::fs::read_dir(directory).await {
+				while let Ok(Some(dir_entry)) = dir_entries.next_entry().await {
+					let path = dir_entry.path();
+					if path.extension().is_some_and(|extension| extension == "json") {
+						if let Ok(bundle) = read_bundle(path).await {
+							let entry = LibraryEntry::from(&bundle.fixture_info);
+							entries.insert(entry.id, entry);
+						}
+					}
+				}
+			}
+		}
+		return FixtureLibrary { directory, entries };
+	}
+
+	/// Gets the path of the bundle file for a fixture type
+	pub fn bundle_path(&self, fixture_id: &Uuid) -> Result<PathBuf, FixtureLibraryError> {
+		return self
+			.directory
+			.as_ref()
+			.map(|directory| directory.join(format!("{}.json", fixture_id)))
+			.ok_or(FixtureLibraryError::Unavailable);
+	}
+
+	pub fn get(&self, fixture_id: &Uuid) -> Option<&LibraryEntry> {
+		return self.entries.get(fixture_id);
+	}
+
+	pub fn insert(&mut self, entry: LibraryEntry) {
+		self.entries.insert(entry.id, entry);
+	}
+
+	pub fn remove(&mut self, fixture_id: &Uuid) -> Option<LibraryEntry> {
+		return self.entries.remove(fixture_id);
+	}
+
+	/// Searches the library, sorted by manufacturer, family, then name.
+	///
+	/// `query` is matched case-insensitively against the name, short name, manufacturer, and family.
+	pub fn search(&self, query: &str, manufacturer: Option<&str>, family: Option<&str>) -> Vec<LibraryEntry> {
+		let query = query.to_lowercase();
+		let mut results: Vec<LibraryEntry> = self
+			.entries
+			.values()
+			.filter(|entry| manufacturer.is_none() || entry.manufacturer.as_deref() == manufacturer)
+			.filter(|entry| family.is_none() || entry.family.as_deref() == family)
+			.filter(|entry| {
+				query.is_empty()
+					|| [Some(&entry.name), entry.short_name.as_ref(), entry.manufacturer.as_ref(), entry.family.as_ref()]
+						.into_iter()
+						.flatten()
+						.any(|field| field.to_lowercase().contains(&query))
+			})
+			.cloned()
+			.collect();
+		results.sort_by(|a, b| {
+			(&a.manufacturer, &a.family, &a.name).cmp(&(&b.manufacturer, &b.family, &b.name))
+		});
+		return results;
+	}
+}
+
+/// Reads a fixture bundle from disk
+pub async fn read_bundle(path: PathBuf) -> Result<FixtureBundle, FixtureLibraryError> {
+	let contents = tokio::fs::read(&path)
+		.await
+		.map_err(|error| FixtureLibraryError::Io(error.to_string()))?;
+	return serde_json::from_slice(&contents).map_err(|error| FixtureLibraryError::InvalidBundle(error.to_string()));
+}
+
+/// Writes a fixture bundle to disk, creating the library directory if necessary
+pub async fn write_bundle(path: PathBuf, bundle: &FixtureBundle) -> Result<(), FixtureLibraryError> {
+	let contents = serde_json::to_vec_pretty(bundle).map_err(|error| FixtureLibraryError::InvalidBundle(error.to_string()))?;
+	if let Some(directory) = path.parent() {
+		tokio::fs::create_dir_all(directory)
+			.await
+			.map_err(|error| FixtureLibraryError::Io(error.to_string()))?;
+	}
+	return tokio::fs::write(&path, contents)
+		.await
+		.map_err(|error| FixtureLibraryError::Io(error.to_string()));
+}
+
+#[portable]
+/// Describes a fixture type in the library
+pub struct LibraryEntry {
+	pub id: Uuid,
+	pub name: String,
+	pub short_name: Option<String>,
+	pub manufacturer: Option<String>,
+	pub family: Option<String>,
+	pub version: u32,
+	pub output_driver: String,
+}
+
+impl From<&FixtureInfo> for LibraryEntry {
+	fn from(fixture_info: &FixtureInfo) -> Self {
+		return LibraryEntry {
+			id: fixture_info.id,
+			name: fixture_info.name.clone(),
+			short_name: fixture_info.short_name.clone(),
+			manufacturer: fixture_info.manufacturer.clone(),
+			family: fixture_info.family.clone(),
+			version: fixture_info.version,
+			output_driver: fixture_info.output_driver.clone(),
+		};
+	}
+}
+
+#[portable]
+/// Identifies a fixture type in the show that has a newer version in the library
+pub struct OutdatedFixture {
+	pub fixture_id: Uuid,
+	pub name: String,
+	pub show_version: u32,
+	pub library_version: u32,
+}
+
+#[portable]
+#[derive(Error)]
+/// An error that could occur while using the fixture library
+pub enum FixtureLibraryError {
+	#[error("The fixture library is not available on this system")]
+	Unavailable,
+	#[error("The requested fixture type does not exist")]
+	FixtureMissing,
+	#[error("The controller responsible for this fixture is missing")]
+	ControllerMissing,
+	#[error("The destination already contains a newer version ({0}) of this fixture type")]
+	NewerVersionExists(u32),
+	#[error("Could not access the fixture library:\n{0}")]
+	Io(String),
+	#[error("The library contains an invalid fixture bundle:\n{0}")]
+	InvalidBundle(String),
+	#[error("The controller could not export the fixture:\n{0}")]
+	ExportFailed(String),
+	#[error("The fixture could not be imported into the show:\n{0}")]
+	ImportFailed(ImportFixtureError),
+}
@@ -1,18 +1,39 @@
 pub mod driver_plugin_api;
+pub mod importers;
+mod curves;
 mod fixture_types;
 mod interface;
+mod library;
 mod services;
 mod state;
+mod validation;
 
 use self::{
 	services::{
-		CreateFixture, DeleteFixture, EditFixture, EditFixturePlacement, GetCreationForm,
-		GetEditForm, GetPatcherState, ImportFixtureDefinition,
+		ChangeFixturePersonality, CreateFixture, DeleteFixture, EditFixture, EditFixturePlacement,
+		GetCreationForm, GetEditForm, GetPatcherState, ImportFixtureDefinition, SearchFixtureLibrary,
+		AddToFixtureLibrary, LoadFromFixtureLibrary, RemoveFromFixtureLibrary, GetOutdatedFixtures,
+		RenumberFixture, RenumberFixtures, FindFixtureByNumber, FindFixturesByRange, GetFixtureCells,
+		GetFixtureTransforms, SetFixtureTransforms, ListDimmerCurves, CreateDimmerCurve, EditDimmerCurve,
+		DeleteDimmerCurve, SetFixtureDimmerCurve, SetChannelDimmerCurve, EditFixtureStagePlacement, EditFixtureHang,
+		GetFixtureControls, FilterFixturesByCapability, GetSelectionCapabilities, GetAttributeSegments,
+		ResolveSegmentValues, DescribeAttributeValue, GetPhysicalValue, ResolvePhysicalValues, GetSoftwareStrobe,
+		SetSoftwareStrobe,
 	},
-	state::{PatcherContext, VisualizationInfo},
+	state::PatcherContext,
+	library::FixtureLibrary,
 };
 use super::saver::SaverInterface;
-pub use interface::PatcherInterface;
+pub use curves::{CurvePoint, CurveShape, DimmerCurve, DimmerCurveError};
+pub use interface::{
+	CreateFixtureError, FixtureTransformsError, ImportFixtureError, PatcherInterface, RenumberFixtureError, ReplaceFixtureTypeError,
+	SoftwareStrobeError,
+};
+pub use state::{
+	CapabilitySummary, ChannelLimits, FixtureControl, FixtureControls, FixtureNumber, FixtureTransforms, SoftwareStrobe,
+	StageRotation, VisualizationInfo, STROBE_DUTY_ATTRIBUTE, STROBE_RATE_ATTRIBUTE,
+};
+use async_trait::async_trait;
 use simplydmx_plugin_framework::*;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -20,11 +41,12 @@ use uuid::Uuid;
 
 pub async fn initialize(plugin_context: PluginContext, saver: SaverInterface) -> Result<PatcherInterface, PatcherInitializationError> {
 	// Create patcher context
+	let fixture_library = FixtureLibrary::load(FixtureLibrary::default_directory()).await;
 	let patcher_interface = if let Ok(data) = saver.load_data(&"patcher".into()).await {
 		if let Some(data) = data {
-			PatcherInterface::new(plugin_context.clone(), Arc::new(RwLock::new(PatcherContext::from_file(data))))
+			PatcherInterface::new(plugin_context.clone(), Arc::new(RwLock::new(PatcherContext::from_file(data, fixture_library))))
 		} else {
-			PatcherInterface::new(plugin_context.clone(), Arc::new(RwLock::new(PatcherContext::new())))
+			PatcherInterface::new(plugin_context.clone(), Arc::new(RwLock::new(PatcherContext::new(fixture_library))))
 		}
 	} else {
 		return Err(PatcherInitializationError::UnrecognizedData);
@@ -40,6 +62,11 @@ pub async fn initialize(plugin_context: PluginContext, saver: SaverInterface) ->
 		Some("Event emitted when a new fixture type has been successfully imported into SimplyDMX".into()),
 	).await.unwrap();
 
+	plugin_context.declare_event::<()>(
+		"patcher.library_updated".into(),
+		Some("Event emitted when a fixture type is added to or removed from the user's fixture library".into()),
+	).await.unwrap();
+
 	plugin_context.declare_event::<(Uuid, VisualizationInfo)>(
 		"patcher.visualization_updated".into(),
 		Some("Event emitted when a fixture's visualization properties have been updated".into()),
@@ -53,12 +80,66 @@ pub async fn initialize(plugin_context: PluginContext, saver: SaverInterface) ->
 	plugin_context.register_service(true, GetEditForm::new(patcher_interface.clone())).await.unwrap();
 	plugin_context.register_service(true, EditFixture::new(patcher_interface.clone())).await.unwrap();
 	plugin_context.register_service(true, EditFixturePlacement::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, EditFixtureStagePlacement::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, EditFixtureHang::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, ChangeFixturePersonality::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, SearchFixtureLibrary::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, AddToFixtureLibrary::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, LoadFromFixtureLibrary::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, RemoveFromFixtureLibrary::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetOutdatedFixtures::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, RenumberFixture::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, RenumberFixtures::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, FindFixtureByNumber::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, FindFixturesByRange::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetFixtureCells::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetFixtureControls::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, FilterFixturesByCapability::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetSelectionCapabilities::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetAttributeSegments::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, ResolveSegmentValues::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, DescribeAttributeValue::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetPhysicalValue::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, ResolvePhysicalValues::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetSoftwareStrobe::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, SetSoftwareStrobe::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, GetFixtureTransforms::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, SetFixtureTransforms::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, ListDimmerCurves::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, CreateDimmerCurve::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, EditDimmerCurve::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, DeleteDimmerCurve::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, SetFixtureDimmerCurve::new(patcher_interface.clone())).await.unwrap();
+	plugin_context.register_service(true, SetChannelDimmerCurve::new(patcher_interface.clone())).await.unwrap();
+
+	plugin_context.register_service_type_specifier("fixture_library".into(), FixtureLibraryTypeSpecifier(patcher_interface.clone())).await.unwrap();
 
 	saver.register_savable("patcher", patcher_interface.clone()).await.unwrap();
 
 	return Ok(patcher_interface);
 }
 
+pub struct FixtureLibraryTypeSpecifier(PatcherInterface);
+#[async_trait]
+impl TypeSpecifier for FixtureLibraryTypeSpecifier {
+	async fn get_options(&self) -> Vec<DropdownOptionNative> {
+		return self
+			.0
+			.search_library(String::new(), None, None)
+			.await
+			.into_iter()
+			.map(|entry| DropdownOptionNative {
+				name: match entry.manufacturer {
+					Some(manufacturer) => format!("{} {}", manufacturer, entry.name),
+					None => entry.name,
+				},
+				description: entry.family,
+				value: Box::new(entry.id),
+			})
+			.collect();
+	}
+}
+
 #[portable]
 /// An error that could occur while initializing the patcher plugin
 pub enum PatcherInitializationError {
@@ -1,12 +1,20 @@
 use crate::utilities::{forms::FormDescriptor, serialized_data::SerializedData};
+use rustc_hash::FxHashMap;
 use simplydmx_plugin_framework::*;
 use uuid::Uuid;
 
 use super::{
+	curves::{CurveShape, DimmerCurve, DimmerCurveError},
 	driver_plugin_api::{FixtureBundle, SharablePatcherState},
+	fixture_types::{Capability, PhysicalValue, Position, Segment},
 	interface::{
 		CreateFixtureError, EditFixtureError, GetCreationFormError, GetEditFormError,
-		ImportFixtureError, DeleteFixtureError,
+		ImportFixtureError, DeleteFixtureError, RenumberFixtureError, FixtureTransformsError, SoftwareStrobeError,
+	},
+	library::{FixtureLibraryError, LibraryEntry, OutdatedFixture},
+	state::{
+		CapabilitySummary, FixtureControls, FixtureNumber, FixtureTransforms, SoftwareStrobe, StageRotation,
+		SubFixture,
 	},
 	PatcherInterface,
 };
@@ -183,6 +191,28 @@ impl EditFixture {
 	}
 }
 
+#[interpolate_service(
+	"change_fixture_personality",
+	"Change Fixture Personality",
+	"Switches a fixture to a different personality, keeping its address and any shared attribute values"
+)]
+impl ChangeFixturePersonality {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		Self(patcher_interface)
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance to change"),
+		("The ID of the personality to switch to"),
+		("Whether or not the personality was changed"),
+	)]
+	async fn main(self, instance_id: Uuid, personality: String) -> Result<(), EditFixtureError> {
+		return self.0.change_personality(&instance_id, personality).await;
+	}
+}
+
 #[interpolate_service(
 	"edit_fixture_placement",
 	"Edit Fixture Placement",
@@ -204,3 +234,657 @@ impl EditFixturePlacement {
 		return self.0.edit_fixture_placement(&fixture_id, x, y).await;
 	}
 }
+
+#[interpolate_service(
+	"edit_fixture_stage_placement",
+	"Edit Fixture Stage Placement",
+	"Edits the 3D position and orientation of the fixture on stage"
+)]
+impl EditFixtureStagePlacement {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the particular fixture instance you would like to edit"),
+		("The fixture's position on stage in meters, or `None` if it is unknown"),
+		("The fixture's orientation in degrees, or `None` if it is unknown")
+	)]
+	async fn main(self, fixture_id: Uuid, position: Option::<Position>, rotation: Option::<StageRotation>) {
+		return self.0.edit_fixture_stage_placement(&fixture_id, position, rotation).await;
+	}
+}
+
+#[interpolate_service(
+	"edit_fixture_hang",
+	"Edit Fixture Hang",
+	"Edits the hang position and layer the fixture is organized under"
+)]
+impl EditFixtureHang {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the particular fixture instance you would like to edit"),
+		("The name of the truss or hang position the fixture is mounted on"),
+		("The layer the fixture is drawn on")
+	)]
+	async fn main(self, fixture_id: Uuid, hang_position: Option::<String>, layer: Option::<String>) {
+		return self.0.edit_fixture_hang(&fixture_id, hang_position, layer).await;
+	}
+}
+
+#[interpolate_service(
+	"search_fixture_library",
+	"Search Fixture Library",
+	"Searches the user's fixture library, which is shared between shows"
+)]
+impl SearchFixtureLibrary {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("Text to search for in the fixture's name, manufacturer, or family. An empty string matches every fixture."),
+		("Only return fixtures from this manufacturer"),
+		("Only return fixtures from this family"),
+		("The matching fixture types, sorted by manufacturer, family, and name"),
+	)]
+	async fn main(self, query: String, manufacturer: Option::<String>, family: Option::<String>) -> Vec::<LibraryEntry> {
+		return self.0.search_library(query, manufacturer, family).await;
+	}
+}
+
+#[interpolate_service(
+	"add_to_fixture_library",
+	"Add to Fixture Library",
+	"Copies a fixture type from the show into the user's fixture library, replacing any older version"
+)]
+impl AddToFixtureLibrary {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture type within the show", "fixture-type-uuid"),
+		("The library's description of the fixture type"),
+	)]
+	async fn main(self, fixture_id: Uuid) -> Result<LibraryEntry, FixtureLibraryError> {
+		return self.0.add_to_library(&fixture_id).await;
+	}
+}
+
+#[interpolate_service(
+	"load_from_fixture_library",
+	"Load from Fixture Library",
+	"Imports a fixture type from the user's fixture library into the show, updating the show's copy if it is older"
+)]
+impl LoadFromFixtureLibrary {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture type within the library", "fixture_library"),
+		("Whether or not the fixture was successfully loaded"),
+	)]
+	async fn main(self, fixture_id: Uuid) -> Result<(), FixtureLibraryError> {
+		return self.0.load_from_library(&fixture_id).await;
+	}
+}
+
+#[interpolate_service(
+	"remove_from_fixture_library",
+	"Remove from Fixture Library",
+	"Deletes a fixture type from the user's fixture library. Shows using the fixture keep their own copy."
+)]
+impl RemoveFromFixtureLibrary {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture type within the library", "fixture_library"),
+		("Whether or not the fixture was successfully removed"),
+	)]
+	async fn main(self, fixture_id: Uuid) -> Result<(), FixtureLibraryError> {
+		return self.0.remove_from_library(&fixture_id).await;
+	}
+}
+
+#[interpolate_service(
+	"get_outdated_fixtures",
+	"Get Outdated Fixtures",
+	"Lists fixture types in the show that have a newer version in the user's fixture library"
+)]
+impl GetOutdatedFixtures {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The outdated fixture types and their versions"),
+	)]
+	async fn main(self) -> Vec::<OutdatedFixture> {
+		return self.0.get_outdated_fixtures().await;
+	}
+}
+
+#[interpolate_service(
+	"renumber_fixture",
+	"Renumber Fixture",
+	"Changes the number a fixture is referred to by"
+)]
+impl RenumberFixture {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance to renumber"),
+		("The fixture's new number, or null to remove its number"),
+		("Whether or not the fixture was renumbered"),
+	)]
+	async fn main(self, fixture_id: Uuid, number: Option::<FixtureNumber>) -> Result<(), RenumberFixtureError> {
+		return self.0.renumber_fixture(&fixture_id, number).await;
+	}
+}
+
+#[interpolate_service(
+	"renumber_fixtures",
+	"Renumber Fixtures",
+	"Assigns sequential numbers to several fixtures in the order given"
+)]
+impl RenumberFixtures {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the fixture instances to renumber, in order"),
+		("The number to give the first fixture"),
+		("The amount to increase the number by for each fixture"),
+		("Whether or not the fixtures were renumbered"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>, start: FixtureNumber, increment: u32) -> Result<(), RenumberFixtureError> {
+		return self.0.renumber_sequence(&fixture_ids, start, increment).await;
+	}
+}
+
+#[interpolate_service(
+	"find_fixture_by_number",
+	"Find Fixture by Number",
+	"Finds the fixture instance with the given number"
+)]
+impl FindFixtureByNumber {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The fixture number to look up"),
+		("The UUID of the fixture instance, if one has the number"),
+	)]
+	async fn main(self, number: FixtureNumber) -> Option<Uuid> {
+		return self.0.find_fixture_by_number(&number).await;
+	}
+}
+
+#[interpolate_service(
+	"find_fixtures_by_range",
+	"Find Fixtures by Range",
+	"Finds the fixture instances numbered within an inclusive range, sorted by number"
+)]
+impl FindFixturesByRange {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The first number in the range"),
+		("The last number in the range. If this is a whole number, the fixture's parts are included."),
+		("The UUIDs of the fixture instances in the range"),
+	)]
+	async fn main(self, start: FixtureNumber, end: FixtureNumber) -> Vec::<Uuid> {
+		return self.0.find_fixtures_by_range(&start, &end).await;
+	}
+}
+
+#[interpolate_service(
+	"get_fixture_cells",
+	"Get Fixture Cells",
+	"Lists the cells of a fixture, which are controlled as sub-fixtures"
+)]
+impl GetFixtureCells {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance"),
+		("The fixture's cells, in the order they are defined"),
+	)]
+	async fn main(self, fixture_id: Uuid) -> Vec::<SubFixture> {
+		return self.0.get_fixture_cells(&fixture_id).await;
+	}
+}
+
+#[interpolate_service(
+	"get_fixture_transforms",
+	"Get Fixture Transforms",
+	"Gets the adjustments applied to a fixture's output, such as inverted pan and tilt"
+)]
+impl GetFixtureTransforms {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance"),
+		("The fixture's transforms, or `None` if the fixture does not exist"),
+	)]
+	async fn main(self, fixture_id: Uuid) -> Option::<FixtureTransforms> {
+		return self.0.get_fixture_transforms(&fixture_id).await;
+	}
+}
+
+#[interpolate_service(
+	"set_fixture_transforms",
+	"Set Fixture Transforms",
+	"Replaces the adjustments applied to a fixture's output, such as inverted pan and tilt"
+)]
+impl SetFixtureTransforms {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance"),
+		("The fixture's new transforms"),
+		("Whether or not the transforms were applied"),
+	)]
+	async fn main(self, fixture_id: Uuid, transforms: FixtureTransforms) -> Result::<(), FixtureTransformsError> {
+		return self.0.set_fixture_transforms(&fixture_id, transforms).await;
+	}
+}
+
+#[interpolate_service(
+	"list_dimmer_curves",
+	"List Dimmer Curves",
+	"Lists the dimmer curves available to the show"
+)]
+impl ListDimmerCurves {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The show's dimmer curves, sorted by name"),
+	)]
+	async fn main(self) -> Vec::<DimmerCurve> {
+		return self.0.list_dimmer_curves().await;
+	}
+}
+
+#[interpolate_service(
+	"create_dimmer_curve",
+	"Create Dimmer Curve",
+	"Adds a dimmer curve to the show"
+)]
+impl CreateDimmerCurve {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The name of the curve"),
+		("The shape of the curve"),
+		("The ID of the new curve"),
+	)]
+	async fn main(self, name: String, shape: CurveShape) -> Result::<Uuid, DimmerCurveError> {
+		return self.0.create_dimmer_curve(name, shape).await;
+	}
+}
+
+#[interpolate_service(
+	"edit_dimmer_curve",
+	"Edit Dimmer Curve",
+	"Changes the name and shape of a dimmer curve"
+)]
+impl EditDimmerCurve {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The ID of the curve to edit"),
+		("The new name of the curve"),
+		("The new shape of the curve"),
+		("Whether or not the curve was edited"),
+	)]
+	async fn main(self, curve_id: Uuid, name: String, shape: CurveShape) -> Result::<(), DimmerCurveError> {
+		return self.0.edit_dimmer_curve(&curve_id, name, shape).await;
+	}
+}
+
+#[interpolate_service(
+	"delete_dimmer_curve",
+	"Delete Dimmer Curve",
+	"Removes a dimmer curve from the show, along with every assignment of it"
+)]
+impl DeleteDimmerCurve {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The ID of the curve to delete"),
+		("Whether or not the curve was deleted"),
+	)]
+	async fn main(self, curve_id: Uuid) -> Result::<(), DimmerCurveError> {
+		return self.0.delete_dimmer_curve(&curve_id).await;
+	}
+}
+
+#[interpolate_service(
+	"set_fixture_dimmer_curve",
+	"Set Fixture Dimmer Curve",
+	"Assigns a dimmer curve to a fixture's intensity channels"
+)]
+impl SetFixtureDimmerCurve {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance"),
+		("The ID of the curve to assign, or `None` to remove the fixture's curve"),
+		("Whether or not the curve was assigned"),
+	)]
+	async fn main(self, fixture_id: Uuid, curve_id: Option::<Uuid>) -> Result::<(), DimmerCurveError> {
+		return self.0.set_fixture_dimmer_curve(&fixture_id, curve_id).await;
+	}
+}
+
+#[interpolate_service(
+	"set_channel_dimmer_curve",
+	"Set Channel Dimmer Curve",
+	"Assigns a dimmer curve to a channel of every instance of a fixture type"
+)]
+impl SetChannelDimmerCurve {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The ID of the fixture type", "fixture-type-uuid"),
+		("The ID of the channel within the fixture type"),
+		("The ID of the curve to assign, or `None` to remove the channel's curve"),
+		("Whether or not the curve was assigned"),
+	)]
+	async fn main(self, fixture_type: Uuid, channel_id: String, curve_id: Option::<Uuid>) -> Result::<(), DimmerCurveError> {
+		return self.0.set_channel_dimmer_curve(&fixture_type, channel_id, curve_id).await;
+	}
+}
+
+#[interpolate_service(
+	"get_fixture_controls",
+	"Get Fixture Controls",
+	"Gets the controls offered by each fixture or cell in a selection, with the attribute IDs each control uses"
+)]
+impl GetFixtureControls {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the selected fixtures or cells"),
+		("The controls offered by each fixture or cell, in selection order"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>) -> Vec::<FixtureControls> {
+		return self.0.get_fixture_controls(&fixture_ids).await;
+	}
+}
+
+#[interpolate_service(
+	"filter_fixtures_by_capability",
+	"Filter Fixtures by Capability",
+	"Finds the fixtures and cells in a selection that offer a capability, such as RGB mixing or pan/tilt"
+)]
+impl FilterFixturesByCapability {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the selected fixtures or cells"),
+		("The capability to look for"),
+		("The fixtures and cells that offer the capability, in selection order"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>, capability: Capability) -> Vec::<Uuid> {
+		return self.0.filter_fixtures_by_capability(&fixture_ids, capability).await;
+	}
+}
+
+#[interpolate_service(
+	"get_selection_capabilities",
+	"Get Selection Capabilities",
+	"Lists every capability offered by a selection, along with the fixtures and cells that offer it"
+)]
+impl GetSelectionCapabilities {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the selected fixtures or cells"),
+		("Each capability offered by the selection"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>) -> Vec::<CapabilitySummary> {
+		return self.0.get_selection_capabilities(&fixture_ids).await;
+	}
+}
+
+#[interpolate_service(
+	"get_attribute_segments",
+	"Get Attribute Segments",
+	"Lists the named segments of a fixture's attribute, such as the slots of a gobo or color wheel"
+)]
+impl GetAttributeSegments {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture or cell"),
+		("The ID of the attribute, as used in the mixer"),
+		("The attribute's segments, or None if it isn't segmented"),
+	)]
+	async fn main(self, fixture_id: Uuid, attribute_id: String) -> Option::<Vec::<Segment>> {
+		return self.0.get_attribute_segments(&fixture_id, &attribute_id).await;
+	}
+}
+
+#[interpolate_service(
+	"resolve_segment_values",
+	"Resolve Segment Values",
+	"Finds the value of a named segment, such as a gobo or color, for each fixture in a selection"
+)]
+impl ResolveSegmentValues {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the selected fixtures or cells"),
+		("The ID of the attribute, as used in the mixer"),
+		("The name of the segment, ignoring case"),
+		("The segment's value for each fixture that has it"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>, attribute_id: String, segment_name: String) -> FxHashMap::<Uuid, u16> {
+		return self.0.resolve_segment_values(&fixture_ids, &attribute_id, &segment_name).await;
+	}
+}
+
+#[interpolate_service(
+	"describe_attribute_value",
+	"Describe Attribute Value",
+	"Finds the named segment an attribute's value falls into"
+)]
+impl DescribeAttributeValue {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture or cell"),
+		("The ID of the attribute, as used in the mixer"),
+		("The value to look up"),
+		("The segment the value falls into, or None if it isn't in a segment"),
+	)]
+	async fn main(self, fixture_id: Uuid, attribute_id: String, value: u16) -> Option::<Segment> {
+		return self.0.describe_attribute_value(&fixture_id, &attribute_id, value).await;
+	}
+}
+
+#[interpolate_service(
+	"get_physical_value",
+	"Get Physical Value",
+	"Converts an attribute's raw value to a physical unit, such as degrees or Kelvin, for display"
+)]
+impl GetPhysicalValue {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture or cell"),
+		("The ID of the attribute, as used in the mixer"),
+		("The raw value to convert"),
+		("The physical value, or None if the attribute has no physical range"),
+	)]
+	async fn main(self, fixture_id: Uuid, attribute_id: String, value: u16) -> Option::<PhysicalValue> {
+		return self.0.get_physical_value(&fixture_id, &attribute_id, value).await;
+	}
+}
+
+#[interpolate_service(
+	"resolve_physical_values",
+	"Resolve Physical Values",
+	"Converts a physical value, such as 90 degrees or 3200K, to the raw value of each fixture in a selection"
+)]
+impl ResolvePhysicalValues {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the selected fixtures or cells"),
+		("The ID of the attribute, as used in the mixer"),
+		("The physical value to convert"),
+		("The raw value for each fixture whose attribute uses the value's unit"),
+	)]
+	async fn main(self, fixture_ids: Vec::<Uuid>, attribute_id: String, value: PhysicalValue) -> FxHashMap::<Uuid, u16> {
+		return self.0.resolve_physical_values(&fixture_ids, &attribute_id, &value).await;
+	}
+}
+
+#[interpolate_service(
+	"get_software_strobe",
+	"Get Software Strobe",
+	"Gets the settings of a fixture's software strobe"
+)]
+impl GetSoftwareStrobe {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUID of the fixture instance"),
+		("The strobe's settings, or None if the fixture doesn't exist or has no software strobe"),
+	)]
+	async fn main(self, instance_id: Uuid) -> Option::<SoftwareStrobe> {
+		return self.0.get_software_strobe(&instance_id).await;
+	}
+}
+
+#[interpolate_service(
+	"set_software_strobe",
+	"Set Software Strobe",
+	"Enables or disables a strobe generated in the output stage, for fixtures without a shutter. The strobe is controlled through the `_strobe_rate` and `_strobe_duty` attributes."
+)]
+impl SetSoftwareStrobe {
+	#![inner_raw(PatcherInterface)]
+
+	pub fn new(patcher_interface: PatcherInterface) -> Self {
+		return Self(patcher_interface);
+	}
+
+	#[service_main(
+		("The UUIDs of the fixture instances to change"),
+		("The strobe's settings, or None to disable it"),
+		("Whether or not the strobe was changed"),
+	)]
+	async fn main(self, instance_ids: Vec::<Uuid>, strobe: Option::<SoftwareStrobe>) -> Result::<(), SoftwareStrobeError> {
+		return self.0.set_software_strobe(&instance_ids, strobe).await;
+	}
+}
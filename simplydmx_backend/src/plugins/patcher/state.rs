@@ -1,28 +1,60 @@
-use super::{driver_plugin_api::OutputDriver, fixture_types::FixtureInfo};
+use super::{
+	curves::DimmerCurve,
+	driver_plugin_api::OutputDriver,
+	fixture_types::{Capability, Position, Channel, ControlGroupData, FixtureCell, FixtureInfo},
+	library::FixtureLibrary,
+};
+use crate::mixer_utils::data_sources::FrameInfo;
 use rustc_hash::FxHashMap;
 use simplydmx_plugin_framework::*;
 use std::sync::Arc;
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 pub struct PatcherContext {
 	pub output_drivers: FxHashMap<String, Arc<Box<dyn OutputDriver>>>,
 	pub sharable: SharablePatcherState,
+
+	/// The user's fixture library, which is shared between shows
+	pub fixture_library: FixtureLibrary,
+
+	/// The phase of each running free-running software strobe, from `0` to `1`, advanced every frame
+	pub strobe_phases: Mutex<FxHashMap<Uuid, f64>>,
 }
 impl PatcherContext {
-	pub fn new() -> Self {
+	pub fn new(fixture_library: FixtureLibrary) -> Self {
 		return PatcherContext {
 			output_drivers: FxHashMap::default(),
+			fixture_library,
+			strobe_phases: Mutex::new(FxHashMap::default()),
 			sharable: SharablePatcherState {
 				library: FxHashMap::default(),
 				fixture_order: Vec::default(),
 				fixtures: FxHashMap::default(),
+				cells: FxHashMap::default(),
+				curves: FxHashMap::default(),
+				channel_curves: FxHashMap::default(),
 			},
 		};
 	}
-	pub fn from_file(file: SharablePatcherState) -> Self {
+	pub fn from_file(mut file: SharablePatcherState, fixture_library: FixtureLibrary) -> Self {
+		// Shows saved before fixture numbers existed are numbered in patch order
+		for fixture_id in file.fixture_order.clone() {
+			if file.fixtures.get(&fixture_id).is_some_and(|fixture| fixture.number.is_none()) {
+				let number = file.next_fixture_number();
+				if let Some(fixture) = file.fixtures.get_mut(&fixture_id) {
+					fixture.number = Some(number);
+				}
+			}
+		}
+
+		file.sync_all_cells();
+
 		return PatcherContext {
 			output_drivers: FxHashMap::default(),
 			sharable: file,
+			fixture_library,
+			strobe_phases: Mutex::new(FxHashMap::default()),
 		};
 	}
 }
@@ -34,6 +66,288 @@ pub struct SharablePatcherState {
 	pub library: FxHashMap<Uuid, FixtureInfo>,
 	pub fixture_order: Vec<Uuid>,
 	pub fixtures: FxHashMap<Uuid, FixtureInstance>,
+
+	/// Cells of fixture instances, which are controlled as sub-fixtures
+	#[serde(default)]
+	pub cells: FxHashMap<Uuid, CellInstance>,
+
+	/// Dimmer curves available to the show's fixtures
+	#[serde(default)]
+	pub curves: FxHashMap<Uuid, DimmerCurve>,
+
+	/// Dimmer curves assigned to channels of a fixture type, applying to every instance of that type.
+	///
+	/// Keyed by fixture type ID, then channel ID.
+	#[serde(default)]
+	pub channel_curves: FxHashMap<Uuid, FxHashMap<String, Uuid>>,
+}
+impl SharablePatcherState {
+	/// Gets the first whole fixture number after every number currently in use.
+	///
+	/// If `u32::MAX` is already in use, the lowest unused number is returned instead.
+	pub fn next_fixture_number(&self) -> FixtureNumber {
+		let mut used: Vec<u32> = self
+			.fixtures
+			.values()
+			.filter_map(|fixture| fixture.number)
+			.map(|number| number.number)
+			.collect();
+		used.sort_unstable();
+		let number = match used.last() {
+			None => 1,
+			Some(highest) => highest.checked_add(1).unwrap_or_else(|| {
+				let mut lowest_free: u32 = 1;
+				for number in used.iter() {
+					if *number > lowest_free {
+						break;
+					}
+					if *number == lowest_free {
+						lowest_free = lowest_free.saturating_add(1);
+					}
+				}
+				lowest_free
+			}),
+		};
+		return FixtureNumber { number, part: 0 };
+	}
+
+	/// Lists the numbers of every fixture and cell, as if the fixtures in `overrides` had the given numbers
+	pub fn numbered_fixtures(&self, overrides: &FxHashMap<Uuid, Option<FixtureNumber>>) -> Vec<(FixtureNumber, Uuid)> {
+		let get_number = |fixture: &FixtureInstance| overrides.get(&fixture.id).copied().unwrap_or(fixture.number);
+
+		let mut numbers: Vec<(FixtureNumber, Uuid)> = self
+			.fixtures
+			.values()
+			.filter_map(|fixture| get_number(fixture).map(|number| (number, fixture.id)))
+			.collect();
+		for cell in self.cells.values() {
+			if let (Some(parent), Some((index, _))) = (self.fixtures.get(&cell.parent), self.get_cell_definition(cell)) {
+				if let Some(number) = get_number(parent).and_then(|number| number.with_cell(index)) {
+					numbers.push((number, cell.id));
+				}
+			}
+		}
+		return numbers;
+	}
+
+	/// Finds the fixture or cell with the given number
+	pub fn find_fixture_by_number(&self, number: &FixtureNumber) -> Option<Uuid> {
+		return self
+			.numbered_fixtures(&FxHashMap::default())
+			.into_iter()
+			.find(|(fixture_number, _)| fixture_number == number)
+			.map(|(_, fixture_id)| fixture_id);
+	}
+
+	/// Gets the cells of a fixture instance's type that have channels in its personality, along with each
+	/// cell's index within the fixture type
+	pub fn active_cells(&self, instance: &FixtureInstance) -> Vec<(usize, &FixtureCell)> {
+		let fixture_type = match self.library.get(&instance.fixture_id) {
+			Some(fixture_type) => fixture_type,
+			None => return Vec::new(),
+		};
+		let personality = match fixture_type.personalities.get(&instance.personality) {
+			Some(personality) => personality,
+			None => return Vec::new(),
+		};
+		return fixture_type
+			.cells
+			.iter()
+			.enumerate()
+			.filter(|(_, cell)| {
+				cell.attributes
+					.values()
+					.any(|channel_id| personality.available_channels.contains(channel_id))
+			})
+			.collect();
+	}
+
+	/// Gets the definition of a cell instance, along with its index within the fixture type
+	pub fn get_cell_definition(&self, cell: &CellInstance) -> Option<(usize, &FixtureCell)> {
+		let parent = self.fixtures.get(&cell.parent)?;
+		let fixture_type = self.library.get(&parent.fixture_id)?;
+		return fixture_type
+			.cells
+			.iter()
+			.enumerate()
+			.find(|(_, definition)| definition.id == cell.cell);
+	}
+
+	/// Creates and removes cell instances to match the fixture instance's type and personality.
+	///
+	/// Cells that still exist keep their IDs, so mixer data stays attached to them.
+	pub fn sync_cells(&mut self, instance_id: &Uuid) {
+		let active: Vec<String> = match self.fixtures.get(instance_id) {
+			Some(instance) => self
+				.active_cells(instance)
+				.into_iter()
+				.map(|(_, cell)| cell.id.clone())
+				.collect(),
+			None => Vec::new(),
+		};
+
+		self.cells
+			.retain(|_, cell| cell.parent != *instance_id || active.contains(&cell.cell));
+		for cell_id in active {
+			if !self
+				.cells
+				.values()
+				.any(|cell| cell.parent == *instance_id && cell.cell == cell_id)
+			{
+				let id = Uuid::new_v4();
+				self.cells.insert(id, CellInstance { id, parent: *instance_id, cell: cell_id });
+			}
+		}
+	}
+
+	/// Gets the channel behind one of a fixture's or cell's attributes, as used in the mixer.
+	///
+	/// Returns `None` if the attribute isn't available in the fixture's personality.
+	pub fn get_attribute(&self, fixture_id: &Uuid, attribute_id: &str) -> Option<&Channel> {
+		if let Some(cell) = self.cells.get(fixture_id) {
+			let parent = self.fixtures.get(&cell.parent)?;
+			let fixture_type = self.library.get(&parent.fixture_id)?;
+			let personality = fixture_type.personalities.get(&parent.personality)?;
+			let (_, cell_info) = self.get_cell_definition(cell)?;
+			let channel_id = cell_info.attributes.get(attribute_id)?;
+			if !personality.available_channels.contains(channel_id) {
+				return None;
+			}
+			return fixture_type.channels.get(channel_id);
+		}
+
+		let fixture = self.fixtures.get(fixture_id)?;
+		let fixture_type = self.library.get(&fixture.fixture_id)?;
+		let personality = fixture_type.personalities.get(&fixture.personality)?;
+		if fixture_type.is_cell_channel(attribute_id)
+			|| !personality.available_channels.iter().any(|channel_id| channel_id == attribute_id)
+		{
+			return None;
+		}
+		return fixture_type.channels.get(attribute_id);
+	}
+
+	/// Gets the controls offered by a fixture or cell, or `None` if it doesn't exist.
+	///
+	/// Only controls whose channels are all available in the fixture's personality are included. Cells offer the
+	/// controls whose channels all belong to the cell, using the cell's attribute IDs, and their parent fixture
+	/// offers the rest.
+	pub fn get_controls(&self, fixture_id: &Uuid) -> Option<Vec<FixtureControl>> {
+		if let Some(cell) = self.cells.get(fixture_id) {
+			let parent = self.fixtures.get(&cell.parent)?;
+			let (_, cell_info) = self.get_cell_definition(cell)?;
+			return self.resolve_controls(parent, |channel_id| {
+				return cell_info
+					.attributes
+					.iter()
+					.find(|(_, cell_channel)| *cell_channel == channel_id)
+					.map(|(attribute_id, _)| attribute_id.clone());
+			});
+		}
+
+		let fixture = self.fixtures.get(fixture_id)?;
+		let fixture_type = self.library.get(&fixture.fixture_id)?;
+		return self.resolve_controls(fixture, |channel_id| {
+			return Some(channel_id.clone()).filter(|channel_id| !fixture_type.is_cell_channel(channel_id));
+		});
+	}
+
+	/// Lists the control groups of an instance's fixture type that are available in its personality, translating
+	/// channel IDs with `map`. Controls with a channel that can't be translated are left out.
+	fn resolve_controls(
+		&self,
+		instance: &FixtureInstance,
+		map: impl Fn(&String) -> Option<String>,
+	) -> Option<Vec<FixtureControl>> {
+		let fixture_type = self.library.get(&instance.fixture_id)?;
+		let personality = fixture_type.personalities.get(&instance.personality)?;
+		return Some(
+			fixture_type
+				.control_groups
+				.iter()
+				.filter_map(|control_group| {
+					let channels = control_group.get_channels().map_channels(|channel_id| {
+						if !personality.available_channels.contains(channel_id) {
+							return None;
+						}
+						return map(channel_id);
+					})?;
+					return Some(FixtureControl {
+						capability: channels.capability(),
+						name: control_group.get_name().cloned(),
+						fixture_type: fixture_type.id,
+						channels,
+					});
+				})
+				.collect(),
+		);
+	}
+
+	/// Syncs the cells of every fixture instance, removing cells whose parent no longer exists
+	pub fn sync_all_cells(&mut self) {
+		let fixtures = &self.fixtures;
+		self.cells.retain(|_, cell| fixtures.contains_key(&cell.parent));
+		let instance_ids: Vec<Uuid> = self.fixtures.keys().copied().collect();
+		for instance_id in instance_ids {
+			self.sync_cells(&instance_id);
+		}
+	}
+}
+
+/// A cell of a fixture instance, which is controlled as its own sub-fixture
+#[portable]
+pub struct CellInstance {
+	/// The ID the cell is controlled by in the mixer
+	pub id: Uuid,
+
+	/// The fixture instance the cell belongs to
+	pub parent: Uuid,
+
+	/// The ID of the cell within the parent's fixture type
+	pub cell: String,
+}
+
+/// Describes a cell of a fixture instance for display
+#[portable]
+pub struct SubFixture {
+	/// The ID the cell is controlled by in the mixer
+	pub id: Uuid,
+
+	/// The ID of the cell within the parent's fixture type
+	pub cell: String,
+	pub name: String,
+	pub number: Option<FixtureNumber>,
+
+	/// The cell's position relative to the parent fixture's origin, in meters
+	pub position: Position,
+}
+
+/// A control offered by a fixture or cell
+#[portable]
+pub struct FixtureControl {
+	pub capability: Capability,
+
+	/// The name of a non-standard control. Named controls should only be grouped with controls from the same
+	/// fixture type.
+	pub name: Option<String>,
+	pub fixture_type: Uuid,
+
+	/// The attribute IDs the control is made up of, as used in the mixer
+	pub channels: ControlGroupData,
+}
+
+/// The controls offered by a fixture or cell
+#[portable]
+pub struct FixtureControls {
+	pub fixture: Uuid,
+	pub controls: Vec<FixtureControl>,
+}
+
+/// The fixtures in a selection that offer a capability
+#[portable]
+pub struct CapabilitySummary {
+	pub capability: Capability,
+	pub fixtures: Vec<Uuid>,
 }
 
 /// Identifies an individual instance of a fixture
@@ -56,18 +370,201 @@ pub struct FixtureInstance {
 
 	/// Information about this particular fixture for the visualizer
 	pub visualization_info: VisualizationInfo,
+
+	/// The number the user refers to this fixture by. Numbers are unique within a show.
+	#[serde(default)]
+	pub number: Option<FixtureNumber>,
+
+	/// Adjustments applied to this fixture's output after blending, such as inverting a mover that is hung
+	/// upside down
+	#[serde(default)]
+	pub transforms: FixtureTransforms,
+
+	/// The dimmer curve applied to this fixture's intensity channels, taking precedence over curves assigned
+	/// to its fixture type's channels
+	#[serde(default)]
+	pub dimmer_curve: Option<Uuid>,
+
+	/// A strobe generated in the output stage, for fixtures without a shutter. `None` if it is disabled.
+	#[serde(default)]
+	pub software_strobe: Option<SoftwareStrobe>,
+}
+
+/// Adjustments applied to a fixture instance's output after blending, so looks stay portable between fixtures
+/// that are hung or configured differently.
+///
+/// Transforms are applied in the order they are declared here.
+#[portable]
+#[derive(Default)]
+pub struct FixtureTransforms {
+	/// Swaps the values of the fixture's pan and tilt channels, scaling them if their sizes differ
+	pub swap_pan_tilt: bool,
+
+	/// Channels whose output is inverted, so their minimum value becomes their maximum
+	pub inverted: Vec<String>,
+
+	/// Limits the output of individual channels, in the channel's own units
+	pub limits: FxHashMap<String, ChannelLimits>,
+
+	/// Scales the fixture's intensity channels so they never exceed this value, where 65535 is full
+	pub intensity_cap: Option<u16>,
+}
+impl FixtureTransforms {
+	/// Checks whether any transforms need to be applied
+	pub fn is_empty(&self) -> bool {
+		return !self.swap_pan_tilt && self.inverted.is_empty() && self.limits.is_empty() && self.intensity_cap.is_none();
+	}
+}
+
+/// The virtual attribute that sets the rate of a fixture's software strobe. `0` stops the strobe.
+pub const STROBE_RATE_ATTRIBUTE: &str = "_strobe_rate";
+
+/// The virtual attribute that sets the fraction of each software strobe cycle the fixture is lit for
+pub const STROBE_DUTY_ATTRIBUTE: &str = "_strobe_duty";
+
+/// Settings for a software strobe, which flashes a fixture's intensity channels in the output stage.
+///
+/// The strobe is controlled through the fixture's virtual strobe attributes. Tempo-synced strobes follow the mixer's
+/// beat. Free-running strobes keep their own phase, so fixtures started together at the same rate flash together.
+#[portable]
+#[derive(Copy)]
+pub struct SoftwareStrobe {
+	/// The flash rate, in flashes per second, at the rate attribute's full value
+	pub max_rate: f32,
+
+	/// Locks flashes to the mixer's tempo. The rate attribute then selects 1, 2, 4, 8, or 16 flashes per beat.
+	pub tempo_sync: bool,
+}
+impl SoftwareStrobe {
+	/// Checks whether a strobing fixture is lit during a frame, given the values of its strobe attributes.
+	///
+	/// Free-running strobes advance `phase` by the frame's duration at the current rate, so changing the rate
+	/// doesn't make the strobe jump to a different point in its cycle.
+	pub fn is_lit(&self, rate: u16, duty: u16, frame: &FrameInfo, phase: &mut f64) -> bool {
+		if rate == 0 {
+			return true;
+		}
+		let rate = rate as f64 / u16::MAX as f64;
+		let phase = if self.tempo_sync {
+			let flashes_per_beat = 2f64.powi((rate * 4.0).round() as i32);
+			(frame.beat * flashes_per_beat).fract()
+		} else {
+			*phase = (*phase + frame.delta * rate * self.max_rate as f64).fract();
+			*phase
+		};
+		return phase < duty as f64 / u16::MAX as f64;
+	}
+}
+
+/// The range a channel's output is clamped to
+#[portable]
+#[derive(Copy)]
+pub struct ChannelLimits {
+	pub min: u16,
+	pub max: u16,
+}
+
+/// A user-facing fixture number, such as `101` or `101.2` for part 2 of fixture 101
+#[portable]
+#[derive(Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixtureNumber {
+	pub number: u32,
+
+	/// The sub-fixture part of the number, or 0 for a whole fixture
+	pub part: u32,
+}
+impl FixtureNumber {
+	/// Gets the number of a cell within the fixture with this number, such as 101.2 for the second cell of 101.
+	///
+	/// Only fixtures with whole numbers have numbered cells.
+	pub fn with_cell(&self, cell_index: usize) -> Option<FixtureNumber> {
+		if self.part != 0 {
+			return None;
+		}
+		return Some(FixtureNumber {
+			number: self.number,
+			part: u32::try_from(cell_index).ok()?.checked_add(1)?,
+		});
+	}
+
+	/// Checks whether this number falls within an inclusive range.
+	///
+	/// If the end of the range is a whole number, the parts of that fixture are included as well.
+	pub fn in_range(&self, start: &FixtureNumber, end: &FixtureNumber) -> bool {
+		if end.part == 0 {
+			return self >= start && self.number <= end.number;
+		} else {
+			return self >= start && self <= end;
+		}
+	}
+}
+impl std::fmt::Display for FixtureNumber {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.part == 0 {
+			return write!(f, "{}", self.number);
+		} else {
+			return write!(f, "{}.{}", self.number, self.part);
+		}
+	}
+}
+impl std::str::FromStr for FixtureNumber {
+	type Err = std::num::ParseIntError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		return match s.trim().split_once('.') {
+			Some((number, part)) => Ok(FixtureNumber {
+				number: number.parse()?,
+				part: part.parse()?,
+			}),
+			None => Ok(FixtureNumber {
+				number: s.trim().parse()?,
+				part: 0,
+			}),
+		};
+	}
 }
 
 #[portable]
 pub struct VisualizationInfo {
 	pub x: u16,
 	pub y: u16,
+
+	/// The fixture's position on stage, in meters.
+	///
+	/// X runs from stage left to stage right, Y runs from downstage to upstage, and Z runs upwards from the deck.
+	#[serde(default)]
+	pub position: Option<Position>,
+
+	/// The fixture's orientation on stage
+	#[serde(default)]
+	pub rotation: Option<StageRotation>,
+
+	/// The name of the truss or hang position the fixture is mounted on
+	#[serde(default)]
+	pub hang_position: Option<String>,
+
+	/// The layer the fixture is drawn on, for organizing plots
+	#[serde(default)]
+	pub layer: Option<String>,
 }
 impl Default for VisualizationInfo {
 	fn default() -> Self {
 		return VisualizationInfo {
 			x: 0,
 			y: 0,
+			position: None,
+			rotation: None,
+			hang_position: None,
+			layer: None,
 		};
 	}
 }
+
+/// An orientation on stage, in degrees around each of the stage's axes (see [`VisualizationInfo::position`])
+#[portable]
+#[derive(Copy, Default)]
+pub struct StageRotation {
+	pub x: f32,
+	pub y: f32,
+	pub z: f32,
+}
@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use simplydmx_plugin_framework::*;
+use thiserror::Error;
+
+use super::{
+	curves::CurveShape,
+	fixture_types::{Channel, ChannelType, FixtureInfo, PhysicalRange},
+};
+
+#[portable]
+#[derive(Error, PartialEq)]
+/// A problem found in a fixture definition that would prevent it from being used reliably
+pub enum FixtureValidationError {
+	#[error("The fixture does not define any personalities")]
+	NoPersonalities,
+	#[error("Personality {personality:?} references channel {channel:?}, which does not exist")]
+	PersonalityChannelMissing { personality: String, channel: String },
+	#[error("Segment {segment:?} of channel {channel:?} ends before it starts")]
+	SegmentReversed { channel: String, segment: String },
+	#[error("Segment {segment:?} of channel {channel:?} does not fit in the channel's size")]
+	SegmentOutOfRange { channel: String, segment: String },
+	#[error("Segments {first:?} and {second:?} of channel {channel:?} overlap")]
+	SegmentsOverlap { channel: String, first: String, second: String },
+	#[error("The default value of channel {channel:?} ({default}) does not fit in the channel's size")]
+	DefaultOutOfRange { channel: String, default: u16 },
+	#[error("Channel {channel:?} has an invalid physical range: {reason}")]
+	InvalidPhysicalRange { channel: String, reason: String },
+	#[error("Channel {channel:?} emulates intensity for channel {target:?}, which does not exist")]
+	IntensityEmulationMissing { channel: String, target: String },
+	#[error("A control group references channel {channel:?}, which does not exist")]
+	ControlGroupChannelMissing { channel: String },
+	#[error("Cell ID {cell:?} is used more than once")]
+	DuplicateCell { cell: String },
+	#[error("Cell {cell:?} references channel {channel:?}, which does not exist")]
+	CellChannelMissing { cell: String, channel: String },
+	#[error("Channel {channel:?} is used by more than one cell attribute")]
+	CellChannelShared { channel: String },
+	#[error("The output driver has no data for personality {personality:?}")]
+	DriverPersonalityMissing { personality: String },
+	#[error("The output driver's layout for personality {personality:?} uses channel {channel:?}, which is not available in the personality")]
+	DriverChannelUnavailable { personality: String, channel: String },
+	#[error("Channel {channel:?} is available in personality {personality:?}, but the output driver does not output it")]
+	DriverChannelUnmapped { personality: String, channel: String },
+	#[error("The output driver's layout for personality {personality:?} outputs channel {channel:?} to a slot that is already in use")]
+	DriverSlotConflict { personality: String, channel: String },
+	#[error("The output driver's layout for personality {personality:?} outputs channel {channel:?} past the end of the universe")]
+	DriverSlotOutOfRange { personality: String, channel: String },
+}
+
+/// Formats a list of validation errors with one error per line
+pub fn format_validation_errors(errors: &[FixtureValidationError]) -> String {
+	return errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n");
+}
+
+/// Checks the generic portion of a fixture definition for internal consistency.
+///
+/// Output drivers are responsible for checking their own data against the definition.
+pub fn validate_fixture_info(fixture_info: &FixtureInfo) -> Vec<FixtureValidationError> {
+	let mut errors = Vec::new();
+
+	if fixture_info.personalities.is_empty() {
+		errors.push(FixtureValidationError::NoPersonalities);
+	}
+
+	// Sort keys so errors are reported in a predictable order
+	let mut personality_ids: Vec<&String> = fixture_info.personalities.keys().collect();
+	personality_ids.sort();
+	for personality_id in personality_ids {
+		for channel_id in fixture_info.personalities[personality_id].available_channels.iter() {
+			if !fixture_info.channels.contains_key(channel_id) {
+				errors.push(FixtureValidationError::PersonalityChannelMissing {
+					personality: personality_id.clone(),
+					channel: channel_id.clone(),
+				});
+			}
+		}
+	}
+
+	let mut channel_ids: Vec<&String> = fixture_info.channels.keys().collect();
+	channel_ids.sort();
+	for channel_id in channel_ids {
+		validate_channel(fixture_info, channel_id, &fixture_info.channels[channel_id], &mut errors);
+	}
+
+	for control_group in fixture_info.control_groups.iter() {
+		for channel_id in control_group.get_channels().channel_ids() {
+			if !fixture_info.channels.contains_key(channel_id) {
+				errors.push(FixtureValidationError::ControlGroupChannelMissing {
+					channel: channel_id.clone(),
+				});
+			}
+		}
+	}
+
+	validate_cells(fixture_info, &mut errors);
+
+	return errors;
+}
+
+fn validate_cells(fixture_info: &FixtureInfo, errors: &mut Vec<FixtureValidationError>) {
+	let mut cell_ids = HashSet::new();
+	let mut cell_channels = HashSet::new();
+	for cell in fixture_info.cells.iter() {
+		if !cell_ids.insert(&cell.id) {
+			errors.push(FixtureValidationError::DuplicateCell { cell: cell.id.clone() });
+		}
+
+		let mut channel_ids: Vec<&String> = cell.attributes.values().collect();
+		channel_ids.sort();
+		for channel_id in channel_ids {
+			if !fixture_info.channels.contains_key(channel_id) {
+				errors.push(FixtureValidationError::CellChannelMissing {
+					cell: cell.id.clone(),
+					channel: channel_id.clone(),
+				});
+			} else if !cell_channels.insert(channel_id) {
+				errors.push(FixtureValidationError::CellChannelShared {
+					channel: channel_id.clone(),
+				});
+			}
+		}
+	}
+}
+
+fn validate_channel(fixture_info: &FixtureInfo, channel_id: &str, channel: &Channel, errors: &mut Vec<FixtureValidationError>) {
+	let max_value = channel.size.max_value();
+
+	if channel.default > max_value {
+		errors.push(FixtureValidationError::DefaultOutOfRange {
+			channel: channel_id.to_owned(),
+			default: channel.default,
+		});
+	}
+
+	if let Some(ref targets) = channel.intensity_emulation {
+		for target in targets.iter() {
+			if !fixture_info.channels.contains_key(target) {
+				errors.push(FixtureValidationError::IntensityEmulationMissing {
+					channel: channel_id.to_owned(),
+					target: target.clone(),
+				});
+			}
+		}
+	}
+
+	if let Some(ref physical) = channel.physical {
+		if let Err(reason) = validate_physical_range(physical, max_value) {
+			errors.push(FixtureValidationError::InvalidPhysicalRange {
+				channel: channel_id.to_owned(),
+				reason,
+			});
+		}
+	}
+
+	if let ChannelType::Segmented { ref segments, .. } = channel.ch_type {
+		for segment in segments.iter() {
+			if segment.end < segment.start {
+				errors.push(FixtureValidationError::SegmentReversed {
+					channel: channel_id.to_owned(),
+					segment: segment.name.clone(),
+				});
+			} else if segment.end > max_value {
+				errors.push(FixtureValidationError::SegmentOutOfRange {
+					channel: channel_id.to_owned(),
+					segment: segment.name.clone(),
+				});
+			}
+		}
+
+		let mut sorted: Vec<_> = segments.iter().filter(|segment| segment.start <= segment.end).collect();
+		sorted.sort_by_key(|segment| segment.start);
+		for pair in sorted.windows(2) {
+			if pair[1].start <= pair[0].end {
+				errors.push(FixtureValidationError::SegmentsOverlap {
+					channel: channel_id.to_owned(),
+					first: pair[0].name.clone(),
+					second: pair[1].name.clone(),
+				});
+			}
+		}
+	}
+}
+
+fn validate_physical_range(physical: &PhysicalRange, max_value: u16) -> Result<(), String> {
+	if !physical.start.is_finite() || !physical.end.is_finite() {
+		return Err("The start and end must be finite numbers".into());
+	}
+	if let Some((raw_start, raw_end)) = physical.raw_range {
+		if raw_start.max(raw_end) > max_value {
+			return Err("The raw range does not fit in the channel's size".into());
+		}
+	}
+	if let CurveShape::Custom(ref points) = physical.curve {
+		let mut curve = physical.curve.clone();
+		curve.prepare().map_err(|error| error.to_string())?;
+		if points.windows(2).any(|pair| pair[1].input < pair[0].input) {
+			return Err("Curve points must be sorted by input".into());
+		}
+		if points.windows(2).any(|pair| pair[1].output < pair[0].output) {
+			return Err("The curve must never decrease".into());
+		}
+	}
+	return Ok(());
+}
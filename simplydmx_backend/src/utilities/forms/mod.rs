@@ -308,12 +308,13 @@ pub enum FormItemOptionSource {
 
 pub struct OptionsBuilder(Vec<DropdownOptionJSON>);
 impl OptionsBuilder {
-	pub fn add_item(mut self, name: impl Into<String>, value: impl Into<String>) {
+	pub fn add_item(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
 		self.0.push(DropdownOptionJSON {
 			name: name.into(),
 			description: None,
 			value: value.into().serialize_json().unwrap(),
 		});
+		return self;
 	}
 }
 impl Into<FormItemOptionSource> for OptionsBuilder {
@@ -13,7 +13,20 @@ export type AbstractLayerLight = FxHashMap<string, BlenderValue>;
 /**
  * Describes an image to display
  */
-export type AssetDescriptor = { BuiltIn: string } | { SVGInline: string };
+export type AssetDescriptor = { BuiltIn: string } | { SVGInline: string } | { PNGInline: number[] };
+
+/**
+ * A family of attributes to be moved into a new layer
+ */
+export interface AttributeSplitGroup {
+    name: string;
+    attributes: string[];
+}
+
+/**
+ * An error that could occur while manipulating fader banks
+ */
+export type BankError = "BankMissing" | "PageMissing" | "SlotMissing" | "LayerMissing" | "LastPage";
 
 /**
  * Value to be used in a submaster with instructions for mixing it into the result
@@ -38,6 +51,48 @@ export interface BlendingData {
  */
 export type BlendingScheme = "HTP" | "LTP";
 
+/**
+ * An error that could occur while bulk patching fixtures
+ */
+export type BulkPatchError = "FixtureTypeMissing" | "PersonalityMissing" | "UniverseMissing" | "InvalidOffset" | "OutOfSpace" | { CreateFailed: CreateFixtureError };
+
+/**
+ * Describes a set of fixture instances to create with [`DMXInterface::bulk_patch`]
+ */
+export interface BulkPatchOptions {
+    fixture_type: Uuid;
+    personality: string;
+    count: number;
+    name: string | null;
+    start_number: number;
+    universe: Uuid;
+    start_offset: number | null;
+    gap: number;
+    wrap: boolean;
+}
+
+/**
+ * The kinds of control a fixture can offer, matching the variants of [`ControlGroupData`]
+ */
+export type Capability = "Intensity" | "RGBGroup" | "CMYKGroup" | "PanTilt" | "Gobo" | "ColorWheel" | "Zoom" | "GenericInput";
+
+/**
+ * The fixtures in a selection that offer a capability
+ */
+export interface CapabilitySummary {
+    capability: Capability;
+    fixtures: Uuid[];
+}
+
+/**
+ * A cell of a fixture instance, which is controlled as its own sub-fixture
+ */
+export interface CellInstance {
+    id: Uuid;
+    parent: Uuid;
+    cell: string;
+}
+
 /**
  * Information about a specific channel available on the fixture
  */
@@ -46,6 +101,15 @@ export interface Channel {
     size: ChannelSize;
     default?: number;
     ch_type: ChannelType;
+    physical?: PhysicalRange | null;
+}
+
+/**
+ * The range a channel's output is clamped to
+ */
+export interface ChannelLimits {
+    min: number;
+    max: number;
 }
 
 /**
@@ -82,6 +146,36 @@ export type CreateFixtureError = "FixtureTypeMissing" | "ControllerMissing" | {
  */
 export type CreateInstanceError = { InvalidData: string } | { Other: string };
 
+/**
+ * A point in a custom dimmer curve. Both values range from 0 to 1.
+ */
+export interface CurvePoint {
+    input: number;
+    output: number;
+}
+
+/**
+ * The shape of a dimmer curve
+ */
+export type CurveShape = "Linear" | "SquareLaw" | "InverseSquareLaw" | "SCurve" | { Custom: CurvePoint[] };
+
+/**
+ * The order that a multi-byte channel outputs its bytes in
+ */
+export type DMXByteOrder = "MsbFirst" | "LsbFirst";
+
+/**
+ * Outputs a channel's bytes to specific slots. Slots are numbered from 0 at the fixture's first address.
+ * 
+ * Values are scaled to the number of slots used, so 8-bit channels can drive 16-bit outputs and 16-bit channels
+ * can drive 24-bit outputs.
+ */
+export interface DMXChannelMapping {
+    coarse: number;
+    fine?: number | null;
+    ultra?: number | null;
+}
+
 /**
  * A description for a DMX driver
  */
@@ -120,6 +214,8 @@ export type DMXInitializationError = "UnrecognizedData";
  */
 export interface DMXPersonalityData {
     dmx_channel_order: string[];
+    channel_mappings?: Record<string, DMXChannelMapping>;
+    byte_order?: DMXByteOrder;
 }
 
 /**
@@ -129,6 +225,8 @@ export interface DMXShowSave {
     library: Record<Uuid, DMXFixtureData>;
     fixtures: Record<Uuid, DMXFixtureInstance>;
     universes: Record<Uuid, UniverseInstance>;
+    universe_display_order?: Uuid[];
+    fixture_drafts?: Record<Uuid, FixtureDraft>;
 }
 
 /**
@@ -136,6 +234,20 @@ export interface DMXShowSave {
  */
 export type DeleteFixtureError = "FixtureMissing" | "FixtureTypeMissing" | "ControllerMissing" | { ErrorFromController: string };
 
+/**
+ * A named dimmer curve stored in the show, which maps the mixer's output to the level sent to a fixture
+ */
+export interface DimmerCurve {
+    id: Uuid;
+    name: string;
+    shape: CurveShape;
+}
+
+/**
+ * An error that could occur while editing or assigning dimmer curves
+ */
+export type DimmerCurveError = "CurveMissing" | "FixtureMissing" | "FixtureTypeMissing" | { UnknownChannel: string } | { InvalidCurve: string };
+
 /**
  * Minified representation of a DMX driver for display
  */
@@ -145,6 +257,17 @@ export interface DisplayableDMXDriver {
     description: string;
 }
 
+/**
+ * Descriptive details of a fixture type, as entered in the details form
+ */
+export interface DraftDetails {
+    name: string;
+    short_name?: string | null;
+    manufacturer?: string | null;
+    family?: string | null;
+    manual_link?: string | null;
+}
+
 /** Describes a value to be shown in a dropdown list */
 export interface DropdownOptionJSON {
     name: string;
@@ -174,16 +297,88 @@ export interface E131Universe {
 /**
  * An error that could occur when creating a fixture
  */
-export type EditFixtureError = "FixtureMissing" | "FixtureTypeMissing" | "ControllerMissing" | { ErrorFromController: EditInstanceError };
+export type EditFixtureError = "FixtureMissing" | "FixtureTypeMissing" | "ControllerMissing" | "PersonalityMissing" | { ErrorFromController: EditInstanceError };
 
 /**
  * A generic error originating from an OutputDriver interface when editing an existing fixture instance
  */
 export type EditInstanceError = { InvalidData: string } | { Other: string };
 
+/**
+ * An error that could occur while parsing an expression
+ */
+export type ExpressionError = "Empty" | "TooLong" | "TooDeep" | { UnexpectedCharacter: [string, number] } | "UnexpectedEnd" | { UnexpectedToken: [string, number] } | { UnknownVariable: string } | { UnknownFunction: string } | { WrongArgumentCount: [string, number] } | { InvalidLayerId: string };
+
+/**
+ * Defines a submaster whose values are calculated from expressions every frame.
+ * 
+ * Each expression produces a value from `0` to `1`, which is scaled to the attribute's range.
+ */
+export interface ExpressionLayer {
+    name: string;
+    fixtures: Uuid[];
+    expressions: FxHashMap<string, string>;
+}
+
+/**
+ * An error that could occur while editing an expression layer
+ */
+export type ExpressionLayerError = "LayerMissing" | { InvalidExpression: [string, ExpressionError] };
+
+/**
+ * A playback bank, mapping a fixed set of fader slots to layers across several pages.
+ * 
+ * Banks are the basis for control surfaces. A physical controller with 8 faders would
+ * use a bank with 8 slots, and page through the layers assigned to those slots.
+ */
+export interface FaderBank {
+    id: Uuid;
+    name: string;
+    slot_count: number;
+    pages: FaderPage[];
+    current_page: number;
+    pickup_mode: PickupMode;
+}
+
+/**
+ * A single page of fader assignments within a bank
+ */
+export interface FaderPage {
+    name: string;
+    assignments: (Uuid | null)[];
+}
+
+/**
+ * Runtime tracking data for a physical fader. This is not saved with the show.
+ */
+export interface FaderPickupState {
+    engaged: boolean;
+    last_position: number | null;
+}
+
+/**
+ * The current state of a fader slot, used to provide feedback to control surfaces
+ */
+export interface FaderSlotState {
+    layer_id: Uuid | null;
+    opacity: number;
+    engaged: boolean;
+    physical_position: number | null;
+}
+
 /** Represents criteria used to filter an event. For example, a submaster UUID could be used to filter submaster updates by that specific submaster */
 export type FilterCriteria = { type: "None" } | { type: "String"; data: string } | { type: "Uuid"; data: Uuid };
 
+/**
+ * An error that could occur while building a fixture type
+ */
+export type FixtureBuilderError = "DraftMissing" | "FixtureTypeMissing" | { InvalidForm: string } | { DuplicateChannel: string } | { ChannelMissing: string } | { NotSegmented: string } | { SegmentMissing: number } | { DuplicatePersonality: string } | { PersonalityMissing: string } | "InvalidChannelOrder" | { ChannelNotMapped: string } | { ControlGroupMissing: number } | { DuplicateCell: string } | { CellMissing: string } | { CellAttributeMissing: string } | { InvalidStep: FixtureValidationError[] } | { ImportFailed: ImportFixtureError };
+
+/**
+ * The forms used to build a fixture type
+ */
+export type FixtureBuilderForm = "Details" | "Channel" | "Segment" | "Personality" | "Cell";
+
 /**
  * Importable fixture descriptor bundle that includes output driver-specific information
  */
@@ -192,12 +387,54 @@ export interface FixtureBundle {
     output_info: SerializedData;
 }
 
+/**
+ * A cell, or head, of a fixture, which is controlled as its own sub-fixture
+ */
+export interface FixtureCell {
+    id: string;
+    name: string;
+    attributes: FxHashMap<string, string>;
+    position?: Position;
+}
+
+/**
+ * A control offered by a fixture or cell
+ */
+export interface FixtureControl {
+    capability: Capability;
+    name: string | null;
+    fixture_type: Uuid;
+    channels: ControlGroupData;
+}
+
+/**
+ * The controls offered by a fixture or cell
+ */
+export interface FixtureControls {
+    fixture: Uuid;
+    controls: FixtureControl[];
+}
+
+/**
+ * A fixture type that is being built or edited
+ */
+export interface FixtureDraft {
+    info: FixtureInfo;
+    dmx: DMXFixtureData;
+}
+
+/**
+ * A single step in building a fixture type
+ */
+export type FixtureDraftEdit = { SetDetails: DraftDetails } | { AddChannel: NewChannel } | { RemoveChannel: string } | { AddSegment: NewSegment } | { RemoveSegment: { channel: string; index: number } } | { SetPhysicalRange: { channel: string; physical: PhysicalRange | null } } | { AddPersonality: NewPersonality } | { RemovePersonality: string } | { AddPersonalityChannel: { personality: string; channel: string } } | { RemovePersonalityChannel: { personality: string; channel: string } } | { SetDMXChannelOrder: { personality: string; order: string[] } } | { MapPersonalityChannel: { personality: string; channel: string; mapping: DMXChannelMapping } } | { UnmapPersonalityChannel: { personality: string; channel: string } } | { AddControlGroup: { name: string | null; channels: ControlGroupData } } | { RemoveControlGroup: number } | { AddCell: NewCell } | { RemoveCell: string } | { SetCellPosition: { cell: string; position: Position } } | { SetCellAttribute: { cell: string; attribute: string; channel: string } } | { RemoveCellAttribute: { cell: string; attribute: string } };
+
 /**
  * Data type that contains generic, protocol-erased information about a fixture such as name,
  * metadata, personalities, and references to services within the output controller.
  */
 export interface FixtureInfo {
     id: Uuid;
+    version?: number;
     name: string;
     short_name: string | null;
     manufacturer: string | null;
@@ -207,6 +444,7 @@ export interface FixtureInfo {
     personalities: FxHashMap<string, Personality>;
     output_driver: string;
     control_groups: ControlGroup[];
+    cells?: FixtureCell[];
 }
 
 /**
@@ -219,8 +457,17 @@ export interface FixtureInstance {
     name: string | null;
     comments: string | null;
     visualization_info: VisualizationInfo;
+    number?: FixtureNumber | null;
+    transforms?: FixtureTransforms;
+    dimmer_curve?: Uuid | null;
+    software_strobe?: SoftwareStrobe | null;
 }
 
+/**
+ * An error that could occur while using the fixture library
+ */
+export type FixtureLibraryError = "Unavailable" | "FixtureMissing" | "ControllerMissing" | { NewerVersionExists: number } | { Io: string } | { InvalidBundle: string } | { ExportFailed: string } | { ImportFailed: ImportFixtureError };
+
 /**
  * Metadata about the fixture, used for display in the UI
  */
@@ -236,6 +483,45 @@ export interface FixtureMeta {
  */
 export type FixtureMixerOutput = FxHashMap<string, number>;
 
+/**
+ * A user-facing fixture number, such as `101` or `101.2` for part 2 of fixture 101
+ */
+export interface FixtureNumber {
+    number: number;
+    part: number;
+}
+
+/**
+ * A group of fixtures to be moved into a new layer
+ */
+export interface FixtureSplitGroup {
+    name: string;
+    fixtures: Uuid[];
+}
+
+/**
+ * Adjustments applied to a fixture instance's output after blending, so looks stay portable between fixtures
+ * that are hung or configured differently.
+ * 
+ * Transforms are applied in the order they are declared here.
+ */
+export interface FixtureTransforms {
+    swap_pan_tilt: boolean;
+    inverted: string[];
+    limits: FxHashMap<string, ChannelLimits>;
+    intensity_cap: number | null;
+}
+
+/**
+ * An error that could occur while changing a fixture's transforms
+ */
+export type FixtureTransformsError = "FixtureMissing" | "FixtureTypeMissing" | { UnknownChannel: string } | { InvalidLimits: string };
+
+/**
+ * A problem found in a fixture definition that would prevent it from being used reliably
+ */
+export type FixtureValidationError = "NoPersonalities" | { PersonalityChannelMissing: { personality: string; channel: string } } | { SegmentReversed: { channel: string; segment: string } } | { SegmentOutOfRange: { channel: string; segment: string } } | { SegmentsOverlap: { channel: string; first: string; second: string } } | { DefaultOutOfRange: { channel: string; default: number } } | { InvalidPhysicalRange: { channel: string; reason: string } } | { IntensityEmulationMissing: { channel: string; target: string } } | { ControlGroupChannelMissing: { channel: string } } | { DuplicateCell: { cell: string } } | { CellChannelMissing: { cell: string; channel: string } } | { CellChannelShared: { channel: string } } | { DriverPersonalityMissing: { personality: string } } | { DriverChannelUnavailable: { personality: string; channel: string } } | { DriverChannelUnmapped: { personality: string; channel: string } } | { DriverSlotConflict: { personality: string; channel: string } } | { DriverSlotOutOfRange: { personality: string; channel: string } };
+
 /**
  * Describes a form-style UI using a frontend-agnostic generic data structure
  */
@@ -298,6 +584,11 @@ export type FullMixerOutput = FxHashMap<Uuid, FixtureMixerOutput>;
 /** This is the same as a HashMap, but uses a more efficient hashing algorithm in the backend */
 export type FxHashMap<K extends string | number | symbol, V> = Record<K, V>;
 
+/**
+ * An error that prevented a GDTF file from being imported
+ */
+export type GdtfImportError = { InvalidArchive: string } | "MissingDescription" | { InvalidXml: string } | "MissingFixtureType" | "NoModes" | { InvalidOffset: [string, number] } | { ImportFailed: string };
+
 /**
  * An error that could occur while retrieving a fixture creation form
  */
@@ -314,12 +605,29 @@ export type GetLinkUniverseFormError = { ErrorFromController: string } | "Contro
 /**
  * A generic error originating from an OutputDriver interface when importing a fixture definition
  */
-export type ImportError = { InvalidData: string } | { Other: string };
+export type ImportError = { InvalidData: string } | { InvalidDefinition: FixtureValidationError[] } | { InstanceConflict: [Uuid, string] } | { Other: string };
 
 /**
  * An error that could occur when importing a fixture definition
  */
-export type ImportFixtureError = "UnknownController" | { ErrorFromController: ImportError };
+export type ImportFixtureError = "UnknownController" | { InvalidDefinition: FixtureValidationError[] } | { ErrorFromController: ImportError } | { PersonalityInUse: [Uuid, string] };
+
+/**
+ * The result of importing a fixture definition from another format
+ */
+export interface ImportReport {
+    fixture_id: Uuid;
+    name: string;
+    warnings: ImportWarning[];
+}
+
+/**
+ * Describes a feature of an imported fixture definition that could not be fully represented
+ */
+export interface ImportWarning {
+    location: string;
+    message: string;
+}
 
 /**
  * Describes a function that can be used to add interactivity to a form
@@ -341,18 +649,72 @@ export type JSONCommand = { type: "CallService"; message_id: number; plugin_id:
  */
 export type JSONResponse = { type: "CallServiceResponse"; message_id: number; result: Value } | { type: "ServiceList"; message_id: number; list: ServiceDescription[] } | { type: "OptionsList"; message_id: number; list: { Ok: DropdownOptionJSON[] } | { Err: TypeSpecifierRetrievalError } } | { type: "CallServiceError"; message_id: number; error: JSONCallServiceError } | { type: "Event"; name: string; criteria: FilterCriteria; data: Value };
 
+/**
+ * Selects which mixing context an operation should apply to
+ */
+export type LayerContextTarget = "Live" | "Blind";
+
+/**
+ * Links a layer's opacity to the opacity of one or more master layers
+ */
+export interface LayerLink {
+    masters: Uuid[];
+    mode: LinkMode;
+}
+
+/**
+ * An error that could occur while duplicating, merging, or splitting layers
+ */
+export type LayerOperationError = "LayerMissing" | "BlindInactive" | "NoLayers";
+
+/**
+ * Describes how a layer should be split into several new layers
+ */
+export type LayerSplit = { Fixtures: FixtureSplitGroup[] } | { Attributes: AttributeSplitGroup[] };
+
+/**
+ * Describes a fixture type in the library
+ */
+export interface LibraryEntry {
+    id: Uuid;
+    name: string;
+    short_name: string | null;
+    manufacturer: string | null;
+    family: string | null;
+    version: number;
+    output_driver: string;
+}
+
+/**
+ * An error that could occur while linking layers
+ */
+export type LinkError = "LayerMissing" | "MasterMissing" | "Cycle";
+
+/**
+ * Determines how a linked layer's opacity is combined with its masters' opacities
+ */
+export type LinkMode = "Multiply" | "Min" | "Max";
+
 /**
  * An error that could occur while linking a DMX universe to a universe controller
  */
 export type LinkUniverseError = { ErrorFromController: RegisterUniverseError } | "UniverseNotFound" | "ControllerNotFound";
 
+/**
+ * The rule used to resolve conflicts when merging layers that contain values for the same attribute
+ */
+export type MergeRule = "LTP" | "HTP" | "LoTP";
+
 /**
  * Data used by the mixer to blend submasters and produce a final result
  */
-export interface MixerContext {
-    default_context: MixingContext;
-    frozen_context: MixingContext | null;
-    blind_opacity: number;
+export interface MixerContext extends MixingZone {
+    zones?: Record<Uuid, MixingZone>;
+    zone_order?: Uuid[];
+    fixture_zones?: Record<Uuid, Uuid>;
+    tempo?: number;
+    banks?: Record<Uuid, FaderBank>;
+    bank_order?: Uuid[];
 }
 
 /**
@@ -370,6 +732,63 @@ export interface MixingContext {
     layer_opacities: Record<Uuid, number>;
     user_submaster_order: Uuid[];
     user_submasters: Record<Uuid, StaticLayer>;
+    expression_layers?: Record<Uuid, ExpressionLayer>;
+    layer_links?: Record<Uuid, LayerLink>;
+}
+
+/**
+ * A mixing zone, with its own layer stack, blind state, and grand master.
+ * 
+ * Zones allow separate areas of a venue (such as a stage and lobby) to be controlled independently.
+ */
+export interface MixingZone {
+    name?: string;
+    default_context: MixingContext;
+    frozen_context: MixingContext | null;
+    blind_opacity: number;
+    grand_master?: number;
+}
+
+/**
+ * A cell to add to a draft, as entered in the cell form
+ */
+export interface NewCell {
+    id: string;
+    name: string;
+    position?: Position;
+}
+
+/**
+ * A channel to add to a draft, as entered in the channel form
+ */
+export interface NewChannel {
+    id: string;
+    size: ChannelSize;
+    default?: number;
+    kind: NewChannelKind;
+    priority: BlendingScheme;
+}
+
+/**
+ * Whether a new channel is linear or segmented
+ */
+export type NewChannelKind = "Linear" | "Segmented";
+
+/**
+ * A personality to add to a draft, as entered in the personality form
+ */
+export interface NewPersonality {
+    id: string;
+}
+
+/**
+ * A segment to add to a segmented channel, as entered in the segment form
+ */
+export interface NewSegment {
+    channel: string;
+    name: string;
+    start: number;
+    end: number;
 }
 
 /**
@@ -377,11 +796,53 @@ export interface MixingContext {
  */
 export type NumberValidation = "None" | { Not: NumberValidation } | { And: NumberValidation[] } | { Or: NumberValidation[] } | { Between: [number, number] } | { DivisibleBy: number };
 
+/**
+ * An error that prevented an OFL definition from being imported
+ */
+export type OflImportError = { InvalidDefinition: string } | "NoModes" | { UnsupportedResolution: string } | { ImportFailed: string };
+
 /**
  * An error that could occur while initializing the E131 plugin
  */
 export type OpenDMXInitializationError = "UnrecognizedData";
 
+/**
+ * Identifies a fixture type in the show that has a newer version in the library
+ */
+export interface OutdatedFixture {
+    fixture_id: Uuid;
+    name: string;
+    show_version: number;
+    library_version: number;
+}
+
+/**
+ * A patch sheet row that could not be applied
+ */
+export interface PatchImportConflict {
+    row: number;
+    message: string;
+}
+
+/**
+ * Describes the outcome of a patch sheet import
+ */
+export interface PatchImportReport {
+    created: Uuid[];
+    updated: Uuid[];
+    conflicts: PatchImportConflict[];
+}
+
+/**
+ * A problem found in the show's patch
+ */
+export type PatchIssue = { AddressOverlap: { universe: Uuid; fixtures: [Uuid, Uuid]; addresses: [number, number] } } | { AddressOutOfRange: { fixture: Uuid; universe: Uuid; offset: number } } | { Unpatched: { fixture: Uuid } } | { UniverseMissing: { fixture: Uuid; universe: Uuid } } | { UniverseUnlinked: { universe: Uuid } } | { ControllerMissing: { universe: Uuid; controller: string } } | { FixtureTypeMissing: { fixture: Uuid; fixture_type: Uuid } } | { DMXFixtureTypeMissing: { fixture: Uuid; fixture_type: Uuid } } | { PersonalityMissing: { fixture: Uuid; personality: string } } | { DMXPersonalityMissing: { fixture: Uuid; personality: string } } | { InvalidLayout: { fixture: Uuid; message: string } } | { DMXInstanceMissing: { fixture: Uuid } } | { OrphanedInstance: { fixture: Uuid } } | { OrphanedFixtureType: { fixture_type: Uuid } };
+
+/**
+ * An error that could occur while importing or exporting a patch sheet
+ */
+export type PatchSheetError = { InvalidSheet: string } | { ExportFailed: string };
+
 /**
  * An error that could occur while initializing the patcher plugin
  */
@@ -397,6 +858,51 @@ export interface Personality {
     available_channels: string[];
 }
 
+/**
+ * Maps a channel's raw values onto a physical quantity
+ */
+export interface PhysicalRange {
+    unit: PhysicalUnit;
+    start: number;
+    end: number;
+    raw_range?: [number, number] | null;
+    curve?: CurveShape;
+}
+
+/**
+ * A unit of physical quantity a channel can control
+ */
+export type PhysicalUnit = "Degrees" | "Kelvin" | "Hertz" | "Percent";
+
+/**
+ * A value in a physical unit
+ */
+export interface PhysicalValue {
+    value: number;
+    unit: PhysicalUnit;
+}
+
+/**
+ * An error that could occur while setting a layer's attribute to a physical value
+ */
+export type PhysicalValueError = "LayerMissing" | { UnitUnsupported: PhysicalUnit };
+
+/**
+ * Determines how a physical fader behaves when its position differs from the level
+ * of the layer it controls.
+ */
+export type PickupMode = "Jump" | "Pickup";
+
+/**
+ * A position in meters. Cells are positioned relative to their fixture's origin, and fixtures are
+ * positioned relative to the stage.
+ */
+export interface Position {
+    x: number;
+    y: number;
+    z: number;
+}
+
 /**
  * An error returned when registering a saver. This is usually okay to unwrap, since it should be during init
  */
@@ -407,6 +913,16 @@ export type RegisterSavableError = "SaverAlreadyExists";
  */
 export type RegisterUniverseError = { InvalidData: string } | { Other: string };
 
+/**
+ * An error that could occur while changing fixture numbers
+ */
+export type RenumberFixtureError = { FixtureMissing: Uuid } | { DuplicateNumber: FixtureNumber } | "NumberOutOfRange";
+
+/**
+ * An error that could occur when replacing the fixture type of existing fixtures
+ */
+export type ReplaceFixtureTypeError = { FixtureMissing: Uuid } | "FixtureTypeMissing" | "PersonalityMissing" | "ControllerMissing" | "DriverMismatch" | { UnknownAttribute: string } | { ErrorFromController: [Uuid, EditInstanceError] };
+
 /**
  * An error returned by the saver if saving data failed
  */
@@ -432,6 +948,11 @@ export interface Segment {
  */
 export type SegmentDisplay = { Gobo: { asset: AssetDescriptor } } | { Color: { red: number; green: number; blue: number } } | { Image: { asset: AssetDescriptor } } | "Other";
 
+/**
+ * An error that could occur while setting a layer's attribute by segment name
+ */
+export type SegmentSelectionError = "LayerMissing" | { SegmentMissing: string };
+
 /**
  * Data type used to hold a serialized instance of an arbitrary data type.
  * 
@@ -465,6 +986,9 @@ export interface SharablePatcherState {
     library: FxHashMap<Uuid, FixtureInfo>;
     fixture_order: Uuid[];
     fixtures: FxHashMap<Uuid, FixtureInstance>;
+    cells?: FxHashMap<Uuid, CellInstance>;
+    curves?: FxHashMap<Uuid, DimmerCurve>;
+    channel_curves?: FxHashMap<Uuid, FxHashMap<string, Uuid>>;
 }
 
 /**
@@ -481,6 +1005,31 @@ export interface ShowFile {
  */
 export type SnapData = "NoSnap" | { SnapAt: number };
 
+/**
+ * Settings for a software strobe, which flashes a fixture's intensity channels in the output stage.
+ * 
+ * The strobe is controlled through the fixture's virtual strobe attributes. Tempo-synced strobes follow the mixer's
+ * beat. Free-running strobes keep their own phase, so fixtures started together at the same rate flash together.
+ */
+export interface SoftwareStrobe {
+    max_rate: number;
+    tempo_sync: boolean;
+}
+
+/**
+ * An error that could occur while changing the software strobe of fixtures
+ */
+export type SoftwareStrobeError = { FixtureMissing: Uuid } | "FixtureTypeMissing" | { NoIntensity: Uuid } | "InvalidRate";
+
+/**
+ * An orientation on stage, in degrees around each of the stage's axes (see [`VisualizationInfo::position`])
+ */
+export interface StageRotation {
+    x: number;
+    y: number;
+    z: number;
+}
+
 /**
  * Defines a static submaster
  */
@@ -489,6 +1038,17 @@ export interface StaticLayer {
     values: SubmasterData;
 }
 
+/**
+ * Describes a cell of a fixture instance for display
+ */
+export interface SubFixture {
+    id: Uuid;
+    cell: string;
+    name: string;
+    number: FixtureNumber | null;
+    position: Position;
+}
+
 /**
  * Represents the data within a submaster used for blending
  */
@@ -516,8 +1076,27 @@ export type Value = any;
 export interface VisualizationInfo {
     x: number;
     y: number;
+    position?: Position | null;
+    rotation?: StageRotation | null;
+    hang_position?: string | null;
+    layer?: string | null;
 }
 
+/**
+ * Summary of a mixing zone, for display in the UI
+ */
+export interface ZoneDescription {
+    id: Uuid | null;
+    name: string;
+    grand_master: number;
+    blind: boolean;
+}
+
+/**
+ * An error that could occur while managing mixing zones
+ */
+export type ZoneError = "ZoneMissing" | "LayerMissing" | "BlindActive";
+
 
 export const core = {
 	/** Log a message somewhere useful */
@@ -527,74 +1106,246 @@ export const core = {
 };
 
 export const mixer = {
+	/** Adds a new, empty page to the end of a fader bank */
+	add_bank_page(bank_id: Uuid, name: string): Promise<{ Ok: number } | { Err: BankError }> { return callService("mixer", "add_bank_page", [bank_id, name]) },
+	/** Assigns a layer to a fader slot on a page of a bank */
+	assign_fader(bank_id: Uuid, page: number, slot: number, layer_id: Uuid | null): Promise<{ Ok: null } | { Err: BankError }> { return callService("mixer", "assign_fader", [bank_id, page, slot, layer_id]) },
 	/** Commits all changes made in blind mode, deleting the previous look. Changes are made instantly. Use `set_blind_opacity` to fade. */
-	commit_blind(): Promise<void> { return callService("mixer", "commit_blind", []) },
+	commit_blind(zone_id: Uuid | null): Promise<void> { return callService("mixer", "commit_blind", [zone_id]) },
+	/** Creates a new fader bank with a single, empty page */
+	create_bank(name: string, slot_count: number): Promise<Uuid> { return callService("mixer", "create_bank", [name, slot_count]) },
+	/** Creates a new layer whose values are calculated from expressions every frame */
+	create_expression_layer(name: string): Promise<Uuid> { return callService("mixer", "create_expression_layer", [name]) },
 	/** Creates a new submaster that can be used for blending */
 	create_layer(name: string): Promise<Uuid> { return callService("mixer", "create_layer", [name]) },
+	/** Creates a new mixing zone with its own layer stack, blind mode, and grand master */
+	create_zone(name: string): Promise<Uuid> { return callService("mixer", "create_zone", [name]) },
+	/** Deletes a fader bank. Layers assigned to the bank are not affected. */
+	delete_bank(bank_id: Uuid): Promise<boolean> { return callService("mixer", "delete_bank", [bank_id]) },
+	/** Deletes a page from a fader bank. The last remaining page cannot be deleted. */
+	delete_bank_page(bank_id: Uuid, page: number): Promise<{ Ok: null } | { Err: BankError }> { return callService("mixer", "delete_bank_page", [bank_id, page]) },
 	/** Deletes a layer from the registry */
 	delete_layer(submaster_id: Uuid): Promise<boolean> { return callService("mixer", "delete_layer", [submaster_id]) },
+	/** Deletes a mixing zone, moving its layers and fixtures back to the main zone */
+	delete_zone(zone_id: Uuid): Promise<{ Ok: null } | { Err: ZoneError }> { return callService("mixer", "delete_zone", [zone_id]) },
+	/** Duplicates a layer, inserting the copy into the live or blind context */
+	duplicate_layer(submaster_id: Uuid, name: string | null, target: LayerContextTarget): Promise<{ Ok: Uuid } | { Err: LayerOperationError }> { return callService("mixer", "duplicate_layer", [submaster_id, name, target]) },
 	/** Copies the default layer bin to a new one with 0 opacity, setting it as the new default. */
-	enter_blind_mode(): Promise<void> { return callService("mixer", "enter_blind_mode", []) },
+	enter_blind_mode(zone_id: Uuid | null): Promise<void> { return callService("mixer", "enter_blind_mode", [zone_id]) },
+	/** Retrieves the pages and assignments of a fader bank */
+	get_bank(bank_id: Uuid): Promise<FaderBank | null> { return callService("mixer", "get_bank", [bank_id]) },
+	/** Gets the state of every fader slot on a bank's current page, for control surface feedback */
+	get_bank_state(bank_id: Uuid): Promise<{ Ok: FaderSlotState[] } | { Err: BankError }> { return callService("mixer", "get_bank_state", [bank_id]) },
 	/** Gets the opacity of the blind layer */
-	get_blind_opacity(): Promise<number | null> { return callService("mixer", "get_blind_opacity", []) },
+	get_blind_opacity(zone_id: Uuid | null): Promise<number | null> { return callService("mixer", "get_blind_opacity", [zone_id]) },
+	/** Gets the opacity of a layer after links to master layers have been applied */
+	get_effective_opacity(submaster_id: Uuid): Promise<number | null> { return callService("mixer", "get_effective_opacity", [submaster_id]) },
+	/** Gets the fixtures and expressions of an expression layer */
+	get_expression_layer(submaster_id: Uuid): Promise<ExpressionLayer | null> { return callService("mixer", "get_expression_layer", [submaster_id]) },
+	/** Gets the zone assignment of every fixture that is not in the main zone */
+	get_fixture_zones(): Promise<Record<Uuid, Uuid>> { return callService("mixer", "get_fixture_zones", []) },
+	/** Gets the grand master level of a zone */
+	get_grand_master(zone_id: Uuid | null): Promise<number | null> { return callService("mixer", "get_grand_master", [zone_id]) },
 	/** Retrieves the contents of a layer */
 	get_layer_contents(submaster_id: Uuid): Promise<StaticLayer | null> { return callService("mixer", "get_layer_contents", [submaster_id]) },
+	/** Gets the master layers controlling a layer's opacity */
+	get_layer_link(submaster_id: Uuid): Promise<LayerLink | null> { return callService("mixer", "get_layer_link", [submaster_id]) },
 	/** Gets the opacity of a layer (Optionally within a specific bin) */
 	get_layer_opacity(submaster_id: Uuid): Promise<number | null> { return callService("mixer", "get_layer_opacity", [submaster_id]) },
+	/** Gets the tempo used for the `beat` variable in expression layers */
+	get_tempo(): Promise<number> { return callService("mixer", "get_tempo", []) },
+	/** Lists all fader banks */
+	list_banks(): Promise<[Uuid, string][]> { return callService("mixer", "list_banks", []) },
 	/** Lists all user-created layers (submasters) */
 	list_submasters(): Promise<[Uuid, string][]> { return callService("mixer", "list_submasters", []) },
+	/** Lists all mixing zones, starting with the main zone */
+	list_zones(): Promise<ZoneDescription[]> { return callService("mixer", "list_zones", []) },
+	/** Merges several layers into a new one, resolving conflicting values with the given rule */
+	merge_layers(submaster_ids: Uuid[], name: string, rule: MergeRule, delete_sources: boolean): Promise<{ Ok: Uuid } | { Err: LayerOperationError }> { return callService("mixer", "merge_layers", [submaster_ids, name, rule, delete_sources]) },
+	/** Reports the position of a physical fader, setting the opacity of its assigned layer once the fader has been picked up */
+	move_fader(bank_id: Uuid, slot: number, position: number): Promise<{ Ok: FaderSlotState } | { Err: BankError }> { return callService("mixer", "move_fader", [bank_id, slot, position]) },
+	/** Moves a layer to another mixing zone, keeping its level */
+	move_layer_to_zone(submaster_id: Uuid, zone_id: Uuid | null): Promise<{ Ok: null } | { Err: ZoneError }> { return callService("mixer", "move_layer_to_zone", [submaster_id, zone_id]) },
+	/** Renames a fader bank */
+	rename_bank(bank_id: Uuid, name: string): Promise<{ Ok: null } | { Err: BankError }> { return callService("mixer", "rename_bank", [bank_id, name]) },
 	/** Renames a submaster */
 	rename_layer(submaster_id: Uuid, new_name: string): Promise<void> { return callService("mixer", "rename_layer", [submaster_id, new_name]) },
+	/** Renames a mixing zone */
+	rename_zone(zone_id: Uuid | null, name: string): Promise<{ Ok: null } | { Err: ZoneError }> { return callService("mixer", "rename_zone", [zone_id, name]) },
+	/** Swaps existing fixtures to a different fixture type, migrating their layer data to the new type's attributes */
+	replace_fixture_type(instance_ids: Uuid[], fixture_type: Uuid, personality: string, attribute_map: FxHashMap<string, string>): Promise<{ Ok: null } | { Err: ReplaceFixtureTypeError }> { return callService("mixer", "replace_fixture_type", [instance_ids, fixture_type, personality, attribute_map]) },
 	/** Manually requests the mixer to blend layers and emit new output */
 	request_blend(): Promise<void> { return callService("mixer", "request_blend", []) },
 	/** Reverts all changes made in blind mode. Changes are made instantly. Use `set_blind_opacity` to fade. */
-	revert_blind(): Promise<void> { return callService("mixer", "revert_blind", []) },
+	revert_blind(zone_id: Uuid | null): Promise<void> { return callService("mixer", "revert_blind", [zone_id]) },
+	/** Switches the page that is loaded onto a bank's faders */
+	set_bank_page(bank_id: Uuid, page: number): Promise<{ Ok: null } | { Err: BankError }> { return callService("mixer", "set_bank_page", [bank_id, page]) },
+	/** Sets how a bank's physical faders take control of layers whose level differs from the fader position */
+	set_bank_pickup_mode(bank_id: Uuid, pickup_mode: PickupMode): Promise<{ Ok: null } | { Err: BankError }> { return callService("mixer", "set_bank_pickup_mode", [bank_id, pickup_mode]) },
 	/** Sets the opacity of the blind layer */
-	set_blind_opacity(opacity: number): Promise<void> { return callService("mixer", "set_blind_opacity", [opacity]) },
+	set_blind_opacity(zone_id: Uuid | null, opacity: number): Promise<void> { return callService("mixer", "set_blind_opacity", [zone_id, opacity]) },
+	/** Replaces the fixtures and per-attribute expressions of an expression layer. Expressions should produce values from 0 to 1. */
+	set_expression_layer_contents(submaster_id: Uuid, fixtures: Uuid[], expressions: FxHashMap<string, string>): Promise<{ Ok: null } | { Err: ExpressionLayerError }> { return callService("mixer", "set_expression_layer_contents", [submaster_id, fixtures, expressions]) },
+	/** Assigns fixtures to a mixing zone. Fixtures only receive values from layers in their own zone. */
+	set_fixture_zone(fixture_ids: Uuid[], zone_id: Uuid | null): Promise<{ Ok: null } | { Err: ZoneError }> { return callService("mixer", "set_fixture_zone", [fixture_ids, zone_id]) },
+	/** Sets the grand master of a zone, which scales the intensity of every fixture in the zone */
+	set_grand_master(zone_id: Uuid | null, level: number): Promise<{ Ok: null } | { Err: ZoneError }> { return callService("mixer", "set_grand_master", [zone_id, level]) },
 	/** Adds or removes content in a layer */
 	set_layer_contents(submaster_id: Uuid, submaster_delta: SubmasterData): Promise<boolean> { return callService("mixer", "set_layer_contents", [submaster_id, submaster_delta]) },
+	/** Links a layer's opacity to one or more master layers. The effective opacity is calculated by the blender. */
+	set_layer_link(submaster_id: Uuid, masters: Uuid[], mode: LinkMode): Promise<{ Ok: null } | { Err: LinkError }> { return callService("mixer", "set_layer_link", [submaster_id, masters, mode]) },
 	/** Sets the opacity of a layer (Optionally within a specific bin) */
 	set_layer_opacity(submaster_id: Uuid, opacity: number, auto_insert: boolean): Promise<boolean> { return callService("mixer", "set_layer_opacity", [submaster_id, opacity, auto_insert]) },
+	/** Sets an attribute to a physical value, such as 90 degrees of pan or 3200K, across a selection of fixtures */
+	set_layer_physical(submaster_id: Uuid, fixture_ids: Uuid[], attribute_id: string, value: PhysicalValue): Promise<{ Ok: Uuid[] } | { Err: PhysicalValueError }> { return callService("mixer", "set_layer_physical", [submaster_id, fixture_ids, attribute_id, value]) },
+	/** Sets an attribute to a named segment, such as a gobo or color, across a selection of fixtures */
+	set_layer_segment(submaster_id: Uuid, fixture_ids: Uuid[], attribute_id: string, segment_name: string): Promise<{ Ok: Uuid[] } | { Err: SegmentSelectionError }> { return callService("mixer", "set_layer_segment", [submaster_id, fixture_ids, attribute_id, segment_name]) },
+	/** Sets the tempo used for the `beat` variable in expression layers */
+	set_tempo(bpm: number): Promise<void> { return callService("mixer", "set_tempo", [bpm]) },
+	/** Splits a layer into several new layers by fixture or attribute. Unmatched values remain in the original layer. */
+	split_layer(submaster_id: Uuid, split: LayerSplit): Promise<{ Ok: Uuid[] } | { Err: LayerOperationError }> { return callService("mixer", "split_layer", [submaster_id, split]) },
+	/** Moves a bank forward or backward by the given number of pages, wrapping around at either end */
+	step_bank_page(bank_id: Uuid, delta: number): Promise<{ Ok: number } | { Err: BankError }> { return callService("mixer", "step_bank_page", [bank_id, delta]) },
+	/** Checks if an expression is valid without assigning it to a layer */
+	validate_expression(source: string): Promise<{ Ok: null } | { Err: ExpressionError }> { return callService("mixer", "validate_expression", [source]) },
 };
 
 export const output_dmx = {
+	/** Creates several instances of a fixture type, numbering their names and assigning DMX addresses automatically */
+	bulk_patch_fixtures(options: BulkPatchOptions): Promise<{ Ok: Uuid[] } | { Err: BulkPatchError }> { return callService("output_dmx", "bulk_patch_fixtures", [options]) },
+	/** Starts building a new fixture type from the fixture builder's details form */
+	create_fixture_draft(form_data: SerializedData): Promise<{ Ok: Uuid } | { Err: FixtureBuilderError }> { return callService("output_dmx", "create_fixture_draft", [form_data]) },
+	/** Starts a fixture builder draft that edits a fixture type already in the show */
+	create_fixture_draft_from_type(fixture_type: Uuid): Promise<{ Ok: Uuid } | { Err: FixtureBuilderError }> { return callService("output_dmx", "create_fixture_draft_from_type", [fixture_type]) },
 	/** Creates a new, unlinked universe for DMX output */
 	create_universe(name: string): Promise<Uuid> { return callService("output_dmx", "create_universe", [name]) },
 	/** Deletes an existing universe, unlinking any associated lights or controllers */
 	delete_universe(universe_id: Uuid): Promise<void> { return callService("output_dmx", "delete_universe", [universe_id]) },
+	/** Deletes a fixture builder draft without importing it */
+	discard_fixture_draft(draft_id: Uuid): Promise<{ Ok: null } | { Err: FixtureBuilderError }> { return callService("output_dmx", "discard_fixture_draft", [draft_id]) },
+	/** Applies a step to a fixture builder draft, rejecting steps that would make the draft less valid */
+	edit_fixture_draft(draft_id: Uuid, edit: FixtureDraftEdit): Promise<{ Ok: null } | { Err: FixtureBuilderError }> { return callService("output_dmx", "edit_fixture_draft", [draft_id, edit]) },
+	/** Exports the show's patch as a CSV patch sheet */
+	export_patch_csv(): Promise<{ Ok: string } | { Err: PatchSheetError }> { return callService("output_dmx", "export_patch_csv", []) },
+	/** Finds the first offset in a universe where a range of channels is unoccupied */
+	find_free_dmx_range(universe_id: Uuid, size: number, start_offset: number | null): Promise<number | null> { return callService("output_dmx", "find_free_dmx_range", [universe_id, size, start_offset]) },
+	/** Imports a fixture builder draft into the show, replacing the fixture type it edits */
+	finish_fixture_draft(draft_id: Uuid): Promise<{ Ok: Uuid } | { Err: FixtureBuilderError }> { return callService("output_dmx", "finish_fixture_draft", [draft_id]) },
+	/** Gets the number of DMX channels a fixture type occupies in a personality */
+	get_dmx_footprint(fixture_type: Uuid, personality: string): Promise<number | null> { return callService("output_dmx", "get_dmx_footprint", [fixture_type, personality]) },
+	/** Gets one of the fixture builder's forms */
+	get_fixture_builder_form(draft_id: Uuid | null, form: FixtureBuilderForm): Promise<{ Ok: FormDescriptor } | { Err: FixtureBuilderError }> { return callService("output_dmx", "get_fixture_builder_form", [draft_id, form]) },
+	/** Gets the current state of a fixture builder draft */
+	get_fixture_draft(draft_id: Uuid): Promise<FixtureDraft | null> { return callService("output_dmx", "get_fixture_draft", [draft_id]) },
 	/** Gets a form for linking a universe */
 	get_link_universe_form(driver_id: string, universe_id: Uuid | null): Promise<{ Ok: FormDescriptor } | { Err: GetLinkUniverseFormError }> { return callService("output_dmx", "get_link_universe_form", [driver_id, universe_id]) },
 	/** Gets the ID of the controller linked to a universe */
 	get_linked_controller(universe_id: Uuid): Promise<string | null> { return callService("output_dmx", "get_linked_controller", [universe_id]) },
+	/** Checks the show's patch for overlapping addresses, unpatched fixtures, unlinked universes, and missing or orphaned data */
+	get_patch_report(): Promise<PatchIssue[]> { return callService("output_dmx", "get_patch_report", []) },
+	/** Imports a fixture type from a GDTF (.gdtf) file, reporting any features that could not be imported. Re-importing a fixture type replaces the show's copy, as long as the fixtures using it are still valid. */
+	import_gdtf(data: number[]): Promise<{ Ok: ImportReport } | { Err: GdtfImportError }> { return callService("output_dmx", "import_gdtf", [data]) },
+	/** Imports a fixture type from an Open Fixture Library JSON definition, reporting any features that could not be imported. Re-importing a fixture type replaces the show's copy, as long as the fixtures using it are still valid. */
+	import_ofl_fixture(definition: string, manufacturer: string | null): Promise<{ Ok: ImportReport } | { Err: OflImportError }> { return callService("output_dmx", "import_ofl_fixture", [definition, manufacturer]) },
+	/** Creates or updates fixture instances from a CSV patch sheet, reporting any rows that could not be applied */
+	import_patch_csv(sheet: string): Promise<{ Ok: PatchImportReport } | { Err: PatchSheetError }> { return callService("output_dmx", "import_patch_csv", [sheet]) },
 	/** Links an existing universe to a DMX driver */
 	link_universe(universe_id: Uuid, driver: string, form_data: SerializedData): Promise<{ Ok: null } | { Err: LinkUniverseError }> { return callService("output_dmx", "link_universe", [universe_id, driver, form_data]) },
 	/** List the DMX device drivers registered with the DMX output driver */
 	list_drivers(): Promise<DMXDriverDescription[]> { return callService("output_dmx", "list_drivers", []) },
+	/** Lists the fixture types being built or edited */
+	list_fixture_drafts(): Promise<[Uuid, string][]> { return callService("output_dmx", "list_fixture_drafts", []) },
 	/** Lists the universes registered in the DMX driver */
 	list_universes(): Promise<[Uuid, string][]> { return callService("output_dmx", "list_universes", []) },
 	/** Renames a universe */
 	rename_universe(universe_id: Uuid, universe_name: string): Promise<void> { return callService("output_dmx", "rename_universe", [universe_id, universe_name]) },
+	/** Applies data from one of the fixture builder's forms to a draft */
+	submit_fixture_builder_form(draft_id: Uuid, form: FixtureBuilderForm, form_data: SerializedData): Promise<{ Ok: null } | { Err: FixtureBuilderError }> { return callService("output_dmx", "submit_fixture_builder_form", [draft_id, form, form_data]) },
 	/** Unlinks an existing universe from its driver */
 	unlink_universe(universe_id: Uuid): Promise<void> { return callService("output_dmx", "unlink_universe", [universe_id]) },
+	/** Lists every problem that would prevent a fixture builder draft from being imported */
+	validate_fixture_draft(draft_id: Uuid): Promise<{ Ok: FixtureValidationError[] } | { Err: FixtureBuilderError }> { return callService("output_dmx", "validate_fixture_draft", [draft_id]) },
 };
 
 export const patcher = {
+	/** Copies a fixture type from the show into the user's fixture library, replacing any older version */
+	add_to_fixture_library(fixture_id: Uuid): Promise<{ Ok: LibraryEntry } | { Err: FixtureLibraryError }> { return callService("patcher", "add_to_fixture_library", [fixture_id]) },
+	/** Switches a fixture to a different personality, keeping its address and any shared attribute values */
+	change_fixture_personality(instance_id: Uuid, personality: string): Promise<{ Ok: null } | { Err: EditFixtureError }> { return callService("patcher", "change_fixture_personality", [instance_id, personality]) },
+	/** Adds a dimmer curve to the show */
+	create_dimmer_curve(name: string, shape: CurveShape): Promise<{ Ok: Uuid } | { Err: DimmerCurveError }> { return callService("patcher", "create_dimmer_curve", [name, shape]) },
 	/** Creates a new fixture in the patcher */
 	create_fixture(fixture_type: Uuid, personality: string, name: string | null, comments: string | null, form_data: SerializedData): Promise<{ Ok: Uuid } | { Err: CreateFixtureError }> { return callService("patcher", "create_fixture", [fixture_type, personality, name, comments, form_data]) },
+	/** Removes a dimmer curve from the show, along with every assignment of it */
+	delete_dimmer_curve(curve_id: Uuid): Promise<{ Ok: null } | { Err: DimmerCurveError }> { return callService("patcher", "delete_dimmer_curve", [curve_id]) },
 	/** Deletes a fixture from the patcher */
 	delete_fixture(fixture_id: Uuid): Promise<{ Ok: null } | { Err: DeleteFixtureError }> { return callService("patcher", "delete_fixture", [fixture_id]) },
+	/** Finds the named segment an attribute's value falls into */
+	describe_attribute_value(fixture_id: Uuid, attribute_id: string, value: number): Promise<Segment | null> { return callService("patcher", "describe_attribute_value", [fixture_id, attribute_id, value]) },
+	/** Changes the name and shape of a dimmer curve */
+	edit_dimmer_curve(curve_id: Uuid, name: string, shape: CurveShape): Promise<{ Ok: null } | { Err: DimmerCurveError }> { return callService("patcher", "edit_dimmer_curve", [curve_id, name, shape]) },
 	/** Edits the requested fixture using data provided by the user */
 	edit_fixture(instance_id: Uuid, personality: string, name: string | null, comments: string | null, form_data: SerializedData): Promise<{ Ok: null } | { Err: EditFixtureError }> { return callService("patcher", "edit_fixture", [instance_id, personality, name, comments, form_data]) },
+	/** Edits the hang position and layer the fixture is organized under */
+	edit_fixture_hang(fixture_id: Uuid, hang_position: string | null, layer: string | null): Promise<void> { return callService("patcher", "edit_fixture_hang", [fixture_id, hang_position, layer]) },
 	/** Edits the x,y coordinates of the fixture within the visualizer */
 	edit_fixture_placement(fixture_id: Uuid, x: number, y: number): Promise<void> { return callService("patcher", "edit_fixture_placement", [fixture_id, x, y]) },
+	/** Edits the 3D position and orientation of the fixture on stage */
+	edit_fixture_stage_placement(fixture_id: Uuid, position: Position | null, rotation: StageRotation | null): Promise<void> { return callService("patcher", "edit_fixture_stage_placement", [fixture_id, position, rotation]) },
+	/** Finds the fixtures and cells in a selection that offer a capability, such as RGB mixing or pan/tilt */
+	filter_fixtures_by_capability(fixture_ids: Uuid[], capability: Capability): Promise<Uuid[]> { return callService("patcher", "filter_fixtures_by_capability", [fixture_ids, capability]) },
+	/** Finds the fixture instance with the given number */
+	find_fixture_by_number(number: FixtureNumber): Promise<Uuid | null> { return callService("patcher", "find_fixture_by_number", [number]) },
+	/** Finds the fixture instances numbered within an inclusive range, sorted by number */
+	find_fixtures_by_range(start: FixtureNumber, end: FixtureNumber): Promise<Uuid[]> { return callService("patcher", "find_fixtures_by_range", [start, end]) },
+	/** Lists the named segments of a fixture's attribute, such as the slots of a gobo or color wheel */
+	get_attribute_segments(fixture_id: Uuid, attribute_id: string): Promise<Segment[] | null> { return callService("patcher", "get_attribute_segments", [fixture_id, attribute_id]) },
 	/** Queries the given fixture's driver for a fixture creation form to display */
 	get_creation_form(fixture_type: Uuid): Promise<{ Ok: FormDescriptor } | { Err: GetCreationFormError }> { return callService("patcher", "get_creation_form", [fixture_type]) },
 	/** Queries the given fixture's driver for a fixture edit form to display */
 	get_edit_form(fixture_id: Uuid): Promise<{ Ok: FormDescriptor } | { Err: GetEditFormError }> { return callService("patcher", "get_edit_form", [fixture_id]) },
+	/** Lists the cells of a fixture, which are controlled as sub-fixtures */
+	get_fixture_cells(fixture_id: Uuid): Promise<SubFixture[]> { return callService("patcher", "get_fixture_cells", [fixture_id]) },
+	/** Gets the controls offered by each fixture or cell in a selection, with the attribute IDs each control uses */
+	get_fixture_controls(fixture_ids: Uuid[]): Promise<FixtureControls[]> { return callService("patcher", "get_fixture_controls", [fixture_ids]) },
+	/** Gets the adjustments applied to a fixture's output, such as inverted pan and tilt */
+	get_fixture_transforms(fixture_id: Uuid): Promise<FixtureTransforms | null> { return callService("patcher", "get_fixture_transforms", [fixture_id]) },
+	/** Lists fixture types in the show that have a newer version in the user's fixture library */
+	get_outdated_fixtures(): Promise<OutdatedFixture[]> { return callService("patcher", "get_outdated_fixtures", []) },
 	/** Retrieves the current state of the patcher, with libraries, registered fixtures, etc. */
 	get_patcher_state(): Promise<SharablePatcherState> { return callService("patcher", "get_patcher_state", []) },
+	/** Converts an attribute's raw value to a physical unit, such as degrees or Kelvin, for display */
+	get_physical_value(fixture_id: Uuid, attribute_id: string, value: number): Promise<PhysicalValue | null> { return callService("patcher", "get_physical_value", [fixture_id, attribute_id, value]) },
+	/** Lists every capability offered by a selection, along with the fixtures and cells that offer it */
+	get_selection_capabilities(fixture_ids: Uuid[]): Promise<CapabilitySummary[]> { return callService("patcher", "get_selection_capabilities", [fixture_ids]) },
+	/** Gets the settings of a fixture's software strobe */
+	get_software_strobe(instance_id: Uuid): Promise<SoftwareStrobe | null> { return callService("patcher", "get_software_strobe", [instance_id]) },
 	/** Import a fixture definition */
 	import_fixture(fixture_bundle: FixtureBundle): Promise<{ Ok: null } | { Err: ImportFixtureError }> { return callService("patcher", "import_fixture", [fixture_bundle]) },
+	/** Lists the dimmer curves available to the show */
+	list_dimmer_curves(): Promise<DimmerCurve[]> { return callService("patcher", "list_dimmer_curves", []) },
+	/** Imports a fixture type from the user's fixture library into the show, updating the show's copy if it is older */
+	load_from_fixture_library(fixture_id: Uuid): Promise<{ Ok: null } | { Err: FixtureLibraryError }> { return callService("patcher", "load_from_fixture_library", [fixture_id]) },
+	/** Deletes a fixture type from the user's fixture library. Shows using the fixture keep their own copy. */
+	remove_from_fixture_library(fixture_id: Uuid): Promise<{ Ok: null } | { Err: FixtureLibraryError }> { return callService("patcher", "remove_from_fixture_library", [fixture_id]) },
+	/** Changes the number a fixture is referred to by */
+	renumber_fixture(fixture_id: Uuid, number: FixtureNumber | null): Promise<{ Ok: null } | { Err: RenumberFixtureError }> { return callService("patcher", "renumber_fixture", [fixture_id, number]) },
+	/** Assigns sequential numbers to several fixtures in the order given */
+	renumber_fixtures(fixture_ids: Uuid[], start: FixtureNumber, increment: number): Promise<{ Ok: null } | { Err: RenumberFixtureError }> { return callService("patcher", "renumber_fixtures", [fixture_ids, start, increment]) },
+	/** Converts a physical value, such as 90 degrees or 3200K, to the raw value of each fixture in a selection */
+	resolve_physical_values(fixture_ids: Uuid[], attribute_id: string, value: PhysicalValue): Promise<FxHashMap<Uuid, number>> { return callService("patcher", "resolve_physical_values", [fixture_ids, attribute_id, value]) },
+	/** Finds the value of a named segment, such as a gobo or color, for each fixture in a selection */
+	resolve_segment_values(fixture_ids: Uuid[], attribute_id: string, segment_name: string): Promise<FxHashMap<Uuid, number>> { return callService("patcher", "resolve_segment_values", [fixture_ids, attribute_id, segment_name]) },
+	/** Searches the user's fixture library, which is shared between shows */
+	search_fixture_library(query: string, manufacturer: string | null, family: string | null): Promise<LibraryEntry[]> { return callService("patcher", "search_fixture_library", [query, manufacturer, family]) },
+	/** Assigns a dimmer curve to a channel of every instance of a fixture type */
+	set_channel_dimmer_curve(fixture_type: Uuid, channel_id: string, curve_id: Uuid | null): Promise<{ Ok: null } | { Err: DimmerCurveError }> { return callService("patcher", "set_channel_dimmer_curve", [fixture_type, channel_id, curve_id]) },
+	/** Assigns a dimmer curve to a fixture's intensity channels */
+	set_fixture_dimmer_curve(fixture_id: Uuid, curve_id: Uuid | null): Promise<{ Ok: null } | { Err: DimmerCurveError }> { return callService("patcher", "set_fixture_dimmer_curve", [fixture_id, curve_id]) },
+	/** Replaces the adjustments applied to a fixture's output, such as inverted pan and tilt */
+	set_fixture_transforms(fixture_id: Uuid, transforms: FixtureTransforms): Promise<{ Ok: null } | { Err: FixtureTransformsError }> { return callService("patcher", "set_fixture_transforms", [fixture_id, transforms]) },
+	/** Enables or disables a strobe generated in the output stage, for fixtures without a shutter. The strobe is controlled through the `_strobe_rate` and `_strobe_duty` attributes. */
+	set_software_strobe(instance_ids: Uuid[], strobe: SoftwareStrobe | null): Promise<{ Ok: null } | { Err: SoftwareStrobeError }> { return callService("patcher", "set_software_strobe", [instance_ids, strobe]) },
 };
 
 export const saver = {